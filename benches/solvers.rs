@@ -0,0 +1,171 @@
+//! Benchmarks the generator and the parts of each day on the puzzle inputs in
+//! `input/2018`.
+//!
+//! The benchmarks are named `dayNN/generator`, `dayNN/part1` and
+//! `dayNN/part2`. Criterion writes the estimates of each benchmark to
+//! `target/criterion/dayNN/<name>/new/estimates.json` and reports a change in
+//! performance against a saved baseline:
+//!
+//! ```text
+//! cargo bench --bench solvers -- --save-baseline before
+//! cargo bench --bench solvers -- --baseline before
+//! ```
+//!
+//! Parts that are marked `slow` in `input/2018/answers.txt` are only
+//! benchmarked if the environment variable `AOC_BENCH_SLOW` is set.
+
+use std::{env, fs, path::PathBuf};
+
+use advent_of_code_2018::{error::ParseError, *};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const ANSWERS: &str = include_str!("../input/2018/answers.txt");
+
+/// The day and part of every answer whose heading is marked `slow`.
+fn slow_parts() -> Vec<(u8, u8)> {
+    ANSWERS
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.starts_with(' '))
+        .filter_map(|line| line.split(':').next())
+        .filter_map(
+            |heading| match heading.split_whitespace().collect::<Vec<_>>()[..] {
+                ["day", day, "part", part, "slow"] => Some((
+                    day.parse()
+                        .expect("the day of a slow part should be a number"),
+                    part.parse()
+                        .expect("the part of a slow part should be a number"),
+                )),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+fn read_input(day: u8) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("input/2018")
+        .join(format!("day{}.txt", day));
+    fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("can not read {}: {}", path.display(), err))
+}
+
+fn is_enabled(day: u8, part: u8) -> bool {
+    !slow_parts().contains(&(day, part)) || env::var_os("AOC_BENCH_SLOW").is_some()
+}
+
+/// The solvers of day 14 take the unparsed input.
+fn day14_input(input: &str) -> Result<&str, ParseError> {
+    day14::try_parse(input).map(|_| input)
+}
+
+macro_rules! bench_day {
+    ($c:expr, $day:literal, $parse:expr, $($part:literal => $solve:expr),+ $(,)?) => {{
+        let input = read_input($day);
+        let mut group = $c.benchmark_group(format!("day{:02}", $day));
+        group.bench_function("generator", |b| b.iter(|| $parse(black_box(&input))));
+        let parsed = $parse(&input).expect("the puzzle input should be valid");
+        $(
+            if is_enabled($day, $part) {
+                group.bench_function(concat!("part", $part), |b| {
+                    b.iter(|| $solve(black_box(&parsed)))
+                });
+            }
+        )+
+        group.finish();
+    }};
+}
+
+fn solvers(c: &mut Criterion) {
+    bench_day!(c, 1, day01::try_parse, 1 => day01::accumulate, 2 => day01::calibrate);
+    bench_day!(c, 2, day02::try_parse,
+        1 => day02::checksum,
+        2 => day02::search_prototype_boxes,
+    );
+    bench_day!(c, 3, day03::try_parse,
+        1 => day03::overlapping_area,
+        2 => day03::non_overlapping_claims,
+    );
+    bench_day!(c, 4, day04::try_parse, 1 => day04::strategy1, 2 => day04::strategy2);
+    bench_day!(c, 5, day05::try_parse,
+        1 => day05::reduced_polymer_len,
+        2 => day05::improved_polymer_len,
+    );
+    bench_day!(c, 6, day06::try_parse, 1 => day06::solve_part1, 2 => day06::solve_part2);
+    bench_day!(c, 7, day07::try_parse,
+        1 => day07::execution_order,
+        2 => day07::execution_time,
+    );
+    bench_day!(c, 8, day08::try_parse,
+        1 => day08::metadata_checksum,
+        2 => day08::value_of_license_root,
+    );
+    bench_day!(c, 9, day09::try_parse,
+        1 => day09::marble_highscore,
+        2 => day09::marble_highscore_100,
+    );
+    bench_day!(c, 10, day10::try_parse,
+        1 => day10::align_stars,
+        2 => day10::time_to_aligned_stars,
+    );
+    bench_day!(c, 11, day11::try_parse,
+        1 => day11::best_cell_group,
+        2 => day11::best_cell_group_size,
+    );
+    bench_day!(c, 12, day12::try_parse,
+        1 => day12::sum_of_pot_numbers_after_20_generations,
+        2 => day12::sum_of_pot_numbers_after_50_000_000_000_generations,
+    );
+    bench_day!(c, 13, day13::try_parse,
+        1 => day13::location_of_first_crash,
+        2 => day13::location_of_last_cart,
+    );
+    bench_day!(c, 14, day14_input,
+        1 => day14::score_seq_after_num_recipes,
+        2 => day14::num_needed_recipes,
+    );
+    bench_day!(c, 15, day15::try_parse, 1 => day15::fight, 2 => day15::fake_fight);
+    bench_day!(c, 16, day16::try_parse,
+        1 => day16::num_samples_behaving_like_three_or_more_opcodes,
+        2 => day16::run_program,
+    );
+    bench_day!(c, 17, day17::try_parse,
+        1 => day17::num_tiles_flooded_by_water,
+        2 => day17::num_tiles_flooded_by_water_after_spring_has_run_dry,
+    );
+    bench_day!(c, 18, day18::try_parse,
+        1 => day18::total_resource_value_after_10_minutes,
+        2 => day18::total_resource_value_after_1_000_000_000_minutes,
+    );
+    bench_day!(c, 19, day19::try_parse,
+        1 => day19::run_background_process,
+        2 => day19::run_background_process_2,
+    );
+    bench_day!(c, 20, day20::try_parse,
+        1 => day20::largest_number_of_doors,
+        2 => day20::num_rooms_at_least_1000_doors_away,
+    );
+    bench_day!(c, 21, day21::try_parse,
+        1 => day21::value_halting_after_fewest_instructions,
+        2 => day21::value_halting_after_most_instructions,
+    );
+    bench_day!(c, 22, day22::try_parse,
+        1 => day22::total_risk_level,
+        2 => day22::fewest_minutes_to_reach_target,
+    );
+    bench_day!(c, 23, day23::try_parse,
+        1 => day23::num_nanobots_in_range_of_strongest,
+        2 => day23::shortest_distance_to_best_coverage,
+    );
+    bench_day!(c, 24, day24::try_parse,
+        1 => day24::num_units_of_winning_army,
+        2 => day24::num_units_left_with_smallest_boost,
+    );
+    bench_day!(c, 25, day25::try_parse, 1 => day25::num_constellations);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = solvers
+}
+criterion_main!(benches);
@@ -0,0 +1,122 @@
+//! Runs the puzzle solvers without `cargo aoc`.
+//!
+//! ```text
+//! aoc2018 <day> [<part>] [<input>]
+//! aoc2018 all
+//! ```
+//!
+//! The input defaults to `input/2018/day<day>.txt`. Without a part both parts
+//! of the day are solved. Each answer is printed together with the time it
+//! took to parse the input and solve the part.
+
+use std::{
+    env, fs, process,
+    time::{Duration, Instant},
+};
+
+use advent_of_code_2018::registry::{self, registry, Solver};
+
+const USAGE: &str = "\
+usage: aoc2018 <day> [<part>] [<input>]
+       aoc2018 all";
+
+/// Returns the implemented parts of a day.
+fn parts(day: u8) -> Vec<u8> {
+    registry()
+        .iter()
+        .filter(|solver| solver.day() == day)
+        .map(Solver::part)
+        .collect()
+}
+
+fn default_input_path(day: u8) -> String {
+    format!("input/2018/day{}.txt", day)
+}
+
+/// Solves the given parts of a day and prints the answers. Returns `false` if
+/// any part failed.
+fn run(day: u8, parts: &[u8], input_path: &str) -> bool {
+    let input = match fs::read_to_string(input_path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Day {:2}: can not read {}: {}", day, input_path, err);
+            return false;
+        },
+    };
+    let mut success = true;
+    for &part in parts {
+        let start = Instant::now();
+        let answer = registry::solve(day, part, &input);
+        let elapsed = start.elapsed();
+        match answer {
+            Ok(answer) => print_answer(day, part, &answer, elapsed),
+            Err(err) => {
+                eprintln!("Day {:2} - Part {}: {}", day, part, err);
+                success = false;
+            },
+        }
+    }
+    success
+}
+
+fn print_answer(day: u8, part: u8, answer: &str, elapsed: Duration) {
+    if answer.contains('\n') {
+        println!(
+            "Day {:2} - Part {} ({:.2?}):\n{}",
+            day, part, elapsed, answer
+        );
+    } else {
+        println!(
+            "Day {:2} - Part {} ({:.2?}): {}",
+            day, part, elapsed, answer
+        );
+    }
+}
+
+fn parse_number(arg: &str, what: &str) -> Result<u8, String> {
+    arg.parse()
+        .map_err(|_| format!("{} must be a number but is {:?}", what, arg))
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let success = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["all"] => {
+            let failed = (1..=25)
+                .filter(|&day| !run(day, &parts(day), &default_input_path(day)))
+                .count();
+            failed == 0
+        },
+        [day, ref rest @ ..] if rest.len() <= 2 => {
+            let day = parse_number(day, "day").unwrap_or_else(|err| exit_with_usage(&err));
+            if !(1..=25).contains(&day) {
+                exit_with_usage(&format!("there is no puzzle for day {}", day));
+            }
+            let (part, input_path) = match rest {
+                [] => (None, None),
+                [arg] => match arg.parse() {
+                    Ok(part) => (Some(part), None),
+                    Err(_) => (None, Some(arg)),
+                },
+                [part, path] => (
+                    Some(parse_number(part, "part").unwrap_or_else(|err| exit_with_usage(&err))),
+                    Some(path),
+                ),
+                _ => unreachable!(),
+            };
+            let parts = part.map_or_else(|| parts(day), |part| vec![part]);
+            let input_path =
+                input_path.map_or_else(|| default_input_path(day), ToString::to_string);
+            run(day, &parts, &input_path)
+        },
+        _ => exit_with_usage("wrong number of arguments"),
+    };
+    if !success {
+        process::exit(1);
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2)
+}
@@ -60,44 +60,275 @@
 //!
 //! [Advent of Code 2018 - Day 1](https://adventofcode.com/2018/day/1)
 
-use std::{collections::HashSet, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{self, Display},
+    io::{self, BufRead},
+};
+
+use crate::error::{self, ParseError};
 
 pub type Frequency = i32;
 
+/// A frequency reached by accumulating changes.
+///
+/// It is wider than a single change, so that summing up even very long lists
+/// of changes does not overflow.
+pub type Total = i64;
+
 #[aoc_generator(day1)]
-pub fn parse(input: &str) -> Vec<Frequency> {
+pub fn parse(input: &str) -> Result<Vec<Frequency>, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Vec<Frequency>, ParseError> {
     input
         .lines()
-        .filter(|s| !s.is_empty())
         .enumerate()
-        .map(|(idx, text)| {
-            Frequency::from_str(text.trim()).unwrap_or_else(|_| {
-                panic!(
-                    "input text at line {:03} is not an i32 but is {:?}",
-                    idx + 1,
-                    text
-                )
-            })
-        })
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| error::number(1, idx + 1, line, line.trim(), "a frequency change"))
         .collect()
 }
 
 #[aoc(day1, part1)]
-pub fn accumulate(input: &[Frequency]) -> Frequency {
-    input.iter().sum()
+pub fn accumulate(input: &[Frequency]) -> Total {
+    input.iter().copied().map(Total::from).sum()
+}
+
+/// The first frequency the device reaches twice while it repeats the list of
+/// changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    pub frequency: Total,
+    /// The number of complete passes over the list before the repeat, so the
+    /// first pass is pass 0.
+    pub pass: usize,
+    /// The index of the change that produced the repeated frequency.
+    pub index: usize,
 }
 
 #[aoc(day1, part2)]
-pub fn calibrate(input: &[Frequency]) -> Frequency {
-    let mut history = HashSet::with_capacity(input.len());
-    let mut accumulated = 0;
-    history.insert(accumulated);
-    'rerun: loop {
-        for change in input {
-            accumulated += change;
-            if !history.insert(accumulated) {
-                break 'rerun accumulated;
+pub fn calibrate(input: &[Frequency]) -> Option<Total> {
+    first_repeat(input).map(|repeat| repeat.frequency)
+}
+
+/// Finds the first frequency reached twice without simulating the passes.
+///
+/// Let `s(k)` be the frequency after the first `k` changes of one pass and `d`
+/// the drift of a whole pass. After `p` passes the device reaches
+/// `s(k) + p * d`, so two frequencies can only meet if their `s(k)` are
+/// congruent modulo `d`. Within such a group, each `s(k)` is caught up by its
+/// neighbour in the direction of the drift after `|s(k') - s(k)| / |d|`
+/// passes. If no group has two members, no frequency is ever reached twice.
+pub fn first_repeat(changes: &[Frequency]) -> Option<Repeat> {
+    let num_changes = changes.len();
+    let mut sums = Vec::with_capacity(num_changes);
+    let mut accumulated: Total = 0;
+    let mut seen = HashSet::with_capacity(num_changes);
+    for (index, &change) in changes.iter().enumerate() {
+        if !seen.insert(accumulated) {
+            return Some(repeat(accumulated, num_changes, index));
+        }
+        sums.push(accumulated);
+        accumulated += Total::from(change);
+    }
+    let drift = accumulated;
+    if drift == 0 {
+        return if num_changes == 0 {
+            None
+        } else {
+            Some(repeat(0, num_changes, num_changes))
+        };
+    }
+
+    let mut groups: HashMap<Total, Vec<(Total, usize)>> = HashMap::new();
+    for (step, &sum) in sums.iter().enumerate() {
+        groups
+            .entry(sum.rem_euclid(drift.abs()))
+            .or_default()
+            .push((sum, step));
+    }
+    groups
+        .values_mut()
+        .flat_map(|group| {
+            group.sort_unstable();
+            group
+                .windows(2)
+                .map(|pair| {
+                    let ((lower, lower_step), (upper, upper_step)) = (pair[0], pair[1]);
+                    let passes = ((upper - lower) / drift.abs()) as u64;
+                    let (step, target) = if drift > 0 {
+                        (lower_step, upper)
+                    } else {
+                        (upper_step, lower)
+                    };
+                    (passes * num_changes as u64 + step as u64, target)
+                })
+                .collect::<Vec<_>>()
+        })
+        .min()
+        .map(|(time, frequency)| repeat(frequency, num_changes, time as usize))
+}
+
+/// The repeat of the given frequency reached after `time` changes in total.
+fn repeat(frequency: Total, num_changes: usize, time: usize) -> Repeat {
+    Repeat {
+        frequency,
+        pass: (time - 1) / num_changes,
+        index: (time - 1) % num_changes,
+    }
+}
+
+#[derive(Debug)]
+pub enum DeviceError {
+    Io(io::Error),
+    Parse(ParseError),
+    /// The frequency leaves the range of [`Total`] at the given line.
+    Overflow {
+        line: usize,
+    },
+}
+
+impl Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeviceError::Io(err) => write!(f, "can not read frequency changes: {}", err),
+            DeviceError::Parse(err) => write!(f, "{}", err),
+            DeviceError::Overflow { line } => {
+                write!(f, "line {}: the frequency is out of range", line)
+            },
+        }
+    }
+}
+
+impl Error for DeviceError {}
+
+impl From<io::Error> for DeviceError {
+    fn from(err: io::Error) -> Self {
+        DeviceError::Io(err)
+    }
+}
+
+impl From<ParseError> for DeviceError {
+    fn from(err: ParseError) -> Self {
+        DeviceError::Parse(err)
+    }
+}
+
+/// Applies frequency changes one at a time, so that the changes never need to
+/// be held in memory at once.
+///
+/// Besides the current frequency, the device keeps track of the lowest and the
+/// highest frequency and of the first frequency reached twice. To find the
+/// repeat, it remembers every frequency reached until then, but forgets them
+/// as soon as the repeat is found.
+///
+/// ```
+/// use advent_of_code_2018::day01::FrequencyDevice;
+///
+/// let mut device = FrequencyDevice::new();
+/// device.read("+1\n-2\n+3\n+1\n+1\n-2\n".as_bytes()).unwrap();
+///
+/// assert_eq!(device.frequency(), 2);
+/// assert_eq!(device.first_repeat().map(|repeat| repeat.frequency), Some(2));
+/// assert_eq!((device.min_frequency(), device.max_frequency()), (-1, 4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrequencyDevice {
+    frequency: Total,
+    min_frequency: Total,
+    max_frequency: Total,
+    num_changes: usize,
+    history: HashSet<Total>,
+    first_repeat: Option<Repeat>,
+}
+
+impl Default for FrequencyDevice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrequencyDevice {
+    /// Creates a device starting at frequency zero.
+    pub fn new() -> Self {
+        let mut history = HashSet::new();
+        history.insert(0);
+        Self {
+            frequency: 0,
+            min_frequency: 0,
+            max_frequency: 0,
+            num_changes: 0,
+            history,
+            first_repeat: None,
+        }
+    }
+
+    pub fn frequency(&self) -> Total {
+        self.frequency
+    }
+
+    /// The lowest frequency reached so far, including the start frequency.
+    pub fn min_frequency(&self) -> Total {
+        self.min_frequency
+    }
+
+    /// The highest frequency reached so far, including the start frequency.
+    pub fn max_frequency(&self) -> Total {
+        self.max_frequency
+    }
+
+    /// The number of changes applied so far.
+    pub fn num_changes(&self) -> usize {
+        self.num_changes
+    }
+
+    /// The first frequency reached twice so far. As the changes are applied
+    /// only once, the repeat is always found in pass 0.
+    pub fn first_repeat(&self) -> Option<Repeat> {
+        self.first_repeat
+    }
+
+    /// Applies a single change, unless the frequency would leave the range of
+    /// [`Total`]. Returns the new frequency.
+    pub fn apply(&mut self, change: Frequency) -> Option<Total> {
+        let frequency = self.frequency.checked_add(Total::from(change))?;
+        self.frequency = frequency;
+        self.min_frequency = self.min_frequency.min(frequency);
+        self.max_frequency = self.max_frequency.max(frequency);
+        if self.first_repeat.is_none() && !self.history.insert(frequency) {
+            self.first_repeat = Some(Repeat {
+                frequency,
+                pass: 0,
+                index: self.num_changes,
+            });
+            self.history = HashSet::new();
+        }
+        self.num_changes += 1;
+        Some(frequency)
+    }
+
+    /// Applies the changes read from the reader, one per line, in the format
+    /// of the puzzle input. Empty lines are skipped.
+    ///
+    /// Line numbers in errors start at 1 for the first line of this reader.
+    /// The changes before an erroneous line remain applied.
+    pub fn read(&mut self, mut reader: impl BufRead) -> Result<(), DeviceError> {
+        let mut buffer = String::new();
+        let mut line = 0;
+        loop {
+            buffer.clear();
+            if reader.read_line(&mut buffer)? == 0 {
+                return Ok(());
+            }
+            line += 1;
+            let text = buffer.trim_end_matches('\n').trim_end_matches('\r');
+            if text.is_empty() {
+                continue;
             }
+            let change = error::number(1, line, text, text.trim(), "a frequency change")?;
+            self.apply(change).ok_or(DeviceError::Overflow { line })?;
         }
     }
 }
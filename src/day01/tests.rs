@@ -6,11 +6,23 @@ const INPUT: &str = include_str!("../../input/2018/day1.txt");
 fn parse_input() {
     let input = "1\n -2\n3 \n1";
 
-    let answer = parse(input);
+    let answer = parse(input).unwrap();
 
     assert_eq!(answer, vec![1, -2, 3, 1]);
 }
 
+#[test]
+fn try_parse_reports_invalid_line() {
+    let input = "+1\n-2\n+x\n";
+
+    let result = try_parse(input);
+
+    assert_eq!(
+        result,
+        Err(ParseError::new(1, 3, 1, "a frequency change", "\"+x\""))
+    );
+}
+
 mod part1 {
     use super::*;
 
@@ -52,7 +64,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let answer = accumulate(&parse(INPUT));
+        let answer = accumulate(&parse(INPUT).unwrap());
 
         assert_eq!(answer, 445);
     }
@@ -67,7 +79,7 @@ mod part2 {
 
         let answer = calibrate(&input);
 
-        assert_eq!(answer, 2);
+        assert_eq!(answer, Some(2));
     }
 
     #[test]
@@ -76,7 +88,7 @@ mod part2 {
 
         let answer = calibrate(&input);
 
-        assert_eq!(answer, 0);
+        assert_eq!(answer, Some(0));
     }
 
     #[test]
@@ -85,7 +97,7 @@ mod part2 {
 
         let answer = calibrate(&input);
 
-        assert_eq!(answer, 10);
+        assert_eq!(answer, Some(10));
     }
 
     #[test]
@@ -94,7 +106,7 @@ mod part2 {
 
         let answer = calibrate(&input);
 
-        assert_eq!(answer, 5);
+        assert_eq!(answer, Some(5));
     }
 
     #[test]
@@ -103,13 +115,198 @@ mod part2 {
 
         let answer = calibrate(&input);
 
-        assert_eq!(answer, 14);
+        assert_eq!(answer, Some(14));
     }
 
     #[test]
     fn answer() {
-        let answer = calibrate(&parse(INPUT));
+        let answer = calibrate(&parse(INPUT).unwrap());
+
+        assert_eq!(answer, Some(219));
+    }
+}
+
+mod part1_overflow {
+    use super::*;
+
+    #[test]
+    fn sum_beyond_range_of_a_change() {
+        let input = vec![Frequency::MAX, Frequency::MAX, 2];
+
+        let answer = accumulate(&input);
+
+        assert_eq!(answer, 4_294_967_296);
+    }
+}
+
+mod first_repeat {
+    use super::*;
+    use proptest::collection::vec;
+
+    /// Simulates the passes over the list like the device does.
+    fn simulate(changes: &[Frequency], max_passes: usize) -> Option<Repeat> {
+        let mut history = HashSet::new();
+        let mut accumulated = 0;
+        history.insert(accumulated);
+        for pass in 0..max_passes {
+            for (index, &change) in changes.iter().enumerate() {
+                accumulated += Total::from(change);
+                if !history.insert(accumulated) {
+                    return Some(Repeat {
+                        frequency: accumulated,
+                        pass,
+                        index,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn repeat_in_second_pass() {
+        let input = vec![1, -2, 3, 1];
+
+        let repeat = first_repeat(&input);
+
+        assert_eq!(
+            repeat,
+            Some(Repeat {
+                frequency: 2,
+                pass: 1,
+                index: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn repeat_in_first_pass() {
+        let input = vec![3, -1, -2, 5];
+
+        let repeat = first_repeat(&input);
+
+        assert_eq!(
+            repeat,
+            Some(Repeat {
+                frequency: 0,
+                pass: 0,
+                index: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn repeat_after_many_passes() {
+        let input = vec![1_000_000, -999_999];
+
+        let repeat = first_repeat(&input);
+
+        assert_eq!(
+            repeat,
+            Some(Repeat {
+                frequency: 1_000_000,
+                pass: 999_999,
+                index: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn no_repeat_with_steady_drift() {
+        let input = vec![1, 1];
+
+        let repeat = first_repeat(&input);
+
+        assert_eq!(repeat, None);
+    }
+
+    #[test]
+    fn no_repeat_without_changes() {
+        let repeat = first_repeat(&[]);
+
+        assert_eq!(repeat, None);
+    }
+
+    proptest! {
+        #[test]
+        fn finds_same_repeat_as_simulation(changes in vec(-20..=20 as Frequency, 1..10)) {
+            // Within a group no two frequencies are more than 400 apart, so a
+            // repeat happens within 400 passes if it happens at all.
+            let simulated = simulate(&changes, 401);
+
+            prop_assert_eq!(first_repeat(&changes), simulated);
+        }
+    }
+}
+
+mod frequency_device {
+    use super::*;
+
+    use std::io::BufReader;
+
+    #[test]
+    fn read_changes() {
+        let mut device = FrequencyDevice::new();
+
+        device.read("+1\n-2\n+3\n-2\n+5\n".as_bytes()).unwrap();
+
+        assert_eq!(device.frequency(), 5);
+        assert_eq!(device.num_changes(), 5);
+        assert_eq!(device.min_frequency(), -1);
+        assert_eq!(device.max_frequency(), 5);
+        assert_eq!(
+            device.first_repeat(),
+            Some(Repeat {
+                frequency: 0,
+                pass: 0,
+                index: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn read_from_several_readers() {
+        let mut device = FrequencyDevice::new();
+
+        device.read("-6\r\n+3\r\n\r\n".as_bytes()).unwrap();
+        device.read("+8".as_bytes()).unwrap();
+
+        assert_eq!(device.frequency(), 5);
+        assert_eq!(device.min_frequency(), -6);
+        assert_eq!(device.first_repeat(), None);
+    }
+
+    #[test]
+    fn read_same_as_parsed_input() {
+        let mut device = FrequencyDevice::new();
+
+        device.read(BufReader::new(INPUT.as_bytes())).unwrap();
+
+        assert_eq!(device.frequency(), accumulate(&parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn read_reports_invalid_line() {
+        let mut device = FrequencyDevice::new();
+
+        let result = device.read("+1\n\n+x\n+2\n".as_bytes());
+
+        assert!(matches!(
+            result,
+            Err(DeviceError::Parse(err)) if err == ParseError::new(1, 3, 1, "a frequency change", "\"+x\"")
+        ));
+        assert_eq!(device.frequency(), 1);
+    }
+
+    #[test]
+    fn read_reports_overflow() {
+        let mut device = FrequencyDevice::new();
+        device.frequency = Total::MAX - 3;
+
+        let result = device.read("+1\n+2\n+3\n".as_bytes());
 
-        assert_eq!(answer, 219);
+        assert!(matches!(result, Err(DeviceError::Overflow { line: 3 })));
+        assert_eq!(device.frequency(), Total::MAX);
+        assert_eq!(device.max_frequency(), Total::MAX);
     }
 }
@@ -72,62 +72,162 @@
 
 use std::collections::HashMap;
 
+use crate::error::ParseError;
+
 #[aoc_generator(day2)]
-pub fn parse(input: &str) -> Vec<String> {
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Vec<String>, ParseError> {
     input
         .lines()
-        .filter(|s| !s.is_empty())
-        .map(ToOwned::to_owned)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(
+            |(idx, line)| match line.find(|chr: char| !chr.is_ascii_lowercase()) {
+                Some(pos) => Err(ParseError::at(
+                    2,
+                    idx + 1,
+                    line,
+                    &line[pos..],
+                    "a box ID of lowercase letters",
+                )),
+                None => Ok(line.to_owned()),
+            },
+        )
         .collect()
 }
 
+/// Counts how often each letter occurs in a box ID.
+pub fn letter_histogram(id: &str) -> HashMap<char, usize> {
+    let mut letter_counts = HashMap::with_capacity(32);
+    for letter in id.chars() {
+        *letter_counts.entry(letter).or_insert(0) += 1;
+    }
+    letter_counts
+}
+
+/// Counts for each of the given repeat counts the box IDs that contain at
+/// least one letter occurring exactly that often.
+pub fn num_ids_with_repeats(box_ids: &[String], repeats: &[usize]) -> Vec<usize> {
+    box_ids
+        .iter()
+        .fold(vec![0; repeats.len()], |mut totals, id| {
+            let letter_counts = letter_histogram(id);
+            for (total, repeat) in totals.iter_mut().zip(repeats) {
+                if letter_counts.values().any(|count| count == repeat) {
+                    *total += 1;
+                }
+            }
+            totals
+        })
+}
+
+/// Multiplies the numbers of box IDs with a letter occurring exactly as often
+/// as each of the given repeat counts.
+pub fn checksum_of_repeats(box_ids: &[String], repeats: &[usize]) -> usize {
+    num_ids_with_repeats(box_ids, repeats).into_iter().product()
+}
+
 #[aoc(day2, part1)]
-pub fn checksum(box_ids: &[String]) -> u32 {
-    let (twos_count, threes_count) = box_ids.iter().fold((0, 0), |(twos, threes), id| {
-        let mut letter_counts = HashMap::with_capacity(32);
-
-        for letter in id.chars() {
-            letter_counts
-                .entry(letter)
-                .and_modify(|c| {
-                    *c += 1;
-                })
-                .or_insert(1);
+pub fn checksum(box_ids: &[String]) -> usize {
+    checksum_of_repeats(box_ids, &[2, 3])
+}
+
+/// Two box IDs of the same length that differ in few positions.
+///
+/// `first` and `second` are the indices of the IDs in the searched list, with
+/// `first` less than `second`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NearDuplicate {
+    pub first: usize,
+    pub second: usize,
+    pub mismatches: usize,
+}
+
+/// Splits an ID of the given length into `max_mismatches + 1` segments of
+/// nearly the same length and returns their bounds.
+///
+/// IDs with no more letters than allowed mismatches get an empty segment, which
+/// all IDs of that length share.
+fn segment_bounds(len: usize, max_mismatches: usize) -> impl Iterator<Item = (usize, usize)> {
+    let num_parts = max_mismatches.min(len) + 1;
+    (0..num_parts).map(move |part| (part * len / num_parts, (part + 1) * len / num_parts))
+}
+
+fn count_mismatches(id1: &[u8], id2: &[u8], max_mismatches: usize) -> Option<usize> {
+    let mut mismatches = 0;
+    for (byte1, byte2) in id1.iter().zip(id2) {
+        if byte1 != byte2 {
+            mismatches += 1;
+            if mismatches > max_mismatches {
+                return None;
+            }
         }
-        let twos = if letter_counts.values().any(|c| *c == 2) {
-            twos + 1
-        } else {
-            twos
-        };
-        let threes = if letter_counts.values().any(|c| *c == 3) {
-            threes + 1
-        } else {
-            threes
-        };
-
-        (twos, threes)
-    });
-
-    twos_count * threes_count
+    }
+    Some(mismatches)
 }
 
-#[aoc(day2, part2)]
-pub fn search_prototype_boxes(box_ids: &[String]) -> String {
-    let mut found_id = None;
-    'id_search: for (idx, id1) in box_ids.iter().enumerate() {
-        for id2 in box_ids.iter().skip(idx + 1) {
-            let mut common_letters = String::with_capacity(id1.len());
-            id1.chars()
-                .zip(id2.chars())
-                .filter_map(|(chr1, chr2)| if chr1 == chr2 { Some(chr1) } else { None })
-                .for_each(|chr| common_letters.push(chr));
-            if common_letters.len() + 1 == id1.len() {
-                found_id = Some(common_letters);
-                break 'id_search;
+/// Finds all pairs of box IDs of the same length that differ in at most
+/// `max_mismatches` positions, ordered by the indices of the IDs.
+///
+/// Each ID is split into `max_mismatches + 1` segments. Two IDs with at most
+/// that many mismatches have at least one segment in common, so only IDs
+/// sharing a segment at the same position are compared. A pair is reported
+/// only for the first segment it shares.
+pub fn near_duplicates(box_ids: &[String], max_mismatches: usize) -> Vec<NearDuplicate> {
+    let mut buckets: HashMap<(usize, usize, &[u8]), Vec<usize>> = HashMap::new();
+    for (idx, id) in box_ids.iter().enumerate() {
+        let id = id.as_bytes();
+        for (part, (start, end)) in segment_bounds(id.len(), max_mismatches).enumerate() {
+            buckets
+                .entry((id.len(), part, &id[start..end]))
+                .or_default()
+                .push(idx);
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for (&(len, part, _), indices) in &buckets {
+        for (pos, &first) in indices.iter().enumerate() {
+            let id1 = box_ids[first].as_bytes();
+            for &second in &indices[pos + 1..] {
+                let id2 = box_ids[second].as_bytes();
+                let shares_earlier_segment = segment_bounds(len, max_mismatches)
+                    .take(part)
+                    .any(|(start, end)| id1[start..end] == id2[start..end]);
+                if shares_earlier_segment {
+                    continue;
+                }
+                if let Some(mismatches) = count_mismatches(id1, id2, max_mismatches) {
+                    pairs.push(NearDuplicate {
+                        first,
+                        second,
+                        mismatches,
+                    });
+                }
             }
         }
     }
-    found_id.unwrap_or_else(|| "no correct box id found".to_owned())
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Returns the letters at the positions where both IDs are equal.
+pub fn common_letters(id1: &str, id2: &str) -> String {
+    id1.chars()
+        .zip(id2.chars())
+        .filter_map(|(chr1, chr2)| if chr1 == chr2 { Some(chr1) } else { None })
+        .collect()
+}
+
+#[aoc(day2, part2)]
+pub fn search_prototype_boxes(box_ids: &[String]) -> Option<String> {
+    near_duplicates(box_ids, 1)
+        .into_iter()
+        .find(|pair| pair.mismatches == 1)
+        .map(|pair| common_letters(&box_ids[pair.first], &box_ids[pair.second]))
 }
 
 #[cfg(test)]
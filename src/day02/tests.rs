@@ -22,12 +22,34 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let answer = checksum(&parse(INPUT));
+        let answer = checksum(&parse(INPUT).unwrap());
 
         assert_eq!(answer, 5681);
     }
 }
 
+mod repeats {
+    use super::*;
+
+    #[test]
+    fn count_ids_with_repeated_letters() {
+        let input = prepare(vec!["aabbbc", "abcd", "aaaa", "abab"]);
+
+        let counts = num_ids_with_repeats(&input, &[1, 2, 3, 4]);
+
+        assert_eq!(counts, vec![2, 2, 1, 1]);
+    }
+
+    #[test]
+    fn checksum_of_letters_occurring_once_or_twice() {
+        let input = prepare(vec!["aabbbc", "abcd", "aaaa", "abab"]);
+
+        let answer = checksum_of_repeats(&input, &[1, 2]);
+
+        assert_eq!(answer, 4);
+    }
+}
+
 mod part2 {
     use super::*;
 
@@ -39,13 +61,109 @@ mod part2 {
 
         let answer = search_prototype_boxes(&input);
 
-        assert_eq!(answer, "fgij");
+        assert_eq!(answer, Some("fgij".to_owned()));
+    }
+
+    #[test]
+    fn no_correct_boxes() {
+        let input = prepare(vec!["abcde", "axcye", "abcd"]);
+
+        let answer = search_prototype_boxes(&input);
+
+        assert_eq!(answer, None);
     }
 
     #[test]
     fn answer() {
-        let answer = search_prototype_boxes(&parse(INPUT));
+        let answer = search_prototype_boxes(&parse(INPUT).unwrap());
+
+        assert_eq!(answer, Some("uqyoeizfvmbistpkgnocjtwld".to_owned()));
+    }
+}
+
+mod near_duplicates {
+    use super::*;
+    use proptest::collection::vec;
+
+    fn brute_force(box_ids: &[String], max_mismatches: usize) -> Vec<NearDuplicate> {
+        let mut pairs = Vec::new();
+        for (first, id1) in box_ids.iter().enumerate() {
+            for (second, id2) in box_ids.iter().enumerate().skip(first + 1) {
+                if id1.len() != id2.len() {
+                    continue;
+                }
+                let mismatches = id1.bytes().zip(id2.bytes()).filter(|(a, b)| a != b).count();
+                if mismatches <= max_mismatches {
+                    pairs.push(NearDuplicate {
+                        first,
+                        second,
+                        mismatches,
+                    });
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn pairs_with_up_to_two_mismatches() {
+        let input = prepare(vec![
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ]);
+
+        let pairs = near_duplicates(&input, 2);
+
+        assert_eq!(
+            pairs,
+            vec![
+                NearDuplicate {
+                    first: 0,
+                    second: 5,
+                    mismatches: 2,
+                },
+                NearDuplicate {
+                    first: 1,
+                    second: 4,
+                    mismatches: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn exact_duplicates() {
+        let input = prepare(vec!["abc", "abd", "abc", "ab", "abc"]);
+
+        let pairs = near_duplicates(&input, 0);
+
+        assert_eq!(
+            pairs
+                .iter()
+                .map(|pair| (pair.first, pair.second))
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (0, 4), (2, 4)]
+        );
+    }
+
+    #[test]
+    fn more_mismatches_than_letters() {
+        let input = prepare(vec!["ab", "cd", "", ""]);
+
+        let pairs = near_duplicates(&input, 5);
+
+        assert_eq!(pairs, brute_force(&input, 5));
+        assert_eq!(pairs.len(), 2);
+    }
+
+    proptest! {
+        #[test]
+        fn finds_same_pairs_as_brute_force(
+            box_ids in vec("[ab]{0,6}", 0..30),
+            max_mismatches in 0..8usize,
+        ) {
+            let pairs = near_duplicates(&box_ids, max_mismatches);
 
-        assert_eq!(answer, "uqyoeizfvmbistpkgnocjtwld");
+            prop_assert_eq!(pairs, brute_force(&box_ids, max_mismatches));
+        }
     }
 }
@@ -80,11 +80,30 @@
 //!
 //! [Advent of Code 2018 - Day 3](https://adventofcode.com/2018/day/3)
 
-use std::{collections::HashSet, iter::FromIterator, num::ParseIntError, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    io::{self, Write},
+    iter::FromIterator,
+    num::ParseIntError,
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{error, grid::Pos, union_find::UnionFind};
 
 pub type ClaimId = u32;
 
+/// The coordinates of a square inch of fabric.
+///
+/// They are wider than the coordinates of a claim, because a claim may reach
+/// beyond its left or top edge plus its width or height.
+pub type Coord = u32;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Claim {
     id: ClaimId,
     left: u16,
@@ -93,6 +112,115 @@ pub struct Claim {
     height: u16,
 }
 
+impl Claim {
+    pub fn new(id: ClaimId, left: u16, top: u16, width: u16, height: u16) -> Self {
+        Self {
+            id,
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+
+    pub fn id(&self) -> ClaimId {
+        self.id
+    }
+
+    /// The left edge of the claim, which is within the claim.
+    pub fn left(&self) -> Coord {
+        Coord::from(self.left)
+    }
+
+    /// The right edge of the claim, which is just outside of the claim.
+    pub fn right(&self) -> Coord {
+        self.left() + self.width()
+    }
+
+    /// The top edge of the claim, which is within the claim.
+    pub fn top(&self) -> Coord {
+        Coord::from(self.top)
+    }
+
+    /// The bottom edge of the claim, which is just outside of the claim.
+    pub fn bottom(&self) -> Coord {
+        self.top() + self.height()
+    }
+
+    pub fn width(&self) -> Coord {
+        Coord::from(self.width)
+    }
+
+    pub fn height(&self) -> Coord {
+        Coord::from(self.height)
+    }
+
+    pub fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    pub fn rectangle(&self) -> Rectangle {
+        Rectangle {
+            left: self.left(),
+            top: self.top(),
+            right: self.right(),
+            bottom: self.bottom(),
+        }
+    }
+
+    /// Returns the square inches within both claims.
+    pub fn intersection(&self, other: &Claim) -> Option<Rectangle> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        Some(Rectangle {
+            left: self.left().max(other.left()),
+            top: self.top().max(other.top()),
+            right: self.right().min(other.right()),
+            bottom: self.bottom().min(other.bottom()),
+        })
+    }
+
+    /// Whether the square inch at the given position is within the claim.
+    pub fn contains(&self, pos: Pos<Coord>) -> bool {
+        (self.left()..self.right()).contains(&pos.x) && (self.top()..self.bottom()).contains(&pos.y)
+    }
+
+    /// Whether both claims share at least one square inch.
+    pub fn overlaps(&self, other: &Claim) -> bool {
+        self.area() > 0
+            && other.area() > 0
+            && self.left() < other.right()
+            && other.left() < self.right()
+            && self.top() < other.bottom()
+            && other.top() < self.bottom()
+    }
+}
+
+/// A rectangle of square inches. Like the edges of a claim, the left and top
+/// edge are within the rectangle, the right and bottom edge just outside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rectangle {
+    pub left: Coord,
+    pub top: Coord,
+    pub right: Coord,
+    pub bottom: Coord,
+}
+
+impl Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{}: {}x{}",
+            self.left,
+            self.top,
+            self.right - self.left,
+            self.bottom - self.top
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Token {
     Id,
@@ -175,94 +303,350 @@ impl FromStr for Claim {
 }
 
 #[aoc_generator(day3)]
-pub fn parse(input: &str) -> Vec<Claim> {
+pub fn parse(input: &str) -> Result<Vec<Claim>, error::ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Vec<Claim>, error::ParseError> {
     input
         .lines()
         .enumerate()
-        .map(|(idx, line)| match Claim::from_str(line) {
-            Ok(claim) => claim,
-            Err(err) => panic!("error at line {}: {:?}", idx + 1, err),
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            Claim::from_str(line).map_err(|_| {
+                error::ParseError::at(3, idx + 1, line, line, "a claim like \"#1 @ 1,3: 4x4\"")
+            })
         })
         .collect()
 }
 
-#[aoc(day3, part1)]
-pub fn overlapping_area(input: &[Claim]) -> usize {
-    let mut overlapping_pieces = HashSet::with_capacity(32);
-
-    for (idx, claim1) in input.iter().enumerate() {
-        let a1 = claim1.left;
-        let b1 = claim1.left + claim1.width;
-        let c1 = claim1.top;
-        let d1 = claim1.top + claim1.height;
-
-        for claim2 in input.iter().skip(idx + 1) {
-            let a2 = claim2.left;
-            let b2 = claim2.left + claim2.width;
-            let c2 = claim2.top;
-            let d2 = claim2.top + claim2.height;
-
-            if let Some((x1, x2, y1, y2)) = if a2 >= a1 && a2 <= b1 {
-                if c2 >= c1 && c2 <= d1 {
-                    Some((a2, b1.min(b2), c2, d1.min(d2)))
-                } else if c2 <= c1 && d2 >= c1 {
-                    Some((a2, b1.min(b2), c1, d1.min(d2)))
-                } else {
-                    None
-                }
-            } else if a2 <= a1 && b2 >= a1 {
-                if c2 >= c1 && c2 <= d1 {
-                    Some((a1, b1.min(b2), c2, d1.min(d2)))
-                } else if c2 <= c1 && d2 >= c1 {
-                    Some((a1, b1.min(b2), c1, d1.min(d2)))
-                } else {
-                    None
-                }
-            } else {
-                None
-            } {
-                for y in y1..y2 {
-                    for x in x1..x2 {
-                        overlapping_pieces.insert((x, y));
+/// A piece of fabric with claims on it.
+///
+/// The claims are indexed by square tiles of the fabric, so that only claims
+/// on the same tiles are compared. The size of the tiles is about the average
+/// size of the claims, so that most claims lie on a few tiles only.
+#[derive(Debug, Clone)]
+pub struct Fabric {
+    claims: Vec<Claim>,
+    tile_size: Coord,
+    tiles: HashMap<(Coord, Coord), Vec<usize>>,
+}
+
+impl FromIterator<Claim> for Fabric {
+    fn from_iter<I: IntoIterator<Item = Claim>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl Fabric {
+    pub fn new(claims: Vec<Claim>) -> Self {
+        let (num_claims, total_size) = claims
+            .iter()
+            .filter(|claim| claim.area() > 0)
+            .fold((0u64, 0u64), |(count, total), claim| {
+                (count + 1, total + u64::from(claim.width.max(claim.height)))
+            });
+        let tile_size = (total_size / num_claims.max(1)).max(1) as Coord;
+        let mut tiles = HashMap::<_, Vec<_>>::new();
+        for (idx, claim) in claims.iter().enumerate() {
+            for tile in tiles_of(claim, tile_size) {
+                tiles.entry(tile).or_default().push(idx);
+            }
+        }
+        Self {
+            claims,
+            tile_size,
+            tiles,
+        }
+    }
+
+    pub fn claims(&self) -> &[Claim] {
+        &self.claims
+    }
+
+    /// Returns the claims containing the square inch at the given position.
+    pub fn claims_at(&self, pos: Pos<Coord>) -> impl Iterator<Item = &Claim> {
+        self.tiles
+            .get(&(pos.x / self.tile_size, pos.y / self.tile_size))
+            .into_iter()
+            .flatten()
+            .map(move |&idx| &self.claims[idx])
+            .filter(move |claim| claim.contains(pos))
+    }
+
+    /// Returns whether the claim with the given index overlaps any other
+    /// claim.
+    fn is_overlapped(&self, idx: usize) -> bool {
+        let claim = &self.claims[idx];
+        tiles_of(claim, self.tile_size)
+            .filter_map(|tile| self.tiles.get(&tile))
+            .flatten()
+            .any(|&other| other != idx && claim.overlaps(&self.claims[other]))
+    }
+
+    /// Returns the claims that do not overlap any other claim in the order
+    /// they were made.
+    pub fn intact_claims(&self) -> Vec<&Claim> {
+        (0..self.claims.len())
+            .filter(|&idx| !self.is_overlapped(idx))
+            .map(|idx| &self.claims[idx])
+            .collect()
+    }
+
+    /// Returns the number of square inches within two or more claims.
+    ///
+    /// A vertical line sweeps over the fabric from left to right, stopping at
+    /// the left and right edges of the claims. Between two stops, the length
+    /// of the line within two or more claims does not change.
+    pub fn overlapping_area(&self) -> u64 {
+        let mut events = Vec::with_capacity(2 * self.claims.len());
+        let mut edges = Vec::with_capacity(2 * self.claims.len());
+        for claim in self.claims.iter().filter(|claim| claim.area() > 0) {
+            events.push((claim.left(), 1, claim.top(), claim.bottom()));
+            events.push((claim.right(), -1, claim.top(), claim.bottom()));
+            edges.push(claim.top());
+            edges.push(claim.bottom());
+        }
+        events.sort_unstable();
+        edges.sort_unstable();
+        edges.dedup();
+
+        let mut coverage = Coverage::new(edges);
+        let mut area = 0;
+        let mut last_x = 0;
+        for (x, change, top, bottom) in events {
+            area += coverage.length_covered_twice() * u64::from(x - last_x);
+            coverage.add(top, bottom, change);
+            last_x = x;
+        }
+        area
+    }
+
+    /// Returns all pairs of overlapping claims ordered by the positions of the
+    /// claims.
+    ///
+    /// A pair is found on the tile with the top left corner of the
+    /// intersection, so that it is reported only once.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for (&tile, indices) in &self.tiles {
+            for (pos, &first) in indices.iter().enumerate() {
+                for &second in &indices[pos + 1..] {
+                    let (claim1, claim2) = (&self.claims[first], &self.claims[second]);
+                    let intersection = match claim1.intersection(claim2) {
+                        Some(intersection) => intersection,
+                        None => continue,
+                    };
+                    let corner = (
+                        intersection.left / self.tile_size,
+                        intersection.top / self.tile_size,
+                    );
+                    if corner == tile {
+                        conflicts.push((first.min(second), first.max(second), intersection));
                     }
                 }
             }
         }
+        conflicts.sort_unstable_by_key(|&(first, second, _)| (first, second));
+        conflicts
+            .into_iter()
+            .map(|(first, second, intersection)| Conflict {
+                first: self.claims[first].id,
+                second: self.claims[second].id,
+                intersection,
+            })
+            .collect()
     }
+}
 
-    overlapping_pieces.len()
+/// Two claims sharing the square inches of their intersection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Conflict {
+    pub first: ClaimId,
+    pub second: ClaimId,
+    pub intersection: Rectangle,
 }
 
-#[aoc(day3, part2)]
-pub fn non_overlapping_claims(input: &[Claim]) -> ClaimId {
-    let mut non_overlapping_claims: HashSet<ClaimId> =
-        HashSet::from_iter(input.iter().map(|claim| claim.id));
-
-    for (idx, claim1) in input.iter().enumerate() {
-        let a1 = claim1.left;
-        let b1 = claim1.left + claim1.width - 1;
-        let c1 = claim1.top;
-        let d1 = claim1.top + claim1.height - 1;
-
-        for claim2 in input.iter().skip(idx + 1) {
-            let a2 = claim2.left;
-            let b2 = claim2.left + claim2.width - 1;
-            let c2 = claim2.top;
-            let d2 = claim2.top + claim2.height - 1;
-
-            if ((a2 >= a1 && a2 <= b1) && ((c2 >= c1 && c2 <= d1) || (c2 <= c1 && d2 >= c1)))
-                || ((a2 <= a1 && b2 >= a1) && ((c2 >= c1 && c2 <= d1) || (c2 <= c1 && d2 >= c1)))
-            {
-                non_overlapping_claims.remove(&claim1.id);
-                non_overlapping_claims.remove(&claim2.id);
-            }
+/// The claims connected by an edge for each pair of overlapping claims.
+///
+/// The claims are told apart by their IDs, so the IDs should be unique.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConflictGraph {
+    claims: Vec<Claim>,
+    conflicts: Vec<Conflict>,
+}
+
+impl ConflictGraph {
+    pub fn new(claims: Vec<Claim>) -> Self {
+        let fabric = Fabric::new(claims);
+        let conflicts = fabric.conflicts();
+        Self {
+            claims: fabric.claims,
+            conflicts,
         }
     }
 
-    non_overlapping_claims
-        .into_iter()
-        .next()
-        .expect("no non overlapping claim found")
+    pub fn claims(&self) -> &[Claim] {
+        &self.claims
+    }
+
+    /// Returns the edges of the graph ordered by the positions of the claims.
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.conflicts
+    }
+
+    /// Returns the conflicts the claim with the given ID is involved in.
+    pub fn conflicts_of(&self, id: ClaimId) -> impl Iterator<Item = &Conflict> {
+        self.conflicts
+            .iter()
+            .filter(move |conflict| conflict.first == id || conflict.second == id)
+    }
+
+    /// Returns the groups of claims connected by conflicts, including claims
+    /// without any conflict as groups of their own.
+    ///
+    /// The groups are ordered by their first claim and the claims of a group
+    /// by their position in the list of claims.
+    pub fn components(&self) -> Vec<Vec<ClaimId>> {
+        let positions = self
+            .claims
+            .iter()
+            .enumerate()
+            .map(|(idx, claim)| (claim.id, idx))
+            .collect::<HashMap<_, _>>();
+        let mut union_find = UnionFind::new(self.claims.len());
+        for conflict in &self.conflicts {
+            union_find.union(positions[&conflict.first], positions[&conflict.second]);
+        }
+        union_find
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().map(|idx| self.claims[idx].id).collect())
+            .collect()
+    }
+
+    /// Writes the graph in the DOT language of Graphviz. Each edge is
+    /// labeled with the intersection of its claims.
+    pub fn write_dot(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "graph claims {{")?;
+        for claim in &self.claims {
+            writeln!(writer, "    {};", claim.id)?;
+        }
+        for conflict in &self.conflicts {
+            writeln!(
+                writer,
+                "    {} -- {} [label=\"{}\"];",
+                conflict.first, conflict.second, conflict.intersection
+            )?;
+        }
+        writeln!(writer, "}}")?;
+        writer.flush()
+    }
+}
+
+/// Returns the tiles the claim lies on.
+fn tiles_of(claim: &Claim, tile_size: Coord) -> impl Iterator<Item = (Coord, Coord)> {
+    let (left, top) = (claim.left() / tile_size, claim.top() / tile_size);
+    let (right, bottom) = if claim.area() > 0 {
+        (
+            (claim.right() - 1) / tile_size + 1,
+            (claim.bottom() - 1) / tile_size + 1,
+        )
+    } else {
+        (left, top)
+    };
+    (top..bottom).flat_map(move |y| (left..right).map(move |x| (x, y)))
+}
+
+/// A segment tree counting how often each section of the sweep line is
+/// covered by claims.
+#[derive(Debug, Clone)]
+struct Coverage {
+    /// The edges of the claims, which divide the line into sections.
+    edges: Vec<Coord>,
+    /// For each node, the number of claims covering all sections of the node,
+    /// the length covered at least once and the length covered at least twice.
+    nodes: Vec<(i32, u64, u64)>,
+}
+
+impl Coverage {
+    fn new(edges: Vec<Coord>) -> Self {
+        let num_nodes = 4 * edges.len().max(1);
+        Self {
+            edges,
+            nodes: vec![(0, 0, 0); num_nodes],
+        }
+    }
+
+    fn length_covered_twice(&self) -> u64 {
+        self.nodes[0].2
+    }
+
+    /// Adds `change` to the number of claims covering the line from `top`
+    /// to `bottom`, which must be edges.
+    fn add(&mut self, top: Coord, bottom: Coord, change: i32) {
+        let first = self.edges.binary_search(&top).expect("top is an edge");
+        let last = self
+            .edges
+            .binary_search(&bottom)
+            .expect("bottom is an edge");
+        if first < last {
+            self.update(0, 0, self.edges.len() - 1, first, last, change);
+        }
+    }
+
+    /// Updates the node covering the sections from edge `start` to edge `end`.
+    fn update(
+        &mut self,
+        node: usize,
+        start: usize,
+        end: usize,
+        first: usize,
+        last: usize,
+        change: i32,
+    ) {
+        if last <= start || end <= first {
+            return;
+        }
+        if first <= start && end <= last {
+            self.nodes[node].0 += change;
+        } else {
+            let middle = (start + end) / 2;
+            self.update(2 * node + 1, start, middle, first, last, change);
+            self.update(2 * node + 2, middle, end, first, last, change);
+        }
+        let full = u64::from(self.edges[end] - self.edges[start]);
+        let (once, twice) = if end - start == 1 {
+            (0, 0)
+        } else {
+            let (_, left_once, left_twice) = self.nodes[2 * node + 1];
+            let (_, right_once, right_twice) = self.nodes[2 * node + 2];
+            (left_once + right_once, left_twice + right_twice)
+        };
+        let count = self.nodes[node].0;
+        self.nodes[node] = match count {
+            0 => (count, once, twice),
+            1 => (count, full, once),
+            _ => (count, full, full),
+        };
+    }
+}
+
+#[aoc(day3, part1)]
+pub fn overlapping_area(input: &[Claim]) -> u64 {
+    Fabric::new(input.to_vec()).overlapping_area()
+}
+
+/// Returns the ID of the only claim that does not overlap any other claim, or
+/// nothing if there is no such claim or more than one.
+#[aoc(day3, part2)]
+pub fn non_overlapping_claims(input: &[Claim]) -> Option<ClaimId> {
+    match Fabric::new(input.to_vec()).intact_claims().as_slice() {
+        [claim] => Some(claim.id()),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
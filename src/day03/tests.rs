@@ -1,7 +1,57 @@
 use super::*;
 
+use proptest::{collection::vec, strategy::Strategy};
+
 const INPUT: &str = include_str!("../../input/2018/day3.txt");
 
+fn example_claims() -> Vec<Claim> {
+    vec![
+        Claim::new(1, 1, 3, 4, 4),
+        Claim::new(2, 3, 1, 4, 4),
+        Claim::new(3, 5, 5, 2, 2),
+        Claim::new(4, 6, 4, 3, 3),
+        Claim::new(5, 20, 0, 1, 1),
+    ]
+}
+
+/// Generates small claims, some of them empty, on a small piece of fabric.
+fn small_claims() -> impl Strategy<Value = Vec<Claim>> {
+    vec((0..20u16, 0..20u16, 0..8u16, 0..8u16), 0..12).prop_map(|claims| {
+        claims
+            .into_iter()
+            .enumerate()
+            .map(|(id, (left, top, width, height))| {
+                Claim::new(id as ClaimId, left, top, width, height)
+            })
+            .collect()
+    })
+}
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn skips_blank_lines() {
+        let input = "#1 @ 1,3: 4x4\n\n#2 @ 3,1: 4x4\n  \n";
+
+        let claims = try_parse(input).unwrap();
+
+        assert_eq!(
+            claims,
+            vec![Claim::new(1, 1, 3, 4, 4), Claim::new(2, 3, 1, 4, 4)]
+        );
+    }
+
+    #[test]
+    fn reports_line_of_invalid_claim() {
+        let input = "#1 @ 1,3: 4x4\n\n#2 @ 3,1 4x4\n";
+
+        let error = try_parse(input).unwrap_err();
+
+        assert_eq!(error.line, 3);
+    }
+}
+
 mod part1 {
     use super::*;
 
@@ -69,7 +119,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let answer = overlapping_area(&parse(INPUT));
+        let answer = overlapping_area(&parse(INPUT).unwrap());
 
         assert_eq!(answer, 119_572);
     }
@@ -106,13 +156,228 @@ mod part2 {
 
         let answer = non_overlapping_claims(&input);
 
-        assert_eq!(answer, 3);
+        assert_eq!(answer, Some(3));
     }
 
     #[test]
     fn answer() {
-        let answer = non_overlapping_claims(&parse(INPUT));
+        let answer = non_overlapping_claims(&parse(INPUT).unwrap());
+
+        assert_eq!(answer, Some(775));
+    }
+}
+
+mod fabric {
+    use super::*;
+
+    fn example() -> Fabric {
+        Fabric::new(example_claims())
+    }
+
+    fn ids<'a>(claims: impl IntoIterator<Item = &'a Claim>) -> Vec<ClaimId> {
+        claims.into_iter().map(Claim::id).collect()
+    }
+
+    /// Counts the claims on each square inch.
+    fn count_overlaps(claims: &[Claim]) -> u64 {
+        let mut counts = HashMap::<(Coord, Coord), u32>::new();
+        for claim in claims {
+            for y in claim.top()..claim.bottom() {
+                for x in claim.left()..claim.right() {
+                    *counts.entry((x, y)).or_insert(0) += 1;
+                }
+            }
+        }
+        counts.values().filter(|&&count| count > 1).count() as u64
+    }
+
+    #[test]
+    fn claims_at_inch() {
+        let fabric = example();
+
+        assert_eq!(ids(fabric.claims_at(Pos::new(3, 3))), vec![1, 2]);
+        assert_eq!(ids(fabric.claims_at(Pos::new(5, 6))), vec![3]);
+        assert_eq!(ids(fabric.claims_at(Pos::new(20, 0))), vec![5]);
+        assert_eq!(ids(fabric.claims_at(Pos::new(7, 7))), Vec::<ClaimId>::new());
+    }
+
+    #[test]
+    fn all_intact_claims() {
+        let fabric = example();
+
+        let intact = fabric.intact_claims();
+
+        assert_eq!(ids(intact), vec![5]);
+    }
+
+    #[test]
+    fn empty_claims_are_intact_and_cover_nothing() {
+        let fabric = Fabric::new(vec![Claim::new(1, 0, 0, 3, 3), Claim::new(2, 1, 1, 0, 5)]);
+
+        assert_eq!(ids(fabric.intact_claims()), vec![1, 2]);
+        assert_eq!(ids(fabric.claims_at(Pos::new(1, 1))), vec![1]);
+        assert_eq!(fabric.overlapping_area(), 0);
+    }
+
+    #[test]
+    fn overlapping_area_of_claims_at_edge_of_fabric() {
+        let fabric = Fabric::new(vec![
+            Claim::new(1, u16::MAX, u16::MAX, u16::MAX, 2),
+            Claim::new(2, u16::MAX, u16::MAX, 2, u16::MAX),
+        ]);
+
+        assert_eq!(fabric.overlapping_area(), 4);
+        assert_eq!(
+            ids(fabric.claims_at(Pos::new(
+                2 * Coord::from(u16::MAX) - 1,
+                Coord::from(u16::MAX)
+            ))),
+            vec![1]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn overlapping_area_is_number_of_inches_claimed_twice(
+            claims in small_claims(),
+        ) {
+            let fabric = Fabric::new(claims.clone());
+
+            prop_assert_eq!(fabric.overlapping_area(), count_overlaps(&claims));
+            for claim in &claims {
+                let is_intact = claims
+                    .iter()
+                    .all(|other| other.id() == claim.id() || !claim.overlaps(other));
+                prop_assert_eq!(ids(fabric.intact_claims()).contains(&claim.id()), is_intact);
+            }
+        }
+    }
+}
+
+mod conflict_graph {
+    use super::*;
+
+    fn example() -> ConflictGraph {
+        ConflictGraph::new(example_claims())
+    }
+
+    #[test]
+    fn conflicts_with_intersections() {
+        let graph = example();
+
+        assert_eq!(
+            graph.conflicts(),
+            &[
+                Conflict {
+                    first: 1,
+                    second: 2,
+                    intersection: Rectangle {
+                        left: 3,
+                        top: 3,
+                        right: 5,
+                        bottom: 5,
+                    },
+                },
+                Conflict {
+                    first: 2,
+                    second: 4,
+                    intersection: Rectangle {
+                        left: 6,
+                        top: 4,
+                        right: 7,
+                        bottom: 5,
+                    },
+                },
+                Conflict {
+                    first: 3,
+                    second: 4,
+                    intersection: Rectangle {
+                        left: 6,
+                        top: 5,
+                        right: 7,
+                        bottom: 7,
+                    },
+                },
+            ]
+        );
+        assert_eq!(
+            graph
+                .conflicts_of(4)
+                .map(|conflict| (conflict.first, conflict.second))
+                .collect::<Vec<_>>(),
+            vec![(2, 4), (3, 4)]
+        );
+    }
+
+    #[test]
+    fn components() {
+        let graph = example();
+
+        let components = graph.components();
+
+        assert_eq!(components, vec![vec![1, 2, 3, 4], vec![5]]);
+    }
+
+    #[test]
+    fn write_dot() {
+        let graph = ConflictGraph::new(vec![
+            Claim::new(1, 1, 3, 4, 4),
+            Claim::new(2, 3, 1, 4, 4),
+            Claim::new(3, 5, 5, 2, 2),
+        ]);
+        let mut dot = Vec::new();
+
+        graph.write_dot(&mut dot).unwrap();
+
+        assert_eq!(
+            String::from_utf8(dot).unwrap(),
+            "\
+graph claims {
+    1;
+    2;
+    3;
+    1 -- 2 [label=\"3,3: 2x2\"];
+}
+"
+        );
+    }
+
+    #[test]
+    fn conflicts_of_input_match_intact_claims() {
+        let claims = parse(INPUT).unwrap();
+
+        let graph = ConflictGraph::new(claims);
+
+        let isolated = graph
+            .components()
+            .into_iter()
+            .filter(|component| component.len() == 1)
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(isolated, vec![775]);
+    }
+
+    proptest! {
+        #[test]
+        fn conflicts_are_all_overlapping_pairs(
+            claims in small_claims(),
+        ) {
+            let mut expected = Vec::new();
+            for (idx, claim1) in claims.iter().enumerate() {
+                for claim2 in &claims[idx + 1..] {
+                    if let Some(intersection) = claim1.intersection(claim2) {
+                        expected.push(Conflict {
+                            first: claim1.id(),
+                            second: claim2.id(),
+                            intersection,
+                        });
+                    }
+                }
+            }
+
+            let graph = ConflictGraph::new(claims);
 
-        assert_eq!(answer, 775);
+            prop_assert_eq!(graph.conflicts(), expected.as_slice());
+        }
     }
 }
@@ -102,15 +102,22 @@
 
 use std::{collections::HashMap, u8};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, ParseError};
+
 pub type GuardId = u16;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Event {
     FallAsleep,
     WakeUp,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Record {
     pub guard_id: GuardId,
     pub day: String,
@@ -120,59 +127,65 @@ pub struct Record {
 }
 
 #[aoc_generator(day4)]
-pub fn parse(input: &str) -> Vec<Record> {
-    let mut lines: Vec<_> = input.lines().collect();
-    lines.sort();
+pub fn parse(input: &str) -> Result<Vec<Record>, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Vec<Record>, ParseError> {
+    let mut lines: Vec<_> = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+    lines.sort_by_key(|&(_, line)| line);
     let mut records = Vec::with_capacity(16);
-    let mut guard_id = 0;
-    for line in lines {
-        match &line[19..24] {
-            "Guard" => {
-                guard_id = line[26..]
-                    .chars()
-                    .filter(|c| c.is_digit(10))
-                    .collect::<String>()
-                    .parse()
-                    .unwrap_or_else(|_| {
-                        panic!("not a guard id in line {}", line);
-                    })
-            },
-            "falls" => {
-                let day = line[1..11].to_owned();
-                let hour = line[12..14].parse().unwrap_or_else(|_| {
-                    panic!("not an hour in line {}", line);
-                });
-                let minute = line[15..17].parse().unwrap_or_else(|_| {
-                    panic!("not an minute in line {}", line);
-                });
-                records.push(Record {
-                    guard_id,
-                    day,
-                    hour,
-                    minute,
-                    event: Event::FallAsleep,
-                })
-            },
-            "wakes" => {
-                let day = line[1..11].to_owned();
-                let hour = line[12..14]
-                    .parse()
-                    .unwrap_or_else(|_| panic!("not an hour in line {}", line));
-                let minute = line[15..17]
-                    .parse()
-                    .unwrap_or_else(|_| panic!("not an minute in line {}", line));
-                records.push(Record {
-                    guard_id,
-                    day,
-                    hour,
-                    minute,
-                    event: Event::WakeUp,
-                })
-            },
-            _ => unreachable!(&format!("invalid line: {}", line)),
+    let mut guard_id = None;
+    for (line_no, line) in lines {
+        let part = |range: std::ops::Range<usize>, expected: &str| {
+            line.get(range)
+                .ok_or_else(|| ParseError::end_of_line(4, line_no, line, expected))
+        };
+        let timestamp = part(0..18, "a timestamp like \"[1518-11-01 00:00]\"")?;
+        let day = part(1..11, "a date")?.to_owned();
+        let hour = error::number(4, line_no, line, part(12..14, "an hour")?, "an hour")?;
+        let minute = error::number(4, line_no, line, part(15..17, "a minute")?, "a minute")?;
+        if !timestamp.starts_with('[') || !timestamp.ends_with(']') {
+            return Err(ParseError::at(
+                4,
+                line_no,
+                line,
+                timestamp,
+                "a timestamp like \"[1518-11-01 00:00]\"",
+            ));
         }
+        let text = line[18..].trim();
+        let event = match text {
+            "falls asleep" => Event::FallAsleep,
+            "wakes up" => Event::WakeUp,
+            _ => {
+                let id = text
+                    .strip_prefix("Guard #")
+                    .and_then(|rest| rest.strip_suffix(" begins shift"))
+                    .ok_or_else(|| {
+                        ParseError::at(4, line_no, line, text, "a guard or sleep event")
+                    })?;
+                guard_id = Some(error::number(4, line_no, line, id, "a guard id")?);
+                continue;
+            },
+        };
+        let guard_id = guard_id.ok_or_else(|| {
+            ParseError::at(4, line_no, line, text, "a guard beginning a shift first")
+        })?;
+        records.push(Record {
+            guard_id,
+            day,
+            hour,
+            minute,
+            event,
+        });
     }
-    records
+    Ok(records)
 }
 
 #[aoc(day4, part1)]
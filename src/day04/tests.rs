@@ -6,7 +6,7 @@ const INPUT: &str = include_str!("../../input/2018/day4.txt");
 fn parse_falls_asleep() {
     let input = "[1518-05-19 23:51] Guard #577 begins shift\n[1518-05-20 00:03] falls asleep";
 
-    let records = parse(input);
+    let records = parse(input).unwrap();
 
     assert_eq!(records.len(), 1);
     assert_eq!(
@@ -25,7 +25,7 @@ fn parse_falls_asleep() {
 fn parse_wakes_up() {
     let input = "[1518-07-18 00:00] Guard #1289 begins shift\n[1518-07-19 00:15] wakes up";
 
-    let records = parse(input);
+    let records = parse(input).unwrap();
 
     assert_eq!(records.len(), 1);
     assert_eq!(
@@ -58,6 +58,24 @@ const EXAMPLE1_INPUT: &str = "[1518-11-01 00:00] Guard #10 begins shift
 [1518-11-05 00:45] falls asleep
 [1518-11-05 00:55] wakes up";
 
+#[test]
+fn try_parse_reports_unknown_event() {
+    let input = "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] dozes off\n";
+
+    let result = try_parse(input);
+
+    assert_eq!(
+        result,
+        Err(ParseError::new(
+            4,
+            2,
+            20,
+            "a guard or sleep event",
+            "\"dozes off\""
+        ))
+    );
+}
+
 mod part1 {
     use super::*;
 
@@ -65,14 +83,14 @@ mod part1 {
     fn example1() {
         let input = EXAMPLE1_INPUT;
 
-        let answer = most_asleep_minute(&parse(input));
+        let answer = most_asleep_minute(&parse(input).unwrap());
 
         assert_eq!(answer, (10, 24));
     }
 
     #[test]
     fn answer() {
-        let answer = strategy1(&parse(INPUT));
+        let answer = strategy1(&parse(INPUT).unwrap());
 
         assert_eq!(answer, 14346);
     }
@@ -85,14 +103,14 @@ mod part2 {
     fn example1() {
         let input = EXAMPLE1_INPUT;
 
-        let answer = most_frequently_asleep_minute(&parse(input));
+        let answer = most_frequently_asleep_minute(&parse(input).unwrap());
 
         assert_eq!(answer, (99, 45, 3));
     }
 
     #[test]
     fn answer() {
-        let answer = strategy2(&parse(INPUT));
+        let answer = strategy2(&parse(INPUT).unwrap());
 
         assert_eq!(answer, 5705);
     }
@@ -71,6 +71,20 @@ use std::{
     iter::FromIterator,
 };
 
+use crate::error::{self, ParseError};
+
+/// Checks that the input consists of a single polymer of units and returns
+/// the polymer.
+pub fn try_parse(input: &str) -> Result<&str, ParseError> {
+    let expected = "a polymer of unit letters";
+    let (line_no, line) = error::single_line(5, input, expected)?;
+    let polymer = line.trim();
+    match polymer.find(|chr: char| !chr.is_ascii_alphabetic()) {
+        Some(pos) => Err(ParseError::at(5, line_no, line, &polymer[pos..], expected)),
+        None => Ok(polymer),
+    }
+}
+
 #[aoc(day5, part1)]
 pub fn reduced_polymer_len(input: &str) -> usize {
     reduce_polymer(input.trim()).len()
@@ -1,4 +1,4 @@
-//! # Day 6:
+//! # Day 6: Chronal Coordinates
 //!
 //! The device on your wrist beeps several times, and once again you feel like
 //! you're falling.
@@ -130,19 +130,38 @@ use std::{
     str::FromStr,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, ParseError};
+
 #[aoc_generator(day6)]
-pub fn parse(input: &str) -> Vec<Point> {
+pub fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Vec<Point>, ParseError> {
     input
-        .trim()
         .lines()
-        .map(|line| {
-            line.parse()
-                .unwrap_or_else(|_| panic!("can not parse Point in line: {:?}", line))
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            let mut coords = line.split(',');
+            let x = coords.next().unwrap_or(line).trim();
+            let y = coords
+                .next()
+                .ok_or_else(|| ParseError::end_of_line(6, idx + 1, line, "\",\""))?
+                .trim();
+            Ok(Point {
+                x: error::number(6, idx + 1, line, x, "an x-coordinate")?,
+                y: error::number(6, idx + 1, line, y, "a y-coordinate")?,
+            })
         })
         .collect()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -208,6 +227,7 @@ impl FromStr for Point {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Distance(pub u32);
 
 impl Display for Distance {
@@ -40,7 +40,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let answer = solve_part1(&parse(INPUT));
+        let answer = solve_part1(&parse(INPUT).unwrap());
 
         assert_eq!(answer, 4398);
     }
@@ -67,7 +67,7 @@ mod part2 {
 
     #[test]
     fn answer() {
-        let answer = solve_part2(&parse(INPUT));
+        let answer = solve_part2(&parse(INPUT).unwrap());
 
         assert_eq!(answer, 39560);
     }
@@ -123,9 +123,15 @@ use std::{
     ops::{Add, AddAssign, Sub, SubAssign},
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::ParseError;
+
 pub type InstructionId = char;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Duration(u32);
 
 impl Display for Duration {
@@ -207,6 +213,7 @@ impl SubAssign<u32> for Duration {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InstructionSet {
     duration_offset: Duration,
 }
@@ -222,6 +229,7 @@ impl InstructionSet {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExecutionPlan {
     prior_map: HashMap<InstructionId, HashSet<InstructionId>>,
 }
@@ -396,21 +404,40 @@ impl<'a> Iterator for ExecutionSimulator<'a> {
 }
 
 #[aoc_generator(day7)]
-pub fn parse(input: &str) -> ExecutionPlan {
+pub fn parse(input: &str) -> Result<ExecutionPlan, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<ExecutionPlan, ParseError> {
     let mut instructions = ExecutionPlan::with_capacity(16);
-    input.trim().lines().for_each(|line| {
-        let chars = line.chars();
-        let mut skip5 = chars.skip(5);
-        let prior_id = skip5
-            .next()
-            .unwrap_or_else(|| panic!("no 6th char in line: {}", line));
-        let mut skip30 = skip5.skip(30);
-        let id = skip30
-            .next()
-            .unwrap_or_else(|| panic!("no 37th char in line: {}", line));
+    for (idx, line) in input.lines().enumerate() {
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let step = |part: Option<&str>, expected: &str| {
+            let part = part.ok_or_else(|| ParseError::at(7, idx + 1, line, text, expected))?;
+            let mut chars = part.chars();
+            match (chars.next(), chars.next()) {
+                (Some(id), None) => Ok(id),
+                _ => Err(ParseError::at(7, idx + 1, line, part, expected)),
+            }
+        };
+        let mut parts = text
+            .strip_prefix("Step ")
+            .and_then(|rest| rest.strip_suffix(" can begin."))
+            .map(|rest| rest.split(" must be finished before step "));
+        let prior_id = step(
+            parts.as_mut().and_then(Iterator::next),
+            "\"Step X must be finished before step Y can begin.\"",
+        )?;
+        let id = step(
+            parts.as_mut().and_then(Iterator::next),
+            "\"Step X must be finished before step Y can begin.\"",
+        )?;
         instructions.add_prerequisite(id, prior_id);
-    });
-    instructions
+    }
+    Ok(instructions)
 }
 
 #[aoc(day7, part1)]
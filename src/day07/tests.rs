@@ -15,7 +15,7 @@ mod parse_input {
 
     #[test]
     fn example1() {
-        let instructions = parse(EXAMPLE1_INPUT);
+        let instructions = parse(EXAMPLE1_INPUT).unwrap();
 
         assert_eq!(instructions.len(), 6);
         assert_eq!(
@@ -50,7 +50,7 @@ mod part1 {
 
     #[test]
     fn example1() {
-        let execution_plan = parse(EXAMPLE1_INPUT);
+        let execution_plan = parse(EXAMPLE1_INPUT).unwrap();
 
         let answer = String::from_iter(execution_plan.in_order());
 
@@ -59,7 +59,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let answer = execution_order(&parse(INPUT));
+        let answer = execution_order(&parse(INPUT).unwrap());
 
         assert_eq!(answer, "BHRTWCYSELPUVZAOIJKGMFQDXN");
     }
@@ -71,7 +71,7 @@ mod part2 {
     #[test]
     fn example1() {
         let instruction_set = InstructionSet::new(Duration::from_sec(0));
-        let execution_plan = parse(EXAMPLE1_INPUT);
+        let execution_plan = parse(EXAMPLE1_INPUT).unwrap();
 
         let answer = execution_plan.execution_time(2, instruction_set);
 
@@ -80,7 +80,7 @@ mod part2 {
 
     #[test]
     fn answer() {
-        let answer = execution_time(&parse(INPUT));
+        let answer = execution_time(&parse(INPUT).unwrap());
 
         assert_eq!(answer, Duration::from_sec(959));
     }
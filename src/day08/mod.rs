@@ -90,15 +90,22 @@ use std::{
     iter::FromIterator,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, ParseError};
+
 const ROOT: Node = Node {
     parent_id: NodeId(0),
     id: NodeId(1),
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Metadata(u32);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NodeId(u32);
 
 impl From<u32> for NodeId {
@@ -114,6 +121,7 @@ impl NodeId {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Node {
     parent_id: NodeId,
     id: NodeId,
@@ -161,6 +169,7 @@ impl Ord for Node {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct License {
     root_id: NodeId,
     nodes: HashMap<NodeId, Node>,
@@ -237,23 +246,20 @@ impl License {
 }
 
 #[aoc_generator(day8)]
-pub fn parse(input: &str) -> License {
-    let input_data = input
-        .trim()
-        .lines()
-        .next()
-        .unwrap_or_else(|| panic!("there is not a single line in input: {:?}", input));
-
-    let mut digits = input_data.split(' ').map(|data| {
-        data.parse::<u32>().unwrap_or_else(|_| {
-            panic!("there is other data then digits in input: {:?}", input_data)
-        })
-    });
+pub fn parse(input: &str) -> Result<License, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<License, ParseError> {
+    let (line_no, line) = error::single_line(8, input, "a list of numbers")?;
+
+    let mut digits = line.split_whitespace();
 
     let mut next_digits = || {
         digits
             .next()
-            .unwrap_or_else(|| panic!("no more input data"))
+            .ok_or_else(|| ParseError::end_of_line(8, line_no, line, "a number"))
+            .and_then(|data| error::number::<u32>(8, line_no, line, data, "a number"))
     };
 
     let mut node_id_seq = ROOT.id.0;
@@ -268,8 +274,8 @@ pub fn parse(input: &str) -> License {
     let mut parent_header = Vec::with_capacity(8);
 
     let mut current_node = ROOT;
-    let mut num_children = next_digits();
-    let mut num_metadata_entries = next_digits();
+    let mut num_children = next_digits()?;
+    let mut num_metadata_entries = next_digits()?;
 
     loop {
         if num_children == 0 {
@@ -277,7 +283,7 @@ pub fn parse(input: &str) -> License {
                 .entry(current_node.id)
                 .or_insert_with(|| Vec::with_capacity(num_metadata_entries as usize));
             for _ in 0..num_metadata_entries {
-                let metadata_entry = next_digits();
+                let metadata_entry = next_digits()?;
                 current_metadata.push(Metadata(metadata_entry));
             }
             if let Some((parent_node, parent_children, parent_metadata)) = parent_header.pop() {
@@ -289,18 +295,18 @@ pub fn parse(input: &str) -> License {
             }
         } else {
             parent_header.push((current_node, num_children, num_metadata_entries));
-            num_children = next_digits();
-            num_metadata_entries = next_digits();
+            num_children = next_digits()?;
+            num_metadata_entries = next_digits()?;
             current_node = Node::new(current_node.id, next_node_id());
             nodes.insert(current_node.id, current_node);
         }
     }
 
-    License {
+    Ok(License {
         root_id: ROOT.id,
         nodes,
         metadata,
-    }
+    })
 }
 
 #[aoc(day8, part1)]
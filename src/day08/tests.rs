@@ -9,7 +9,7 @@ mod parse_input {
 
     #[test]
     fn example1() {
-        let license = parse(EXAMPLE1_INPUT);
+        let license = parse(EXAMPLE1_INPUT).unwrap();
 
         assert_eq!(
             license,
@@ -36,7 +36,7 @@ mod part1 {
 
     #[test]
     fn example1() {
-        let license = parse(EXAMPLE1_INPUT);
+        let license = parse(EXAMPLE1_INPUT).unwrap();
 
         let answer = license.metadata_checksum();
 
@@ -45,7 +45,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let license = parse(INPUT);
+        let license = parse(INPUT).unwrap();
 
         let answer = metadata_checksum(&license);
 
@@ -58,7 +58,7 @@ mod part2 {
 
     #[test]
     fn example1() {
-        let license = parse(EXAMPLE1_INPUT);
+        let license = parse(EXAMPLE1_INPUT).unwrap();
 
         let answer = license.value(ROOT.id);
 
@@ -67,7 +67,7 @@ mod part2 {
 
     #[test]
     fn answer() {
-        let license = parse(INPUT);
+        let license = parse(INPUT).unwrap();
 
         let answer = value_of_license_root(&license);
 
@@ -80,7 +80,7 @@ mod license {
 
     #[test]
     fn child_nodes_of_root() {
-        let license = parse(EXAMPLE1_INPUT);
+        let license = parse(EXAMPLE1_INPUT).unwrap();
 
         let child_nodes = license.child_nodes(ROOT.id);
 
@@ -90,7 +90,7 @@ mod license {
 
     #[test]
     fn child_nodes_of_node1() {
-        let license = parse(EXAMPLE1_INPUT);
+        let license = parse(EXAMPLE1_INPUT).unwrap();
 
         let child_nodes = license.child_nodes(NodeId(2));
 
@@ -93,7 +93,13 @@ use std::{
     ops::{Add, AddAssign, Index},
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, ParseError};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerNr(u32);
 
 impl PlayerNr {
@@ -116,6 +122,7 @@ impl Display for PlayerNr {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Score(u32);
 
 impl Score {
@@ -166,6 +173,7 @@ impl AddAssign<u32> for Score {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Marble(u32);
 
 impl Marble {
@@ -188,6 +196,7 @@ impl Display for Marble {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MarbleGame {
     rules: MarbleRules,
     num_marbles: u32,
@@ -215,6 +224,7 @@ impl MarbleGame {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MarbleRules;
 
 impl MarbleRules {
@@ -228,6 +238,7 @@ impl MarbleRules {
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ring<T> {
     list: Vec<T>,
 }
@@ -291,6 +302,7 @@ impl<T> Index<usize> for Ring<T> {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameRunner {
     game: MarbleGame,
     scores: HashMap<PlayerNr, Score>,
@@ -391,18 +403,28 @@ impl Iterator for GameRunner {
 }
 
 #[aoc_generator(day9)]
-pub fn parse(input: &str) -> MarbleGame {
-    let line =
-        input.trim().lines().next().unwrap_or_else(|| {
-            panic!("what? there is not a single line in the input: {:?}", input)
-        });
-
-    let mut words = line.trim().split(' ');
-
-    let num_players = words.next().unwrap().parse().unwrap();
-    let num_marbles = words.nth(5).unwrap().parse().unwrap();
+pub fn parse(input: &str) -> Result<MarbleGame, ParseError> {
+    try_parse(input)
+}
 
-    MarbleGame::new(num_marbles, num_players)
+pub fn try_parse(input: &str) -> Result<MarbleGame, ParseError> {
+    let expected = "\"<n> players; last marble is worth <m> points\"";
+    let (line_no, line) = error::single_line(9, input, expected)?;
+    let text = line.trim();
+    let mut parts = text
+        .strip_suffix(" points")
+        .map(|rest| rest.split(" players; last marble is worth "));
+    let mut number = |expected_number: &str| {
+        let part = parts
+            .as_mut()
+            .and_then(Iterator::next)
+            .ok_or_else(|| ParseError::at(9, line_no, line, text, expected))?;
+        error::number(9, line_no, line, part, expected_number)
+    };
+    let num_players = number("the number of players")?;
+    let num_marbles = number("the value of the last marble")?;
+
+    Ok(MarbleGame::new(num_marbles, num_players))
 }
 
 #[aoc(day9, part1)]
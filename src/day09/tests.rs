@@ -276,6 +276,27 @@ mod game_runner {
     }
 }
 
+mod try_parse {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let game = try_parse("10 players; last marble is worth 1618 points\n");
+
+        assert_eq!(game, Ok(MarbleGame::new(1618, 10)));
+    }
+
+    #[test]
+    fn missing_number_of_players() {
+        let result = try_parse("ten players; last marble is worth 1618 points");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(9, 1, 1, "the number of players", "\"ten\""))
+        );
+    }
+}
+
 mod part1 {
     use super::*;
 
@@ -335,7 +356,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let game = parse(INPUT);
+        let game = parse(INPUT).unwrap();
 
         let answer = marble_highscore(&game);
 
@@ -349,7 +370,7 @@ mod part2 {
     #[ignore]
     #[test]
     fn answer() {
-        let game = parse(INPUT);
+        let game = parse(INPUT).unwrap();
 
         let answer = marble_highscore(&game);
 
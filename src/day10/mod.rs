@@ -190,19 +190,27 @@ use std::{
     i32, i64,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, ParseError};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Position {
     pub x: i32,
     pub y: i32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Velocity {
     pub x: i32,
     pub y: i32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Duration(u64);
 
 impl Display for Duration {
@@ -218,6 +226,7 @@ impl Duration {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Star {
     pub position: Position,
     pub velocity: Velocity,
@@ -231,6 +240,7 @@ impl Star {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Sky {
     stars: Vec<Star>,
 }
@@ -309,24 +319,35 @@ impl Display for Sky {
 }
 
 #[aoc_generator(day10)]
-pub fn parse(input: &str) -> Sky {
+pub fn parse(input: &str) -> Result<Sky, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Sky, ParseError> {
     let stars = input
-        .trim()
         .lines()
-        .map(|line| {
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
             let mut parts = line.split(|c| c == '<' || c == '>' || c == ',');
-            let x = parts.nth(1).unwrap().trim().parse::<i32>().unwrap();
-            let y = parts.nth(0).unwrap().trim().parse::<i32>().unwrap();
-            let vx = parts.nth(1).unwrap().trim().parse::<i32>().unwrap();
-            let vy = parts.nth(0).unwrap().trim().parse::<i32>().unwrap();
+            let mut number = |skip: usize, expected: &str| {
+                let part = parts
+                    .nth(skip)
+                    .ok_or_else(|| ParseError::end_of_line(10, idx + 1, line, expected))?;
+                error::number(10, idx + 1, line, part.trim(), expected)
+            };
+            let x = number(1, "an x-position")?;
+            let y = number(0, "a y-position")?;
+            let vx = number(1, "an x-velocity")?;
+            let vy = number(0, "a y-velocity")?;
 
             let position = Position { x, y };
             let velocity = Velocity { x: vx, y: vy };
-            Star { position, velocity }
+            Ok(Star { position, velocity })
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    Sky::new(stars)
+    Ok(Sky::new(stars))
 }
 
 #[aoc(day10, part1)]
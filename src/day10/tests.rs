@@ -58,12 +58,26 @@ const EXAMPLE_ANSWER: &str = "
 #...#..###
 ";
 
+mod try_parse {
+    use super::*;
+
+    #[test]
+    fn missing_velocity() {
+        let result = try_parse("position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0>\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(10, 2, 18, "an x-velocity", "end of line"))
+        );
+    }
+}
+
 mod part1 {
     use super::*;
 
     #[test]
     fn example1() {
-        let sky = parse(EXAMPLE_INPUT);
+        let sky = parse(EXAMPLE_INPUT).unwrap();
 
         let answer = align_stars(&sky);
 
@@ -72,7 +86,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let sky = parse(INPUT);
+        let sky = parse(INPUT).unwrap();
 
         let answer = align_stars(&sky);
 
@@ -85,7 +99,7 @@ mod part2 {
 
     #[test]
     fn example1() {
-        let sky = parse(EXAMPLE_INPUT);
+        let sky = parse(EXAMPLE_INPUT).unwrap();
 
         let answer = time_to_aligned_stars(&sky);
 
@@ -94,7 +108,7 @@ mod part2 {
 
     #[test]
     fn answer() {
-        let sky = parse(INPUT);
+        let sky = parse(INPUT).unwrap();
 
         let answer = time_to_aligned_stars(&sky);
 
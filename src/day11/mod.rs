@@ -109,7 +109,13 @@ use std::{
     str::FromStr,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, ParseError};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SerialNo(pub u32);
 
 impl AsRef<SerialNo> for SerialNo {
@@ -127,6 +133,7 @@ impl FromStr for SerialNo {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PowerLevel(pub i32);
 
 impl Display for PowerLevel {
@@ -148,6 +155,7 @@ impl Sum for PowerLevel {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CellCoord {
     pub x: u32,
     pub y: u32,
@@ -168,6 +176,7 @@ impl CellCoord {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RackId(pub u32);
 
 impl From<CellCoord> for RackId {
@@ -177,6 +186,7 @@ impl From<CellCoord> for RackId {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PowerGrid {
     serial_no: SerialNo,
     size: u32,
@@ -206,6 +216,7 @@ fn calc_cell_power(serial_no: SerialNo, cell: CellCoord) -> PowerLevel {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CellGroup {
     size: u32,
     coord: CellCoord,
@@ -306,6 +317,7 @@ impl Iterator for Cells {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Answer(u32, u32, u32);
 
 impl Display for Answer {
@@ -321,8 +333,14 @@ impl From<(CellGroup, PowerLevel)> for Answer {
 }
 
 #[aoc_generator(day11)]
-pub fn parse(input: &str) -> SerialNo {
-    input.trim().parse().unwrap()
+pub fn parse(input: &str) -> Result<SerialNo, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<SerialNo, ParseError> {
+    let expected = "a grid serial number";
+    let (line_no, line) = error::single_line(11, input, expected)?;
+    error::number(11, line_no, line, line.trim(), expected)
 }
 
 #[aoc(day11, part1)]
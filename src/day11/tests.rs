@@ -71,7 +71,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let serial_no = parse(INPUT);
+        let serial_no = parse(INPUT).unwrap();
 
         let answer = best_cell_group(&serial_no);
 
@@ -109,7 +109,7 @@ mod part2 {
     #[ignore]
     #[test]
     fn answer() {
-        let serial_no = parse(INPUT);
+        let serial_no = parse(INPUT).unwrap();
 
         let answer = best_cell_group_size(&serial_no);
 
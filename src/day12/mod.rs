@@ -124,6 +124,11 @@ use std::{
     iter::FromIterator,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::ParseError;
+
 fn fmt_pot(pot: bool) -> char {
     if pot {
         '#'
@@ -133,6 +138,7 @@ fn fmt_pot(pot: bool) -> char {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BreedRule {
     pattern: Vec<bool>,
     outcome: bool,
@@ -165,6 +171,7 @@ impl BreedRule {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BreedingRules(Vec<BreedRule>);
 
 impl AsRef<[BreedRule]> for BreedingRules {
@@ -174,6 +181,7 @@ impl AsRef<[BreedRule]> for BreedingRules {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PotRow(Vec<bool>);
 
 impl Display for PotRow {
@@ -197,6 +205,7 @@ impl AsMut<[bool]> for PotRow {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Plantation {
     plants: PotRow,
     breed_rules: BreedingRules,
@@ -233,36 +242,59 @@ impl Plantation {
 }
 
 #[aoc_generator(day12)]
-pub fn parse(input: &str) -> Plantation {
-    let mut lines = input.trim().lines();
-
-    let initial_plants = lines.next().unwrap().trim()[15..]
-        .chars()
-        .map(|chr| chr == '#');
-
-    let _ = lines.next(); // skip one line
-
-    let breeding_rules = lines.map(|line| {
-        let mut parts = line.split("=>");
-        let pattern = parts
-            .next()
-            .unwrap()
-            .trim()
-            .chars()
-            .map(|chr| chr == '#')
-            .collect();
-        let outcome = parts
-            .next()
-            .unwrap()
-            .trim()
-            .chars()
-            .next()
-            .map(|chr| chr == '#')
-            .unwrap();
-        BreedRule::new(pattern, outcome)
-    });
-
-    Plantation::new(initial_plants, breeding_rules)
+pub fn parse(input: &str) -> Result<Plantation, ParseError> {
+    try_parse(input)
+}
+
+fn parse_pots(line_no: usize, line: &str, pots: &str) -> Result<Vec<bool>, ParseError> {
+    pots.char_indices()
+        .map(|(pos, chr)| match chr {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::at(
+                12,
+                line_no,
+                line,
+                &pots[pos..pos + chr.len_utf8()],
+                "'#' or '.'",
+            )),
+        })
+        .collect()
+}
+
+pub fn try_parse(input: &str) -> Result<Plantation, ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (line_no, line) = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(12, input, "the initial state"))?;
+    let text = line.trim();
+    let pots = text
+        .strip_prefix("initial state:")
+        .ok_or_else(|| ParseError::at(12, line_no, line, text, "\"initial state:\""))?;
+    let initial_plants = parse_pots(line_no, line, pots.trim())?;
+
+    let breeding_rules = lines
+        .map(|(line_no, line)| {
+            let mut parts = line.split("=>");
+            let pattern = parts.next().unwrap_or(line).trim();
+            let outcome = parts
+                .next()
+                .ok_or_else(|| ParseError::end_of_line(12, line_no, line, "\"=>\""))?
+                .trim();
+            let pattern = parse_pots(line_no, line, pattern)?;
+            match parse_pots(line_no, line, outcome)?.as_slice() {
+                [outcome] => Ok(BreedRule::new(pattern, *outcome)),
+                _ => Err(ParseError::at(12, line_no, line, outcome, "a single pot")),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Plantation::new(initial_plants, breeding_rules))
 }
 
 #[aoc(day12, part1)]
@@ -25,7 +25,7 @@ mod plantation {
 
     #[test]
     fn parse_example1() {
-        let plantation = parse(EXAMPLE1_INPUT);
+        let plantation = parse(EXAMPLE1_INPUT).unwrap();
 
         assert_eq!(
             plantation,
@@ -61,7 +61,7 @@ mod part1 {
 
     #[test]
     fn example1() {
-        let plantation = parse(EXAMPLE1_INPUT);
+        let plantation = parse(EXAMPLE1_INPUT).unwrap();
 
         let (evolved, offset) = evolve_n_generations(&plantation, 20);
 
@@ -74,7 +74,7 @@ mod part1 {
 
     #[test]
     fn example1_answer() {
-        let plantation = parse(EXAMPLE1_INPUT);
+        let plantation = parse(EXAMPLE1_INPUT).unwrap();
 
         let answer = sum_of_pot_numbers_after_20_generations(&plantation);
 
@@ -83,7 +83,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let plantation = parse(INPUT);
+        let plantation = parse(INPUT).unwrap();
 
         let answer = sum_of_pot_numbers_after_20_generations(&plantation);
 
@@ -96,7 +96,7 @@ mod part2 {
 
     #[test]
     fn answer() {
-        let plantation = parse(INPUT);
+        let plantation = parse(INPUT).unwrap();
 
         let answer = sum_of_pot_numbers_after_50_000_000_000_generations(&plantation);
 
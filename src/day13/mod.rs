@@ -263,9 +263,19 @@ use std::{
     u32,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ParseError,
+    grid::{self, Pos},
+    render::{self, Render, Rgb},
+};
+
 use self::MoveResult::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Position {
     pub x: u32,
     pub y: u32,
@@ -316,6 +326,7 @@ impl PartialOrd for Position {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Direction {
     North,
     East,
@@ -337,6 +348,7 @@ impl Display for Direction {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AtIntersection {
     TurnLeft,
     GoStraight,
@@ -368,6 +380,7 @@ pub enum MoveResult {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CartNo(u8);
 
 impl Display for CartNo {
@@ -387,6 +400,7 @@ impl CartNo {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cart {
     pub number: CartNo,
     pub position: Position,
@@ -531,6 +545,7 @@ impl Cart {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RailKind {
     NorthSouth,
     WestEast,
@@ -567,7 +582,9 @@ impl From<Direction> for RailKind {
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Tracks {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pairs"))]
     rails: HashMap<Position, RailKind>,
 }
 
@@ -678,6 +695,7 @@ impl IndexMut<Position> for Tracks {
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CartsNTracks {
     num_carts: u8,
     carts: Vec<Cart>,
@@ -733,6 +751,26 @@ impl Display for CartsNTracks {
     }
 }
 
+impl Render for CartsNTracks {
+    fn render(&self) -> grid::Grid<Rgb, render::Coord> {
+        let cell = |pos: Position| Pos::new(render::Coord::from(pos.x), render::Coord::from(pos.y));
+        let mut frame = render::canvas(
+            grid::bounding_box(self.tracks.rails.keys().cloned().map(cell)),
+            Rgb::BLACK,
+        );
+        for (&position, rail) in &self.tracks.rails {
+            frame[cell(position)] = match rail {
+                RailKind::Intersection => Rgb(200, 200, 200),
+                _ => Rgb(120, 120, 120),
+            };
+        }
+        for cart in &self.carts {
+            frame[cell(cart.position)] = Rgb(255, 48, 48);
+        }
+        frame
+    }
+}
+
 impl CartsNTracks {
     pub fn new() -> Self {
         Self {
@@ -774,7 +812,11 @@ impl CartsNTracks {
 }
 
 #[aoc_generator(day13)]
-fn parse(input: &str) -> CartsNTracks {
+fn parse(input: &str) -> Result<CartsNTracks, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<CartsNTracks, ParseError> {
     let mut carts_n_tracks = CartsNTracks::new();
 
     for (y, line) in input.lines().enumerate() {
@@ -811,12 +853,20 @@ fn parse(input: &str) -> CartsNTracks {
                     carts_n_tracks.insert_cart(pos, South);
                 },
                 s if s.is_whitespace() => {},
-                _ => panic!("unsupported character {:?} at {}:{}", symbol, y + 1, x + 1),
+                _ => {
+                    return Err(ParseError::new(
+                        13,
+                        y + 1,
+                        x + 1,
+                        "a track or a cart",
+                        format!("{:?}", symbol),
+                    ))
+                },
             }
         }
     }
 
-    carts_n_tracks
+    Ok(carts_n_tracks)
 }
 
 #[aoc(day13, part1)]
@@ -13,16 +13,26 @@ const EXAMPLE1_INPUT: &str = r#"/->-\
 mod parse {
     use super::*;
 
+    #[test]
+    fn unsupported_character() {
+        let result = try_parse("/->-\\\n|   x\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(13, 2, 5, "a track or a cart", "'x'"))
+        );
+    }
+
     #[test]
     fn parse_example1() {
-        let carts_n_tracks = parse(EXAMPLE1_INPUT);
+        let carts_n_tracks = parse(EXAMPLE1_INPUT).unwrap();
         debug!("{:?}", carts_n_tracks);
         assert_eq!(carts_n_tracks.to_string(), EXAMPLE1_INPUT);
     }
 
     #[test]
     fn parse_input() {
-        let _carts_n_tracks = parse(INPUT);
+        let _carts_n_tracks = parse(INPUT).unwrap();
         // just testing for not panicking
     }
 }
@@ -32,7 +42,7 @@ mod part1 {
 
     #[test]
     fn example1() {
-        let carts_n_tracks = parse(EXAMPLE1_INPUT);
+        let carts_n_tracks = parse(EXAMPLE1_INPUT).unwrap();
 
         let answer = location_of_first_crash(&carts_n_tracks);
 
@@ -41,7 +51,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let carts_n_tracks = parse(INPUT);
+        let carts_n_tracks = parse(INPUT).unwrap();
 
         let answer = location_of_first_crash(&carts_n_tracks);
 
@@ -63,7 +73,7 @@ mod part2 {
 
     #[test]
     fn example2() {
-        let carts_n_tracks = parse(EXAMPLE2_INPUT);
+        let carts_n_tracks = parse(EXAMPLE2_INPUT).unwrap();
 
         let answer = location_of_last_cart(&carts_n_tracks);
 
@@ -72,7 +82,7 @@ mod part2 {
 
     #[test]
     fn answer() {
-        let carts_n_tracks = parse(INPUT);
+        let carts_n_tracks = parse(INPUT).unwrap();
 
         let answer = location_of_last_cart(&carts_n_tracks);
 
@@ -92,7 +92,13 @@ use std::{
     str::FromStr,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, ParseError};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ScoreSeq(Vec<u8>);
 
 impl Display for ScoreSeq {
@@ -145,6 +151,7 @@ impl FromStr for ScoreSeq {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Scoreboard(Vec<u8>);
 
 impl Display for Scoreboard {
@@ -178,6 +185,7 @@ impl Scoreboard {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Recipes {
     sequence: Vec<u8>,
     elf1: usize,
@@ -216,6 +224,18 @@ impl<'a> Iterator for Recipes {
     }
 }
 
+/// Parses the puzzle input, a number which is also used as the sequence of
+/// scores to look for.
+pub fn try_parse(input: &str) -> Result<ScoreSeq, ParseError> {
+    let expected = "a sequence of digits";
+    let (line_no, line) = error::single_line(14, input, expected)?;
+    let digits = line.trim();
+    match digits.find(|chr: char| !chr.is_ascii_digit()) {
+        Some(pos) => Err(ParseError::at(14, line_no, line, &digits[pos..], expected)),
+        None => Ok(ScoreSeq::from_str(digits).expect("only digits")),
+    }
+}
+
 #[aoc(day14, part1)]
 pub fn score_seq_after_num_recipes(input: &str) -> ScoreSeq {
     let num_recipes = input
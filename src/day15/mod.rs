@@ -485,6 +485,15 @@ use std::{
     ops::{Add, AddAssign, Sub, SubAssign},
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ParseError,
+    grid::{self, Pos},
+    render::{self, Render, Rgb},
+};
+
 use self::FightResult::*;
 use self::Move::*;
 use self::Tile::*;
@@ -498,6 +507,8 @@ pub const MIN_ID_VAL: u32 = std::u32::MIN;
 pub const MAX_ID_VAL: u32 = std::u32::MAX;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct Id<T: Unit> {
     _unit: PhantomData<T>,
     value: IdValue,
@@ -572,6 +583,7 @@ pub type Distance = usize;
 pub const MAX_DISTANCE: Distance = std::usize::MAX;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Position {
     pub x: Coord,
     pub y: Coord,
@@ -705,6 +717,7 @@ impl Display for Move {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HitPoints(i32);
 
 impl Display for HitPoints {
@@ -736,6 +749,7 @@ impl Add for HitPoints {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AttackPower(i32);
 
 impl Display for AttackPower {
@@ -787,6 +801,7 @@ impl SubAssign<AttackPower> for HitPoints {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Elf {
     id: Id<Elf>,
     hit_points: HitPoints,
@@ -834,6 +849,7 @@ impl Elf {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Goblin {
     id: Id<Goblin>,
     hit_points: HitPoints,
@@ -905,6 +921,7 @@ pub trait WithPositioned<T: Unit> {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Tile {
     OpenCavern,
     Wall,
@@ -921,6 +938,7 @@ impl Display for Tile {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cave(HashSet<Position>);
 
 impl Display for Cave {
@@ -1005,9 +1023,12 @@ pub enum FightResult {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Combat {
     cave: Cave,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pairs"))]
     elves: HashMap<Position, Elf>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pairs"))]
     goblins: HashMap<Position, Goblin>,
     rounds: u32,
     elves_attack_power: AttackPower,
@@ -1047,6 +1068,26 @@ impl Display for Combat {
     }
 }
 
+impl Render for Combat {
+    fn render(&self) -> grid::Grid<Rgb, render::Coord> {
+        let cell = |pos: &Position| Pos::new(pos.x as render::Coord, pos.y as render::Coord);
+        let mut frame = render::canvas(
+            grid::bounding_box(self.cave.0.iter().map(cell)),
+            Rgb(216, 200, 160),
+        );
+        for wall in &self.cave.0 {
+            frame[cell(wall)] = Rgb(96, 64, 32);
+        }
+        for elf in self.elves.keys() {
+            frame[cell(elf)] = Rgb(32, 160, 32);
+        }
+        for goblin in self.goblins.keys() {
+            frame[cell(goblin)] = Rgb(200, 32, 32);
+        }
+        frame
+    }
+}
+
 impl WithPositioned<Elf> for Combat {
     fn position_of(&self, id: Id<Elf>) -> Option<Position> {
         self.elves.iter().find_map(|(position, elf)| {
@@ -1430,7 +1471,11 @@ where
 }
 
 #[aoc_generator(day15)]
-pub fn parse(input: &str) -> Combat {
+pub fn parse(input: &str) -> Result<Combat, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Combat, ParseError> {
     let mut elf_id_seq = IdSequence::default();
     let mut goblin_id_seq = IdSequence::default();
     let mut walls = HashSet::with_capacity(16);
@@ -1454,13 +1499,19 @@ pub fn parse(input: &str) -> Combat {
                 },
                 s if s.is_whitespace() => {},
                 _ => {
-                    panic!("unexpected character {} at {}:{}", chr, y, x);
+                    return Err(ParseError::new(
+                        15,
+                        y + 1,
+                        x + 1,
+                        "'.', '#', 'E' or 'G'",
+                        format!("{:?}", chr),
+                    ));
                 },
             }
         }
     }
 
-    Combat::new(walls, elves, goblins)
+    Ok(Combat::new(walls, elves, goblins))
 }
 
 #[aoc(day15, part1)]
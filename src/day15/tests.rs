@@ -179,51 +179,61 @@ const EXAMPLE6_FINAL: &str = "\
 mod parse {
     use super::*;
 
+    #[test]
+    fn unexpected_character() {
+        let result = try_parse("#######\n#.G.?.#\n#######\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(15, 2, 5, "'.', '#', 'E' or 'G'", "'?'"))
+        );
+    }
+
     #[test]
     fn example1() {
-        let combat_map = parse(EXAMPLE1_INPUT);
+        let combat_map = parse(EXAMPLE1_INPUT).unwrap();
 
         assert_eq!(combat_map.to_string(), EXAMPLE1_INPUT);
     }
 
     #[test]
     fn example2() {
-        let combat_map = parse(EXAMPLE2_INPUT);
+        let combat_map = parse(EXAMPLE2_INPUT).unwrap();
 
         assert_eq!(combat_map.to_string(), EXAMPLE2_INPUT);
     }
 
     #[test]
     fn example3() {
-        let combat_map = parse(EXAMPLE3_INPUT);
+        let combat_map = parse(EXAMPLE3_INPUT).unwrap();
 
         assert_eq!(combat_map.to_string(), EXAMPLE3_INPUT);
     }
 
     #[test]
     fn example4() {
-        let combat_map = parse(EXAMPLE4_INPUT);
+        let combat_map = parse(EXAMPLE4_INPUT).unwrap();
 
         assert_eq!(combat_map.to_string(), EXAMPLE4_INPUT);
     }
 
     #[test]
     fn example5() {
-        let combat_map = parse(EXAMPLE5_INPUT);
+        let combat_map = parse(EXAMPLE5_INPUT).unwrap();
 
         assert_eq!(combat_map.to_string(), EXAMPLE5_INPUT);
     }
 
     #[test]
     fn example6() {
-        let combat_map = parse(EXAMPLE6_INPUT);
+        let combat_map = parse(EXAMPLE6_INPUT).unwrap();
 
         assert_eq!(combat_map.to_string(), EXAMPLE6_INPUT);
     }
 
     #[test]
     fn input() {
-        let combat_map = parse(INPUT);
+        let combat_map = parse(INPUT).unwrap();
 
         assert_eq!(combat_map.to_string(), INPUT);
     }
@@ -234,7 +244,7 @@ mod part1 {
 
     #[test]
     fn example1_1st_round() {
-        let mut combat = parse(EXAMPLE1_INPUT);
+        let mut combat = parse(EXAMPLE1_INPUT).unwrap();
 
         let _ = combat.n_fights(1);
 
@@ -247,7 +257,7 @@ mod part1 {
 
     #[test]
     fn example1_2nd_round() {
-        let mut combat = parse(EXAMPLE1_INPUT);
+        let mut combat = parse(EXAMPLE1_INPUT).unwrap();
 
         let _ = combat.n_fights(2);
 
@@ -260,7 +270,7 @@ mod part1 {
 
     #[test]
     fn example1_22nd_round() {
-        let mut combat = parse(EXAMPLE1_INPUT);
+        let mut combat = parse(EXAMPLE1_INPUT).unwrap();
 
         let _ = combat.n_fights(22);
 
@@ -273,7 +283,7 @@ mod part1 {
 
     #[test]
     fn example1_23nd_round() {
-        let mut combat = parse(EXAMPLE1_INPUT);
+        let mut combat = parse(EXAMPLE1_INPUT).unwrap();
 
         let _ = combat.n_fights(23);
 
@@ -286,7 +296,7 @@ mod part1 {
 
     #[test]
     fn example1_24th_round() {
-        let mut combat = parse(EXAMPLE1_INPUT);
+        let mut combat = parse(EXAMPLE1_INPUT).unwrap();
 
         let _ = combat.n_fights(24);
 
@@ -299,7 +309,7 @@ mod part1 {
 
     #[test]
     fn example1_final() {
-        let mut combat = parse(EXAMPLE1_INPUT);
+        let mut combat = parse(EXAMPLE1_INPUT).unwrap();
 
         let result = combat.fight();
 
@@ -310,7 +320,7 @@ mod part1 {
 
     #[test]
     fn example2_final() {
-        let mut combat = parse(EXAMPLE2_INPUT);
+        let mut combat = parse(EXAMPLE2_INPUT).unwrap();
 
         let result = combat.fight();
 
@@ -321,7 +331,7 @@ mod part1 {
 
     #[test]
     fn example3_final() {
-        let mut combat = parse(EXAMPLE3_INPUT);
+        let mut combat = parse(EXAMPLE3_INPUT).unwrap();
 
         let result = combat.fight();
 
@@ -332,7 +342,7 @@ mod part1 {
 
     #[test]
     fn example4_final() {
-        let mut combat = parse(EXAMPLE4_INPUT);
+        let mut combat = parse(EXAMPLE4_INPUT).unwrap();
 
         let result = combat.fight();
 
@@ -343,7 +353,7 @@ mod part1 {
 
     #[test]
     fn example5_final() {
-        let mut combat = parse(EXAMPLE5_INPUT);
+        let mut combat = parse(EXAMPLE5_INPUT).unwrap();
 
         let result = combat.fight();
 
@@ -354,7 +364,7 @@ mod part1 {
 
     #[test]
     fn example6_final() {
-        let mut combat = parse(EXAMPLE6_INPUT);
+        let mut combat = parse(EXAMPLE6_INPUT).unwrap();
 
         let result = combat.fight();
 
@@ -366,7 +376,7 @@ mod part1 {
     #[ignore]
     #[test]
     fn answer() {
-        let combat = parse(INPUT);
+        let combat = parse(INPUT).unwrap();
 
         let answer = fight(&combat);
 
@@ -471,7 +481,7 @@ mod shortest_path {
 
     #[test]
     fn example1_elf1_goblin1() {
-        let combat_map = parse(EXAMPLE1_INPUT);
+        let combat_map = parse(EXAMPLE1_INPUT).unwrap();
 
         let path1 = combat_map.shortest_path(Position::new(4, 2), Position::new(2, 1));
         assert_eq!(
@@ -487,7 +497,7 @@ mod shortest_path {
 
     #[test]
     fn example1_elf1_goblin2() {
-        let combat_map = parse(EXAMPLE1_INPUT);
+        let combat_map = parse(EXAMPLE1_INPUT).unwrap();
 
         let path1 = combat_map.shortest_path(Position::new(4, 2), Position::new(5, 2));
         assert_eq!(path1, vec![Position::new(4, 2), Position::new(5, 2),]);
@@ -495,7 +505,7 @@ mod shortest_path {
 
     #[test]
     fn example1_elf1_goblin3() {
-        let combat_map = parse(EXAMPLE1_INPUT);
+        let combat_map = parse(EXAMPLE1_INPUT).unwrap();
 
         let path1 = combat_map.shortest_path(Position::new(4, 2), Position::new(5, 3));
         assert_eq!(path1, vec![]);
@@ -503,7 +513,7 @@ mod shortest_path {
 
     #[test]
     fn example1_elf1_goblin4() {
-        let combat_map = parse(EXAMPLE1_INPUT);
+        let combat_map = parse(EXAMPLE1_INPUT).unwrap();
 
         let path1 = combat_map.shortest_path(Position::new(4, 2), Position::new(3, 4));
         assert_eq!(
@@ -133,211 +133,29 @@
 //! [Advent of Code 2018 - Day 16](https://adventofcode.com/2018/day/16)
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
     fmt::{self, Display},
-    iter::FromIterator,
-    ops::{Index, IndexMut},
-    str::FromStr,
 };
 
-use self::Mnemonic::*;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Mnemonic {
-    AddR,
-    AddI,
-    MulR,
-    MulI,
-    BanR,
-    BanI,
-    BorR,
-    BorI,
-    SetR,
-    SetI,
-    GtIR,
-    GtRI,
-    GtRR,
-    EqIR,
-    EqRI,
-    EqRR,
-}
-
-const INSTRUCTION_SET: &[Mnemonic] = &[
-    AddR, AddI, MulR, MulI, BanR, BanI, BorR, BorI, SetR, SetI, GtIR, GtRI, GtRR, EqIR, EqRI, EqRR,
-];
-
-impl Display for Mnemonic {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let display = match *self {
-            AddR => "addr",
-            AddI => "addi",
-            MulR => "mulr",
-            MulI => "muli",
-            BanR => "banr",
-            BanI => "bani",
-            BorR => "borr",
-            BorI => "bori",
-            SetR => "setr",
-            SetI => "seti",
-            GtIR => "gtir",
-            GtRI => "gtri",
-            GtRR => "gtrr",
-            EqIR => "eqir",
-            EqRI => "eqri",
-            EqRR => "eqrr",
-        };
-        f.write_str(display)
-    }
-}
-
-impl FromStr for Mnemonic {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "addr" => AddR,
-            "addi" => AddI,
-            "mulr" => MulR,
-            "muli" => MulI,
-            "banr" => BanR,
-            "bani" => BanI,
-            "borr" => BorR,
-            "bori" => BorI,
-            "setr" => SetR,
-            "seti" => SetI,
-            "gtir" => GtIR,
-            "gtri" => GtRI,
-            "gtrr" => GtRR,
-            "eqir" => EqIR,
-            "eqri" => EqRI,
-            "eqrr" => EqRR,
-            _ => return Err(format!("unknown opcode {:?}", s)),
-        })
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct OpCode(pub u8);
-
-impl Display for OpCode {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:x}", self.0)
-    }
-}
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-pub type Data = u64;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Register([Data; 4]);
-
-impl Display for Register {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "[{}, {}, {}, {}]",
-            self.0[0], self.0[1], self.0[2], self.0[3]
-        )
-    }
-}
-
-impl Default for Register {
-    fn default() -> Self {
-        Register([0; 4])
-    }
-}
-
-impl From<[Data; 4]> for Register {
-    fn from(value: [Data; 4]) -> Self {
-        Register(value)
-    }
-}
-
-impl Index<Data> for Register {
-    type Output = Data;
-
-    fn index(&self, index: Data) -> &Self::Output {
-        &self.0[index as usize]
-    }
-}
-
-impl IndexMut<Data> for Register {
-    fn index_mut(&mut self, index: Data) -> &mut <Self as Index<Data>>::Output {
-        &mut self.0[index as usize]
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Instruction {
-    pub opcode: OpCode,
-    pub a: Data,
-    pub b: Data,
-    pub c: Data,
-}
-
-impl Display for Instruction {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {} {} {}", self.opcode, self.a, self.b, self.c)
-    }
-}
-
-impl From<(u8, Data, Data, Data)> for Instruction {
-    fn from((opc, a, b, c): (u8, Data, Data, Data)) -> Self {
-        Self {
-            opcode: OpCode(opc),
-            a,
-            b,
-            c,
-        }
-    }
-}
-
-impl Instruction {
-    pub fn new(opcode: impl Into<OpCode>, a: Data, b: Data, c: Data) -> Self {
-        Self {
-            opcode: opcode.into(),
-            a,
-            b,
-            c,
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct Interpreter {
-    opcodes: HashMap<OpCode, Mnemonic>,
-}
-
-impl Interpreter {
-    pub fn with_instruction_set(
-        instruction_set: impl IntoIterator<Item = (OpCode, Mnemonic)>,
-    ) -> Self {
-        Self {
-            opcodes: HashMap::from_iter(instruction_set.into_iter()),
-        }
-    }
+use crate::{
+    elfcode::{self, OpCodeTable, INSTRUCTION_SET},
+    error::{self, ParseError},
+};
 
-    pub fn execute(&self, instruction: Instruction, register: &mut Register) -> Result<(), String> {
-        let &mnemonic = self.opcodes.get(&instruction.opcode).ok_or_else(|| {
-            format!(
-                "unsupported opcode {} in instruction {}",
-                instruction.opcode, instruction
-            )
-        })?;
+pub use crate::elfcode::{Data, Mnemonic, OpCode};
 
-        execute_mnemonic(mnemonic, instruction, register);
+pub type Register = elfcode::Register<4>;
 
-        Ok(())
-    }
+pub type Instruction = elfcode::Instruction<OpCode>;
 
-    pub fn run(&self, program: &[Instruction], register: &mut Register) -> Result<(), String> {
-        for &instruction in program {
-            self.execute(instruction, register)?;
-        }
-        Ok(())
-    }
-}
+pub type Interpreter = elfcode::Interpreter<OpCodeTable, 4>;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Sample {
     before: Register,
     instruction: Instruction,
@@ -378,151 +196,305 @@ impl Sample {
     }
 }
 
-#[cfg_attr(feature = "cargo-clippy", allow(clippy::unneeded_field_pattern))]
-fn execute_mnemonic(
-    mnemonic: Mnemonic,
-    Instruction { opcode: _, a, b, c }: Instruction,
-    reg: &mut Register,
-) {
-    match mnemonic {
-        AddR => reg[c] = reg[a] + reg[b],
-        AddI => reg[c] = reg[a] + b,
-        MulR => reg[c] = reg[a] * reg[b],
-        MulI => reg[c] = reg[a] * b,
-        BanR => reg[c] = reg[a] & reg[b],
-        BanI => reg[c] = reg[a] & b,
-        BorR => reg[c] = reg[a] | reg[b],
-        BorI => reg[c] = reg[a] | b,
-        SetR => reg[c] = reg[a],
-        SetI => reg[c] = a,
-        GtIR => reg[c] = if a > reg[b] { 1 } else { 0 },
-        GtRI => reg[c] = if reg[a] > b { 1 } else { 0 },
-        GtRR => reg[c] = if reg[a] > reg[b] { 1 } else { 0 },
-        EqIR => reg[c] = if a == reg[b] { 1 } else { 0 },
-        EqRI => reg[c] = if reg[a] == b { 1 } else { 0 },
-        EqRR => reg[c] = if reg[a] == reg[b] { 1 } else { 0 },
-    }
-}
-
 fn possible_mnemonics(sample: Sample) -> Vec<Mnemonic> {
     let mut possible_mnemonics = Vec::default();
     for &mnemonic in INSTRUCTION_SET {
         let mut register = sample.before;
-        execute_mnemonic(mnemonic, sample.instruction, &mut register);
-        if register == sample.after {
+        let executed = elfcode::execute(sample.instruction.with_opcode(mnemonic), &mut register);
+        if executed.is_ok() && register == sample.after {
             possible_mnemonics.push(mnemonic);
         }
     }
     possible_mnemonics
 }
 
-fn decode_opcodes(samples: &[Sample]) -> HashMap<OpCode, Mnemonic> {
-    let mut opcodes: HashMap<OpCode, Mnemonic> = HashMap::with_capacity(16);
-    let mut mnemonics: HashMap<OpCode, HashSet<Mnemonic>> = HashMap::with_capacity(16);
-    for &sample in samples {
-        mnemonics
-            .entry(sample.instruction.opcode)
-            .or_insert_with(HashSet::default)
-            .extend(possible_mnemonics(sample));
+/// Why the opcodes could not be decoded from the samples.
+///
+/// Samples are referred to by their index in the list of samples.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    /// The sample does not behave like any operation.
+    NoMatchingOperation { sample: usize },
+    /// The samples of the same opcode behave like different operations.
+    ContradictingSamples { opcode: OpCode, samples: Vec<usize> },
+    /// There is no way to assign a different operation to each opcode.
+    Unsatisfiable {
+        candidates: Vec<(OpCode, Vec<Mnemonic>)>,
+    },
+    /// The samples allow more than one operation for these opcodes.
+    Ambiguous {
+        candidates: Vec<(OpCode, Vec<Mnemonic>)>,
+    },
+}
+
+fn format_candidates(candidates: &[(OpCode, Vec<Mnemonic>)]) -> String {
+    candidates
+        .iter()
+        .map(|(opcode, mnemonics)| {
+            let mnemonics = mnemonics
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            format!("{} -> {}", opcode, mnemonics.join("|"))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::NoMatchingOperation { sample } => {
+                write!(f, "sample {} does not behave like any operation", sample)
+            },
+            DecodeError::ContradictingSamples { opcode, samples } => {
+                let samples = samples.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(
+                    f,
+                    "samples {} of opcode {} behave like different operations",
+                    samples.join(", "),
+                    opcode
+                )
+            },
+            DecodeError::Unsatisfiable { candidates } => write!(
+                f,
+                "no distinct operation can be assigned to each opcode, candidates: {}",
+                format_candidates(candidates)
+            ),
+            DecodeError::Ambiguous { candidates } => write!(
+                f,
+                "opcodes remain ambiguous: {}",
+                format_candidates(candidates)
+            ),
+        }
     }
-    while !mnemonics.is_empty() {
-        debug!("{:?}\n", mnemonics);
-        debug!("opcodes: {:?}\n\n", opcodes);
-        let clarified_opcodes: Vec<(OpCode, Mnemonic)> = mnemonics
-            .iter()
-            .filter_map(|(&opc, mnes)| {
-                if mnes.len() == 1 {
-                    Some((opc, *mnes.iter().next().unwrap()))
-                } else {
-                    None
-                }
-            })
-            .collect();
-        for (opc, mnem) in clarified_opcodes {
-            mnemonics.values_mut().for_each(|mnes| {
-                mnes.remove(&mnem);
+}
+
+impl Error for DecodeError {}
+
+type Candidates = BTreeMap<OpCode, BTreeSet<Mnemonic>>;
+
+/// Finds the samples of an opcode that leave no operation in common. These
+/// are two samples without a common operation if there are any, otherwise
+/// all samples up to the last one.
+fn contradicting_samples(possible: &[(usize, BTreeSet<Mnemonic>)]) -> Vec<usize> {
+    let (last, last_possible) = possible.last().expect("at least one sample");
+    possible
+        .iter()
+        .find(|(_, mnemonics)| mnemonics.is_disjoint(last_possible))
+        .map(|(idx, _)| vec![*idx, *last])
+        .unwrap_or_else(|| possible.iter().map(|(idx, _)| *idx).collect())
+}
+
+fn sample_candidates(samples: &[Sample]) -> Result<Candidates, DecodeError> {
+    let mut candidates = Candidates::new();
+    let mut possible_by_opcode: HashMap<OpCode, Vec<(usize, BTreeSet<Mnemonic>)>> =
+        HashMap::with_capacity(16);
+    for (idx, &sample) in samples.iter().enumerate() {
+        let possible: BTreeSet<Mnemonic> = possible_mnemonics(sample).into_iter().collect();
+        if possible.is_empty() {
+            return Err(DecodeError::NoMatchingOperation { sample: idx });
+        }
+        let opcode = sample.instruction.opcode;
+        let mnemonics = candidates.entry(opcode).or_insert_with(|| possible.clone());
+        mnemonics.retain(|mnemonic| possible.contains(mnemonic));
+        let possible_of_opcode = possible_by_opcode.entry(opcode).or_default();
+        possible_of_opcode.push((idx, possible));
+        if mnemonics.is_empty() {
+            return Err(DecodeError::ContradictingSamples {
+                opcode,
+                samples: contradicting_samples(possible_of_opcode),
             });
-            opcodes.insert(opc, mnem);
         }
-        mnemonics.retain(|_, mnes| !mnes.is_empty());
     }
-    opcodes
+    Ok(candidates)
 }
 
-fn parse_register(s: &str) -> Result<Register, String> {
-    let mut values = [0; 4];
-    let mut c_idx = 0;
-    let mut c_val = String::with_capacity(1);
-    for chr in s.chars() {
-        match chr {
-            '[' => {},
-            ']' => {
-                let value = c_val.parse::<Data>().map_err(|e| e.to_string())?;
-                values[c_idx] = value;
-                c_idx += 1;
-                c_val.clear();
-                break;
-            },
-            ',' => {
-                let value = c_val.parse::<Data>().map_err(|e| e.to_string())?;
-                values[c_idx] = value;
-                c_idx += 1;
-                c_val.clear();
-            },
-            ' ' => {},
-            _ if chr.is_digit(10) => c_val.push(chr),
-            _ => return Err(format!("unexpected character {}", chr)),
+/// Assigns the operation of each opcode that has only one candidate left and
+/// removes that operation from the candidates of all other opcodes, until no
+/// more opcodes can be resolved this way. Returns `false` if an opcode is
+/// left without any candidate.
+fn eliminate(candidates: &mut Candidates) -> bool {
+    let mut resolved = BTreeSet::new();
+    loop {
+        let newly_resolved: Vec<(OpCode, Mnemonic)> = candidates
+            .iter()
+            .filter(|(opcode, mnemonics)| mnemonics.len() == 1 && !resolved.contains(*opcode))
+            .map(|(&opcode, mnemonics)| (opcode, *mnemonics.iter().next().unwrap()))
+            .collect();
+        if newly_resolved.is_empty() {
+            return true;
+        }
+        for (opcode, mnemonic) in newly_resolved {
+            resolved.insert(opcode);
+            for (_, mnemonics) in candidates.iter_mut().filter(|(opc, _)| **opc != opcode) {
+                mnemonics.remove(&mnemonic);
+            }
+        }
+        debug!("candidates after elimination: {:?}", candidates);
+        if candidates.values().any(BTreeSet::is_empty) {
+            return false;
         }
     }
-    if c_idx != 4 {
-        Err(format!("expected 4 values but got {}", values.len()))
-    } else {
-        Ok(Register::from(values))
+}
+
+/// Looks for an augmenting path that starts at the opcode with the given
+/// index, reassigning the operations along the path so that this opcode gets
+/// one as well.
+fn augment(
+    opcodes: &[(OpCode, Vec<Mnemonic>)],
+    idx: usize,
+    visited: &mut BTreeSet<Mnemonic>,
+    assigned: &mut BTreeMap<Mnemonic, usize>,
+) -> bool {
+    for &mnemonic in &opcodes[idx].1 {
+        if visited.insert(mnemonic) {
+            let free = match assigned.get(&mnemonic) {
+                Some(&other) => augment(opcodes, other, visited, assigned),
+                None => true,
+            };
+            if free {
+                assigned.insert(mnemonic, idx);
+                return true;
+            }
+        }
     }
+    false
 }
 
-fn parse_instruction(line: &str) -> Result<Instruction, String> {
-    let mut parts = line.split(' ');
-    let opcode = parse_opcode(&mut parts, line)?;
-    let data1 = parse_data(&mut parts, line)?;
-    let data2 = parse_data(&mut parts, line)?;
-    let data3 = parse_data(&mut parts, line)?;
-    Ok(Instruction::from((opcode, data1, data2, data3)))
+/// Whether a distinct operation can be assigned to each opcode, i.e. whether
+/// the maximum matching between opcodes and operations is perfect.
+fn has_complete_assignment(opcodes: &[(OpCode, Vec<Mnemonic>)]) -> bool {
+    let mut assigned = BTreeMap::new();
+    (0..opcodes.len()).all(|idx| augment(opcodes, idx, &mut BTreeSet::new(), &mut assigned))
 }
 
-fn parse_opcode<'a>(parts: &mut impl Iterator<Item = &'a str>, line: &str) -> Result<u8, String> {
-    parts
-        .next()
-        .ok_or_else(|| format!("unexpected end of line in {:?}", line))
-        .and_then(|part| part.trim().parse::<u8>().map_err(|e| e.to_string()))
+/// Narrows the candidates of each opcode down to the operations that are
+/// part of at least one complete assignment. A candidate is feasible if the
+/// matching is still perfect when the opcode is restricted to it.
+fn feasible_candidates(candidates: &Candidates) -> Vec<(OpCode, Vec<Mnemonic>)> {
+    let opcodes: Vec<(OpCode, Vec<Mnemonic>)> = candidates
+        .iter()
+        .map(|(&opcode, mnemonics)| (opcode, mnemonics.iter().cloned().collect()))
+        .collect();
+    (0..opcodes.len())
+        .map(|idx| {
+            let (opcode, mnemonics) = &opcodes[idx];
+            let feasible = mnemonics
+                .iter()
+                .cloned()
+                .filter(|&mnemonic| {
+                    let mut fixed = opcodes.clone();
+                    fixed[idx].1 = vec![mnemonic];
+                    has_complete_assignment(&fixed)
+                })
+                .collect();
+            (*opcode, feasible)
+        })
+        .collect()
 }
 
-fn parse_data<'a>(parts: &mut impl Iterator<Item = &'a str>, line: &str) -> Result<Data, String> {
-    parts
-        .next()
-        .ok_or_else(|| format!("unexpected end of line in {:?}", line))
-        .and_then(|part| part.trim().parse::<Data>().map_err(|e| e.to_string()))
+/// Decodes which operation each opcode of the samples performs.
+///
+/// The operations are narrowed down by elimination first. If elimination
+/// stalls before every opcode is resolved, the remaining candidates are
+/// matched to the operations, which fails if no matching assigns an operation
+/// to every opcode.
+pub fn decode_opcodes(samples: &[Sample]) -> Result<HashMap<OpCode, Mnemonic>, DecodeError> {
+    let mut candidates = sample_candidates(samples)?;
+    let unsatisfiable = |candidates: &Candidates| DecodeError::Unsatisfiable {
+        candidates: candidates
+            .iter()
+            .map(|(&opcode, mnemonics)| (opcode, mnemonics.iter().cloned().collect()))
+            .collect(),
+    };
+    let sampled = candidates.clone();
+    if !eliminate(&mut candidates) {
+        return Err(unsatisfiable(&sampled));
+    }
+    if candidates.values().any(|mnemonics| mnemonics.len() > 1) {
+        debug!("elimination stalled, falling back to matching");
+        let remaining: Vec<(OpCode, Vec<Mnemonic>)> = candidates
+            .iter()
+            .map(|(&opcode, mnemonics)| (opcode, mnemonics.iter().cloned().collect()))
+            .collect();
+        if !has_complete_assignment(&remaining) {
+            return Err(unsatisfiable(&sampled));
+        }
+        let feasible = feasible_candidates(&candidates);
+        let ambiguous: Vec<(OpCode, Vec<Mnemonic>)> = feasible
+            .iter()
+            .filter(|(_, mnemonics)| mnemonics.len() > 1)
+            .cloned()
+            .collect();
+        if !ambiguous.is_empty() {
+            return Err(DecodeError::Ambiguous {
+                candidates: ambiguous,
+            });
+        }
+        candidates = feasible
+            .into_iter()
+            .map(|(opcode, mnemonics)| (opcode, mnemonics.into_iter().collect()))
+            .collect();
+    }
+    Ok(candidates
+        .into_iter()
+        .map(|(opcode, mnemonics)| (opcode, *mnemonics.iter().next().unwrap()))
+        .collect())
+}
+
+fn parse_register(line_no: usize, line: &str, values: &str) -> Result<Register, ParseError> {
+    let expected = "registers like \"[0, 1, 2, 3]\"";
+    let values = values.trim();
+    let mut parts = values
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or_else(|| ParseError::at(16, line_no, line, values, expected))?
+        .split(',');
+    let mut register = [0; 4];
+    for value in register.iter_mut() {
+        let part = parts
+            .next()
+            .ok_or_else(|| ParseError::at(16, line_no, line, values, expected))?;
+        *value = error::number(16, line_no, line, part.trim(), "a register value")?;
+    }
+    if let Some(extra) = parts.next() {
+        return Err(ParseError::at(16, line_no, line, extra.trim(), "\"]\""));
+    }
+    Ok(Register::from(register))
+}
+
+fn parse_instruction(line_no: usize, line: &str) -> Result<Instruction, ParseError> {
+    line.parse::<Instruction>()
+        .map_err(|e| e.with_line(line_no).into_parse_error(16))
 }
 
 #[aoc_generator(day16)]
-pub fn parse(input: &str) -> Result<(Vec<Sample>, Vec<Instruction>), String> {
+pub fn parse(input: &str) -> Result<(Vec<Sample>, Vec<Instruction>), ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<(Vec<Sample>, Vec<Instruction>), ParseError> {
     let mut samples = Vec::with_capacity(16);
     let mut instructions = Vec::with_capacity(16);
-    let mut lines = input.lines();
-    while let Some(line) = lines.next() {
-        if line.starts_with("Before:") {
-            let before = parse_register(&line[8..])?;
-            let line = lines
+    let mut lines = input.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+    while let Some((line_no, line)) = lines.next() {
+        if let Some(values) = line.strip_prefix("Before:") {
+            let before = parse_register(line_no, line, values)?;
+            let (line_no, line) = lines
+                .next()
+                .ok_or_else(|| ParseError::end_of_input(16, input, "a sample instruction"))?;
+            let instruction = parse_instruction(line_no, line)?;
+            let (line_no, line) = lines
                 .next()
-                .ok_or_else(|| "sample instruction expected but no more input left".to_string())?;
-            let instruction = parse_instruction(line)?;
-            let line = lines.next().ok_or_else(|| {
-                "register after instruction expected but no more input left".to_string()
-            })?;
-            let after = parse_register(&line[8..])?;
+                .ok_or_else(|| ParseError::end_of_input(16, input, "\"After:\""))?;
+            let values = line
+                .strip_prefix("After:")
+                .ok_or_else(|| ParseError::at(16, line_no, line, line, "\"After:\""))?;
+            let after = parse_register(line_no, line, values)?;
             samples.push(Sample::new(before, instruction, after));
         } else if !line.trim().is_empty() {
-            let instruction = parse_instruction(line)?;
+            let instruction = parse_instruction(line_no, line)?;
             instructions.push(instruction);
         }
     }
@@ -542,14 +514,17 @@ pub fn num_samples_behaving_like_three_or_more_opcodes(
 }
 
 #[aoc(day16, part2)]
-pub fn run_program((samples, program): &(Vec<Sample>, Vec<Instruction>)) -> Register {
-    let opcodes = decode_opcodes(samples);
+pub fn run_program(
+    (samples, program): &(Vec<Sample>, Vec<Instruction>),
+) -> Result<Register, String> {
+    let opcodes =
+        decode_opcodes(samples).map_err(|err| format!("can not decode opcodes: {}", err))?;
     let mut register = Register::default();
-    let interpreter = Interpreter::with_instruction_set(opcodes);
+    let mut interpreter = Interpreter::new(OpCodeTable::from(opcodes));
     interpreter
         .run(program, &mut register)
-        .unwrap_or_else(|err| println!("error executing program: {}", err));
-    register
+        .map_err(|err| format!("error executing program: {}", err))?;
+    Ok(register)
 }
 
 #[cfg(test)]
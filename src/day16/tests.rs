@@ -21,6 +21,128 @@ mod parse_input {
     }
 }
 
+mod decode_opcodes {
+    use super::*;
+    use crate::elfcode::Mnemonic::*;
+
+    #[test]
+    fn answer() {
+        let (samples, _) = parse(INPUT).unwrap();
+
+        let opcodes = decode_opcodes(&samples).unwrap();
+
+        assert_eq!(opcodes.len(), 16);
+        assert_eq!(opcodes.values().cloned().collect::<BTreeSet<_>>().len(), 16);
+    }
+
+    #[test]
+    fn sample_without_matching_operation() {
+        let samples = vec![Sample::new([0, 0, 0, 0], (1, 0, 0, 0), [5, 5, 5, 5])];
+
+        let opcodes = decode_opcodes(&samples);
+
+        assert_eq!(opcodes, Err(DecodeError::NoMatchingOperation { sample: 0 }));
+    }
+
+    #[test]
+    fn contradicting_samples() {
+        let samples = vec![
+            Sample::new([3, 2, 1, 1], (9, 2, 1, 2), [3, 2, 2, 1]),
+            Sample::new([0, 0, 0, 0], (3, 7, 9, 2), [0, 0, 7, 0]),
+            Sample::new([5, 3, 0, 0], (9, 0, 1, 2), [5, 3, 1, 0]),
+        ];
+
+        let opcodes = decode_opcodes(&samples);
+
+        assert_eq!(
+            opcodes,
+            Err(DecodeError::ContradictingSamples {
+                opcode: OpCode(9),
+                samples: vec![0, 2],
+            })
+        );
+        assert_eq!(
+            opcodes.unwrap_err().to_string(),
+            "samples 0, 2 of opcode 9 behave like different operations"
+        );
+    }
+
+    #[test]
+    fn two_opcodes_with_the_same_single_operation() {
+        let samples = vec![
+            Sample::new([0, 0, 0, 0], (1, 7, 9, 2), [0, 0, 7, 0]),
+            Sample::new([0, 0, 0, 0], (2, 7, 9, 2), [0, 0, 7, 0]),
+        ];
+
+        let opcodes = decode_opcodes(&samples);
+
+        assert_eq!(
+            opcodes,
+            Err(DecodeError::Unsatisfiable {
+                candidates: vec![(OpCode(1), vec![SetI]), (OpCode(2), vec![SetI])],
+            })
+        );
+    }
+
+    #[test]
+    fn ambiguous_opcodes() {
+        let samples = vec![
+            Sample::new([3, 2, 1, 1], (9, 2, 1, 2), [3, 2, 2, 1]),
+            Sample::new([3, 2, 1, 1], (4, 2, 1, 2), [3, 2, 2, 1]),
+            Sample::new([0, 0, 0, 0], (1, 7, 9, 2), [0, 0, 7, 0]),
+        ];
+
+        let opcodes = decode_opcodes(&samples);
+
+        assert_eq!(
+            opcodes,
+            Err(DecodeError::Ambiguous {
+                candidates: vec![(OpCode(4), vec![AddI, MulR]), (OpCode(9), vec![AddI, MulR])],
+            })
+        );
+        assert_eq!(
+            opcodes.unwrap_err().to_string(),
+            "opcodes remain ambiguous: 4 -> addi|mulr, 9 -> addi|mulr"
+        );
+    }
+
+    #[test]
+    fn more_opcodes_than_shared_operations() {
+        let samples = (0..16)
+            .map(|opcode| Sample::new([0, 0, 0, 0], (opcode, 0, 0, 0), [0, 0, 0, 0]))
+            .collect::<Vec<_>>();
+
+        let opcodes = decode_opcodes(&samples);
+
+        match opcodes {
+            Err(DecodeError::Unsatisfiable { candidates }) => assert_eq!(candidates.len(), 16),
+            other => panic!("expected unsatisfiable samples, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn matching_narrows_down_stalled_candidates() {
+        let candidates: Candidates = vec![
+            (OpCode(0), vec![AddR, AddI].into_iter().collect()),
+            (OpCode(1), vec![AddR, AddI].into_iter().collect()),
+            (OpCode(2), vec![AddR, AddI, MulR].into_iter().collect()),
+        ]
+        .into_iter()
+        .collect();
+
+        let feasible = feasible_candidates(&candidates);
+
+        assert_eq!(
+            feasible,
+            vec![
+                (OpCode(0), vec![AddR, AddI]),
+                (OpCode(1), vec![AddR, AddI]),
+                (OpCode(2), vec![MulR]),
+            ]
+        );
+    }
+}
+
 mod part1 {
     use super::*;
 
@@ -50,7 +172,7 @@ mod part2 {
     fn answer() {
         let samples_n_program = parse(INPUT).unwrap();
 
-        let answer = run_program(&samples_n_program);
+        let answer = run_program(&samples_n_program).unwrap();
 
         assert_eq!(answer, Register::from([681, 681, 3, 0]));
     }
@@ -220,10 +220,20 @@ use std::{
     iter::FromIterator,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{self, ParseError},
+    grid::{self, Pos},
+    render::{self, Render, Rgb},
+};
+
 use self::Drops::*;
 use self::Matter::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Matter {
     Sand,
     Clay,
@@ -249,6 +259,7 @@ pub const MAX_COORD: Coord = std::u16::MAX;
 pub const MIN_COORD: Coord = std::u16::MIN;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Position {
     pub x: Coord,
     pub y: Coord,
@@ -296,6 +307,7 @@ pub fn area_of_tiles<'a>(tiles: impl IntoIterator<Item = &'a Position>) -> (Posi
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Spring(pub Position);
 
 impl Default for Spring {
@@ -305,7 +317,9 @@ impl Default for Spring {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Scan {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pairs"))]
     tiles: HashMap<Position, Matter>,
 }
 
@@ -332,15 +346,18 @@ impl Scan {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Drops {
     Down,
     LeftRight(Coord, Coord),
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WaterCourse {
     max_y: Coord,
     spring: Position,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pairs"))]
     tiles: HashMap<Position, Matter>,
     water: Vec<(Position, Drops)>,
 }
@@ -502,6 +519,34 @@ impl Display for WaterCourse {
     }
 }
 
+/// The frame spans from the spring down to the lowest clay and one column
+/// beyond the clay on either side, so it does not grow with the water.
+impl Render for WaterCourse {
+    fn render(&self) -> grid::Grid<Rgb, render::Coord> {
+        let cell = |pos: Position| Pos::new(render::Coord::from(pos.x), render::Coord::from(pos.y));
+        let clay = self
+            .tiles
+            .iter()
+            .filter(|(_, &matter)| matter == Clay)
+            .map(|(&position, _)| cell(position));
+        let corners = grid::bounding_box(clay.chain(Some(cell(self.spring)))).map(
+            |(top_left, bottom_right)| (top_left - Pos::new(1, 0), bottom_right + Pos::new(1, 0)),
+        );
+        let mut frame = render::canvas(corners, Rgb(222, 200, 150));
+        for (&position, matter) in &self.tiles {
+            let color = match matter {
+                Sand => continue,
+                Clay => Rgb(139, 90, 43),
+                Water => Rgb(30, 90, 200),
+                Drop => Rgb(120, 180, 240),
+            };
+            frame.set(cell(position), color);
+        }
+        frame.set(cell(self.spring), Rgb(255, 255, 255));
+        frame
+    }
+}
+
 fn format_tiles(
     spring: Position,
     tiles: &HashMap<Position, Matter>,
@@ -533,81 +578,52 @@ fn format_tiles(
 }
 
 #[aoc_generator(day17)]
-pub fn parse(input: &str) -> Scan {
-    let mut clay_tiles = HashMap::with_capacity(32);
-
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-    enum Value {
-        Xmin,
-        Xmax,
-        Ymin,
-        Ymax,
-    }
+pub fn parse(input: &str) -> Result<Scan, ParseError> {
+    try_parse(input)
+}
 
-    for line in input.lines() {
-        let mut x_min = 0;
-        let mut x_max = 0;
-        let mut y_min = 0;
-        let mut y_max = 0;
-        let mut param = Value::Xmin;
-        let mut value = String::with_capacity(4);
-
-        let mut save_value = |param: Value, value: &str| {
-            let number = value.parse::<Coord>().unwrap();
-            match param {
-                Value::Xmin => {
-                    x_min = number;
-                    x_max = number;
-                },
-                Value::Xmax => x_max = number,
-                Value::Ymin => {
-                    y_min = number;
-                    y_max = number;
-                },
-                Value::Ymax => y_max = number,
-            }
-        };
+pub fn try_parse(input: &str) -> Result<Scan, ParseError> {
+    let mut clay_tiles = HashMap::with_capacity(32);
 
-        for chr in line.chars() {
-            match chr {
-                'x' => param = Value::Xmin,
-                'y' => param = Value::Ymin,
-                '.' => {
-                    if !value.is_empty() {
-                        save_value(param, &value);
-                        value.clear();
-                    }
-                    param = match param {
-                        Value::Xmin => Value::Xmax,
-                        Value::Ymin => Value::Ymax,
-                        _ => param,
-                    }
-                },
-                ',' => {
-                    if !value.is_empty() {
-                        save_value(param, &value);
-                        value.clear();
-                    }
-                },
-                '=' => {},
-                _ if chr.is_digit(10) => value.push(chr),
-                _ if chr.is_whitespace() => {},
-                _ => panic!("unexpected character {} in line {}", chr, line),
-            }
+    for (idx, line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        if line.trim().is_empty() {
+            continue;
         }
-        if !value.is_empty() {
-            save_value(param, &value);
-            value.clear();
-
-            for y in y_min..=y_max {
-                for x in x_min..=x_max {
-                    clay_tiles.insert(Position::new(x, y), Matter::Clay);
-                }
+        let mut x_range = None;
+        let mut y_range = None;
+        for part in line.split(',').map(str::trim) {
+            let (axis, range) = match part.find('=') {
+                Some(pos) => (&part[..pos], &part[pos + 1..]),
+                None => return Err(ParseError::at(17, line_no, line, part, "\"x=\" or \"y=\"")),
+            };
+            let coords = match axis {
+                "x" if x_range.is_none() => &mut x_range,
+                "y" if y_range.is_none() => &mut y_range,
+                _ => return Err(ParseError::at(17, line_no, line, axis, "\"x\" or \"y\"")),
+            };
+            let mut bounds = range.split("..");
+            let min = bounds.next().unwrap_or(range);
+            let min = error::number::<Coord>(17, line_no, line, min, "a coordinate")?;
+            let max = match bounds.next() {
+                Some(max) => error::number(17, line_no, line, max, "a coordinate")?,
+                None => min,
+            };
+            *coords = Some((min, max));
+        }
+        let ((x_min, x_max), (y_min, y_max)) = match (x_range, y_range) {
+            (Some(x_range), Some(y_range)) => (x_range, y_range),
+            (None, _) => return Err(ParseError::end_of_line(17, line_no, line, "\"x=\"")),
+            (_, None) => return Err(ParseError::end_of_line(17, line_no, line, "\"y=\"")),
+        };
+        for y in y_min..=y_max {
+            for x in x_min..=x_max {
+                clay_tiles.insert(Position::new(x, y), Matter::Clay);
             }
         }
     }
 
-    Scan::new(clay_tiles)
+    Ok(Scan::new(clay_tiles))
 }
 
 #[aoc(day17, part1)]
@@ -640,7 +656,7 @@ pub fn num_tiles_flooded_by_water_after_spring_has_run_dry(scan: &Scan) -> usize
     while let Some(_) = water_walker.next() {
         debug!("{}", water_walker);
     }
-    println!("{}", water_walker);
+    debug!("{}", water_walker);
     water_walker
         .tiles()
         .iter()
@@ -44,16 +44,26 @@ y=12, x=503..505
 mod parse_input {
     use super::*;
 
+    #[test]
+    fn invalid_coordinate() {
+        let result = try_parse("x=495, y=2..7\ny=7, x=495..z\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(17, 2, 13, "a coordinate", "\"z\""))
+        );
+    }
+
     #[test]
     fn parse_example1() {
-        let scan = parse(EXAMPLE1_INPUT);
+        let scan = parse(EXAMPLE1_INPUT).unwrap();
         eprintln!("{}", scan);
         assert_eq!(scan.tiles.len(), 34);
     }
 
     #[test]
     fn parse_input() {
-        let scan = parse(INPUT);
+        let scan = parse(INPUT).unwrap();
         eprintln!("{}", scan);
         assert_eq!(scan.tiles.len(), 16721);
     }
@@ -64,7 +74,7 @@ mod part1 {
 
     #[test]
     fn example1() {
-        let scan = parse(EXAMPLE1_INPUT);
+        let scan = parse(EXAMPLE1_INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water(&scan);
 
@@ -73,7 +83,7 @@ mod part1 {
 
     #[test]
     fn example2() {
-        let scan = parse(EXAMPLE2_INPUT);
+        let scan = parse(EXAMPLE2_INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water(&scan);
 
@@ -82,7 +92,7 @@ mod part1 {
 
     #[test]
     fn example3() {
-        let scan = parse(EXAMPLE3_INPUT);
+        let scan = parse(EXAMPLE3_INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water(&scan);
 
@@ -91,7 +101,7 @@ mod part1 {
 
     #[test]
     fn example4() {
-        let scan = parse(EXAMPLE4_INPUT);
+        let scan = parse(EXAMPLE4_INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water(&scan);
 
@@ -100,7 +110,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let scan = parse(INPUT);
+        let scan = parse(INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water(&scan);
 
@@ -113,7 +123,7 @@ mod part2 {
 
     #[test]
     fn example1() {
-        let scan = parse(EXAMPLE1_INPUT);
+        let scan = parse(EXAMPLE1_INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water_after_spring_has_run_dry(&scan);
 
@@ -122,7 +132,7 @@ mod part2 {
 
     #[test]
     fn example2() {
-        let scan = parse(EXAMPLE2_INPUT);
+        let scan = parse(EXAMPLE2_INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water_after_spring_has_run_dry(&scan);
 
@@ -131,7 +141,7 @@ mod part2 {
 
     #[test]
     fn example3() {
-        let scan = parse(EXAMPLE3_INPUT);
+        let scan = parse(EXAMPLE3_INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water_after_spring_has_run_dry(&scan);
 
@@ -140,7 +150,7 @@ mod part2 {
 
     #[test]
     fn example4() {
-        let scan = parse(EXAMPLE4_INPUT);
+        let scan = parse(EXAMPLE4_INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water_after_spring_has_run_dry(&scan);
 
@@ -150,7 +160,7 @@ mod part2 {
     #[ignore]
     #[test]
     fn answer() {
-        let scan = parse(INPUT);
+        let scan = parse(INPUT).unwrap();
 
         let answer = num_tiles_flooded_by_water_after_spring_has_run_dry(&scan);
 
@@ -189,126 +189,28 @@
 //! [Advent of Code 2018 - Day 18](https://adventofcode.com/2018/day/18)
 
 use std::{
-    cmp::{Ord, Ordering, PartialOrd},
     collections::HashMap,
     fmt::{self, Display},
     iter::FromIterator,
-    ops::Add,
 };
 
-use self::Resource::*;
-
-pub type Coord = i16;
-pub const MAX_COORD: Coord = std::i16::MAX;
-pub const MIN_COORD: Coord = std::i16::MIN;
-
-const ADJACENT_OFFSETS: [Position; 8] = [
-    Position { x: -1, y: -1 },
-    Position { x: 0, y: -1 },
-    Position { x: 1, y: -1 },
-    Position { x: -1, y: 0 },
-    Position { x: 1, y: 0 },
-    Position { x: -1, y: 1 },
-    Position { x: 0, y: 1 },
-    Position { x: 1, y: 1 },
-];
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Position {
-    pub x: Coord,
-    pub y: Coord,
-}
-
-impl Position {
-    pub const MAX: Position = Position {
-        x: MAX_COORD,
-        y: MAX_COORD,
-    };
-
-    pub const MIN: Position = Position {
-        x: MIN_COORD,
-        y: MIN_COORD,
-    };
-
-    pub fn new(x: Coord, y: Coord) -> Self {
-        Self { x, y }
-    }
-
-    pub fn adjacent(self) -> Adjacent {
-        Adjacent::new(self)
-    }
-}
-
-impl Display for Position {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({},{})", self.x, self.y)
-    }
-}
-
-impl Ord for Position {
-    fn cmp(&self, other: &Self) -> Ordering {
-        if self.y == other.y {
-            if self.x == other.x {
-                Ordering::Equal
-            } else if self.x < other.x {
-                Ordering::Less
-            } else {
-                Ordering::Greater
-            }
-        } else if self.y < other.y {
-            Ordering::Less
-        } else {
-            Ordering::Greater
-        }
-    }
-}
-
-impl PartialOrd for Position {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Add<Self> for Position {
-    type Output = Self;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-    fn add(self, rhs: Position) -> <Self as Add<Position>>::Output {
-        Position {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
+use crate::{
+    error::ParseError,
+    grid::{self, Pos},
+    render::{self, Render, Rgb},
+};
 
-#[derive(Debug)]
-pub struct Adjacent {
-    position: Position,
-    offset: usize,
-}
+use self::Resource::*;
 
-impl Adjacent {
-    fn new(position: Position) -> Self {
-        Self {
-            position,
-            offset: 0,
-        }
-    }
-}
+pub type Coord = i16;
 
-impl Iterator for Adjacent {
-    type Item = Position;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.offset == ADJACENT_OFFSETS.len() {
-            return None;
-        }
-        let position = self.position + ADJACENT_OFFSETS[self.offset];
-        self.offset += 1;
-        Some(position)
-    }
-}
+pub type Position = Pos<Coord>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Resource {
     OpenGround,
     Trees,
@@ -327,7 +229,9 @@ impl Display for Resource {
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Area {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pairs"))]
     resources: HashMap<Position, Resource>,
 }
 
@@ -352,6 +256,24 @@ impl Display for Area {
     }
 }
 
+impl Render for Area {
+    fn render(&self) -> grid::Grid<Rgb, render::Coord> {
+        let cell = |pos: Position| Pos::new(render::Coord::from(pos.x), render::Coord::from(pos.y));
+        let mut frame = render::canvas(
+            grid::bounding_box(self.resources.keys().cloned().map(cell)),
+            Rgb(200, 180, 120),
+        );
+        for (&position, resource) in &self.resources {
+            frame[cell(position)] = match resource {
+                OpenGround => Rgb(200, 180, 120),
+                Trees => Rgb(34, 139, 34),
+                Lumberyard => Rgb(110, 70, 30),
+            };
+        }
+        frame
+    }
+}
+
 impl Area {
     pub fn new() -> Self {
         Self {
@@ -372,23 +294,7 @@ impl Area {
     }
 
     pub fn corners(&self) -> (Position, Position) {
-        let mut top_left = Position::MAX;
-        let mut bottom_right = Position::MIN;
-        for pos in self.resources.keys() {
-            if pos.x < top_left.x {
-                top_left.x = pos.x;
-            }
-            if pos.y < top_left.y {
-                top_left.y = pos.y;
-            }
-            if pos.x > bottom_right.x {
-                bottom_right.x = pos.x;
-            }
-            if pos.y > bottom_right.y {
-                bottom_right.y = pos.y
-            }
-        }
-        (top_left, bottom_right)
+        grid::bounding_box(self.resources.keys().cloned()).unwrap_or((Position::MAX, Position::MIN))
     }
 
     pub fn resource(&self, position: Position) -> Resource {
@@ -459,7 +365,7 @@ impl Area {
                 match self.resources.get(&position).unwrap_or(&OpenGround) {
                     OpenGround => {
                         let mut num_adj_trees = 0;
-                        for adj_pos in position.adjacent() {
+                        for adj_pos in position.neighbours8() {
                             if Trees == self.resource(adj_pos) {
                                 num_adj_trees += 1;
                                 if num_adj_trees >= 3 {
@@ -471,7 +377,7 @@ impl Area {
                     },
                     Trees => {
                         let mut num_adj_lumberyards = 0;
-                        for adj_pos in position.adjacent() {
+                        for adj_pos in position.neighbours8() {
                             if Lumberyard == self.resource(adj_pos) {
                                 num_adj_lumberyards += 1;
                                 if num_adj_lumberyards >= 3 {
@@ -488,7 +394,7 @@ impl Area {
                     Lumberyard => {
                         let mut num_trees = 0;
                         let mut num_lumberyard = 0;
-                        for adj_pos in position.adjacent() {
+                        for adj_pos in position.neighbours8() {
                             match self.resource(adj_pos) {
                                 Trees => {
                                     num_trees += 1;
@@ -512,7 +418,11 @@ impl Area {
 }
 
 #[aoc_generator(day18)]
-pub fn parse(input: &str) -> Area {
+pub fn parse(input: &str) -> Result<Area, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Area, ParseError> {
     let mut area = Area::new();
     for (y, line) in input.lines().enumerate() {
         for (x, chr) in line.chars().enumerate() {
@@ -521,14 +431,22 @@ pub fn parse(input: &str) -> Area {
                 '|' => Trees,
                 '#' => Lumberyard,
                 _ if chr.is_whitespace() => continue,
-                _ => panic!("unexpected character {:?} in line: {:?}", chr, line),
+                _ => {
+                    return Err(ParseError::new(
+                        18,
+                        y + 1,
+                        x + 1,
+                        "'.', '|' or '#'",
+                        format!("{:?}", chr),
+                    ))
+                },
             };
             if resource != OpenGround {
                 area.set_resource(Position::new(x as Coord, y as Coord), resource);
             }
         }
     }
-    area
+    Ok(area)
 }
 
 #[aoc(day18, part1)]
@@ -148,16 +148,26 @@ const EXAMPLE1_AFTER_10_MINUTES: &str = "\
 mod parse {
     use super::*;
 
+    #[test]
+    fn unexpected_character() {
+        let result = try_parse(".#.\n|*.\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(18, 2, 2, "'.', '|' or '#'", "'*'"))
+        );
+    }
+
     #[test]
     fn example1_initial() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         assert_eq!(area.to_string(), EXAMPLE1_INITIAL);
     }
 
     #[test]
     fn input() {
-        let area = parse(INPUT);
+        let area = parse(INPUT).unwrap();
 
         assert_eq!(area.to_string(), INPUT);
     }
@@ -168,7 +178,7 @@ mod part1 {
 
     #[test]
     fn example1_after_1_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(1);
 
@@ -177,7 +187,7 @@ mod part1 {
 
     #[test]
     fn example1_after_2_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(2);
 
@@ -186,7 +196,7 @@ mod part1 {
 
     #[test]
     fn example1_after_3_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(3);
 
@@ -195,7 +205,7 @@ mod part1 {
 
     #[test]
     fn example1_after_4_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(4);
 
@@ -204,7 +214,7 @@ mod part1 {
 
     #[test]
     fn example1_after_5_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(5);
 
@@ -213,7 +223,7 @@ mod part1 {
 
     #[test]
     fn example1_after_6_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(6);
 
@@ -222,7 +232,7 @@ mod part1 {
 
     #[test]
     fn example1_after_7_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(7);
 
@@ -231,7 +241,7 @@ mod part1 {
 
     #[test]
     fn example1_after_8_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(8);
 
@@ -240,7 +250,7 @@ mod part1 {
 
     #[test]
     fn example1_after_9_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(9);
 
@@ -249,7 +259,7 @@ mod part1 {
 
     #[test]
     fn example1_after_10_minute() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let mutated = area.nth_generation(10);
 
@@ -258,7 +268,7 @@ mod part1 {
 
     #[test]
     fn example1() {
-        let area = parse(EXAMPLE1_INITIAL);
+        let area = parse(EXAMPLE1_INITIAL).unwrap();
 
         let answer = total_resource_value_after_10_minutes(&area);
 
@@ -267,7 +277,7 @@ mod part1 {
 
     #[test]
     fn answer() {
-        let area = parse(INPUT);
+        let area = parse(INPUT).unwrap();
 
         let answer = total_resource_value_after_10_minutes(&area);
 
@@ -280,7 +290,7 @@ mod part2 {
 
     #[test]
     fn answer() {
-        let area = parse(INPUT);
+        let area = parse(INPUT).unwrap();
 
         let answer = total_resource_value_after_1_000_000_000_minutes(&area);
 
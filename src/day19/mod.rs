@@ -110,266 +110,51 @@
 //!
 //! [Advent of Code 2018 - Day 19](https://adventofcode.com/2018/day/19)
 
-use std::{
-    fmt::{self, Display},
-    iter::FromIterator,
-    ops::{Index, IndexMut},
+use crate::{
+    elfcode::{self, assembler::AssembleError, Mnemonics},
+    error::ParseError,
 };
 
-use crate::day16::{Data, Mnemonic};
+pub use crate::elfcode::{Addr, Data, Mnemonic, Program};
 
-use self::Mnemonic::*;
+pub type Register = elfcode::Register<6>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Register([Data; 6]);
+pub type Instruction = elfcode::Instruction<Mnemonic>;
 
-impl Display for Register {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "[{}, {}, {}, {}, {}, {}]",
-            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
-        )
-    }
-}
-
-impl Default for Register {
-    fn default() -> Self {
-        Register([0; 6])
-    }
-}
-
-impl From<[Data; 6]> for Register {
-    fn from(value: [Data; 6]) -> Self {
-        Register(value)
-    }
-}
-
-impl Index<Data> for Register {
-    type Output = Data;
-
-    fn index(&self, index: Data) -> &Self::Output {
-        &self.0[index as usize]
-    }
-}
-
-impl IndexMut<Data> for Register {
-    fn index_mut(&mut self, index: Data) -> &mut <Self as Index<Data>>::Output {
-        &mut self.0[index as usize]
-    }
-}
+pub type Interpreter = elfcode::Interpreter<Mnemonics, 6>;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Instruction {
-    pub opcode: Mnemonic,
-    pub a: Data,
-    pub b: Data,
-    pub c: Data,
-}
-
-impl Display for Instruction {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {} {} {}", self.opcode, self.a, self.b, self.c)
-    }
-}
-
-impl From<(Mnemonic, Data, Data, Data)> for Instruction {
-    fn from((opcode, a, b, c): (Mnemonic, Data, Data, Data)) -> Self {
-        Self { opcode, a, b, c }
-    }
-}
-
-impl Instruction {
-    pub fn new(opcode: Mnemonic, a: Data, b: Data, c: Data) -> Self {
-        Self { opcode, a, b, c }
-    }
-}
-
-pub type Addr = Data;
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct Interpreter {
-    ip_reg: Addr,
-    ip: Addr,
-}
-
-impl Interpreter {
-    pub fn new(ip_reg: Addr) -> Self {
-        Self { ip_reg, ip: 0 }
-    }
-
-    #[inline]
-    pub fn execute(&mut self, instruction: Instruction, register: &mut Register) {
-        register[self.ip_reg] = self.ip;
-        execute_mnemonic(instruction, register);
-        self.ip = register[self.ip_reg] + 1;
-    }
-
-    pub fn run(&mut self, program: &[Instruction], register: &mut Register) -> Result<(), String> {
-        while let Some(&instruction) = program.get(self.ip as usize) {
-            if self.ip == 3 {
-                self.ip = optimized(register);
-                continue;
-            }
-            let _c_ip = self.ip;
-            self.execute(instruction, register);
-            //_trace(_c_ip, instruction, register);
-        }
-        Ok(())
-    }
-}
-
-fn execute_mnemonic(Instruction { opcode, a, b, c }: Instruction, reg: &mut Register) {
-    match opcode {
-        AddR => reg[c] = reg[a] + reg[b],
-        AddI => reg[c] = reg[a] + b,
-        MulR => reg[c] = reg[a] * reg[b],
-        MulI => reg[c] = reg[a] * b,
-        BanR => reg[c] = reg[a] & reg[b],
-        BanI => reg[c] = reg[a] & b,
-        BorR => reg[c] = reg[a] | reg[b],
-        BorI => reg[c] = reg[a] | b,
-        SetR => reg[c] = reg[a],
-        SetI => reg[c] = a,
-        GtIR => reg[c] = if a > reg[b] { 1 } else { 0 },
-        GtRI => reg[c] = if reg[a] > b { 1 } else { 0 },
-        GtRR => reg[c] = if reg[a] > reg[b] { 1 } else { 0 },
-        EqIR => reg[c] = if a == reg[b] { 1 } else { 0 },
-        EqRI => reg[c] = if reg[a] == b { 1 } else { 0 },
-        EqRR => reg[c] = if reg[a] == reg[b] { 1 } else { 0 },
-    }
-}
-
-#[inline]
-fn _trace(ip: Addr, Instruction { opcode, a, b, c }: Instruction, reg: &Register) {
-    match opcode {
-        AddR => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        AddI => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        MulR => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        MulI => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        BanR => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        BanI => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        BorR => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        BorI => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        SetR => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        SetI => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        GtIR => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        GtRI => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        GtRR => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        EqIR => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        EqRI => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-        EqRR => println!("{:02}: {} {} {} {} : {} ", ip, opcode, a, b, c, reg),
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct Program {
-    ip_reg: Addr,
-    instructions: Vec<Instruction>,
-}
-
-impl Display for Program {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "#ip {}", self.ip_reg)?;
-        for instruction in &self.instructions {
-            writeln!(f, "{}", instruction)?;
-        }
-        Ok(())
-    }
-}
-
-impl Program {
-    pub fn new(ip_reg: Addr, instructions: impl IntoIterator<Item = Instruction>) -> Self {
-        Self {
-            ip_reg,
-            instructions: Vec::from_iter(instructions.into_iter()),
-        }
-    }
-
-    pub fn ip_reg(&self) -> Addr {
-        self.ip_reg
-    }
-
-    pub fn instructions(&self) -> &[Instruction] {
-        &self.instructions
-    }
+#[aoc_generator(day19)]
+pub fn parse(input: &str) -> Result<Program, ParseError> {
+    try_parse(input)
 }
 
-#[aoc_generator(day19)]
-pub fn parse(input: &str) -> Result<Program, String> {
-    let mut ip_reg = 6;
-    let mut instructions = Vec::with_capacity(16);
-    for line in input.lines() {
-        if line.starts_with("#ip") {
-            ip_reg = line[4..]
-                .trim()
-                .parse::<Addr>()
-                .map_err(|e| e.to_string())?;
-        } else {
-            let opc = line[0..4].parse()?;
-            let mut oprs = line[5..]
-                .split(' ')
-                .take(3)
-                .map(|s| s.trim().parse::<Data>().map_err(|e| e.to_string()));
-            let opr1 = oprs.next().unwrap()?;
-            let opr2 = oprs.next().unwrap()?;
-            let opr3 = oprs.next().unwrap()?;
-            instructions.push(Instruction::new(opc, opr1, opr2, opr3));
-        }
-    }
-    Ok(Program::new(ip_reg, instructions))
+pub fn try_parse(input: &str) -> Result<Program, ParseError> {
+    input
+        .parse()
+        .map_err(|e: AssembleError| e.into_parse_error(19))
 }
 
 #[aoc(day19, part1)]
-pub fn run_background_process(program: &Program) -> Data {
-    let mut interpreter = Interpreter::new(program.ip_reg);
+pub fn run_background_process(program: &Program) -> Result<Data, String> {
+    let mut interpreter = Interpreter::with_ip_binding(Mnemonics, program.ip_reg());
     let mut register = Register::default();
-    interpreter
-        .run(program.instructions(), &mut register)
-        .unwrap();
-    register[0]
+    program
+        .optimizations()
+        .run(&mut interpreter, program.instructions(), &mut register)
+        .map_err(|err| format!("error executing program: {}", err))?;
+    Ok(register[0])
 }
 
 #[aoc(day19, part2)]
-pub fn run_background_process_2(program: &Program) -> Data {
-    let mut interpreter = Interpreter::new(program.ip_reg);
+pub fn run_background_process_2(program: &Program) -> Result<Data, String> {
+    let mut interpreter = Interpreter::with_ip_binding(Mnemonics, program.ip_reg());
     let mut register = Register::default();
     register[0] = 1;
-    interpreter
-        .run(program.instructions(), &mut register)
-        .unwrap();
-    register[0]
-}
-
-/// Repeated loop:
-///
-/// ```text
-/// 'L1:  R1 = R5 * R2
-///       if R4 == R1 then
-///         R1 = 1
-///         R0 = R5 + R0
-///       else
-///         R1 = 0
-///       end if
-///       R3 = R1 + R3
-///       R2 = R2 + 1
-///       if R2 > R4 then
-///         R1 = 1
-///         R3 = R3 + 1  // goto 'L2
-///       else
-///         R1 = 0
-///         R3 = R3 + R1
-///         R3 = 2  // goto 'L1
-///       end if
-/// 'L2:
-/// ```
-fn optimized(reg: &mut Register) -> Addr {
-    if reg[4] % reg[5] == 0 {
-        reg[0] = reg[5] + reg[0];
-    }
-    reg[2] = reg[4];
-    reg[1] = 0;
-    12
+    program
+        .optimizations()
+        .run(&mut interpreter, program.instructions(), &mut register)
+        .map_err(|err| format!("error executing program: {}", err))?;
+    Ok(register[0])
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use super::*;
+use crate::elfcode::Mnemonic::*;
 
 const INPUT: &str = include_str!("../../input/2018/day19.txt");
 
@@ -63,7 +64,7 @@ mod interpreter {
     fn example1() {
         let program = parse(EXAMPLE1_INPUT).unwrap();
 
-        let mut interpreter = Interpreter::new(program.ip_reg);
+        let mut interpreter = Interpreter::with_ip_binding(Mnemonics, program.ip_reg());
         let mut register = Register::default();
         interpreter
             .run(program.instructions(), &mut register)
@@ -82,7 +83,7 @@ mod part1 {
 
         let answer = run_background_process(&program);
 
-        assert_eq!(answer, 6);
+        assert_eq!(answer, Ok(6));
     }
 
     #[test]
@@ -91,7 +92,7 @@ mod part1 {
 
         let answer = run_background_process(&program);
 
-        assert_eq!(answer, 1056);
+        assert_eq!(answer, Ok(1056));
     }
 }
 
@@ -104,6 +105,6 @@ mod part2 {
 
         let answer = run_background_process_2(&program);
 
-        assert_eq!(answer, 10_915_260);
+        assert_eq!(answer, Ok(10_915_260));
     }
 }
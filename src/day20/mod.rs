@@ -0,0 +1,504 @@
+//! # Day 20: A Regular Map
+//!
+//! While you were learning about instruction pointers, the Elves made
+//! considerable progress. When you look up, you discover that the North Pole
+//! base construction project has completely surrounded you.
+//!
+//! The area you are in is made up entirely of rooms and doors. The rooms are
+//! arranged in a grid, and rooms only connect to adjacent rooms when a door is
+//! present between them.
+//!
+//! For example, drawing rooms as ., walls as #, doors as | or -, your current
+//! position as X, and where north is up, the area you're in might look like
+//! this:
+//!
+//! ```text
+//! #####
+//! #.|.#
+//! #-###
+//! #.|X#
+//! #####
+//! ```
+//!
+//! You get the attention of a passing construction Elf and ask for a map.
+//! "I don't have time to draw out a map of this place - it's huge. Instead, I
+//! can give you directions to every room in the facility!" She writes down the
+//! directions on a piece of parchment and runs off. In the example above, the
+//! instructions might have been ^WNE$, a regular expression or "regex" (your
+//! puzzle input).
+//!
+//! The regex matches routes (like WNE for "west, north, east") that will take
+//! you from your current room through various doors in the facility. In
+//! aggregate, the routes will take you through every door in the facility at
+//! least once; mapping out all of these routes will let you build a proper map
+//! and find your way around.
+//!
+//! ^ and $ are at the beginning and end of your regex; these just mean that the
+//! regex doesn't match anything outside the routes it describes. (Specifically,
+//! ^ matches the start of the route, and $ matches the end of it.) These
+//! characters will not appear elsewhere in the regex.
+//!
+//! The rest of the regex matches various sequences of the characters N
+//! (north), S (south), E (east), and W (west). In the example above, ^WNE$
+//! matches only one route, WNE, which means you can move west, then north, then
+//! east from your current position. Sequences of letters like this always
+//! match that exact route in the same order.
+//!
+//! Sometimes, the route can branch. A branch is given by a list of options
+//! separated by pipes (|) and wrapped in parentheses. So, ^N(E|W)N$ contains a
+//! branch: after going north, you must choose to go either east or west before
+//! finishing your route by going north again. By tracing out the possible
+//! routes after branching, you can determine where the doors are and,
+//! therefore, where the rooms are in the facility.
+//!
+//! For example, consider this regex: ^ENWWW(NEEE|SSE(EE|N))$
+//!
+//! This regex begins with ENWWW, which means that from your current position,
+//! all routes must begin by moving east, north, and then west three times, in
+//! that order. After this, there is a branch. Before you consider the branch,
+//! this is what you know about the map so far, with doors you aren't sure
+//! about marked with a ?:
+//!
+//! ```text
+//! #?#?#?#?#
+//! ?.|.|.|.?
+//! #?#?#?#-#
+//!     ?X|.?
+//!     #?#?#
+//! ```
+//!
+//! After this point, there is (NEEE|SSE(EE|N)). This gives you exactly two
+//! options: NEEE and SSE(EE|N). By following NEEE, the map now looks like
+//! this:
+//!
+//! ```text
+//! #?#?#?#?#
+//! ?.|.|.|.?
+//! #-#?#?#?#
+//! ?.|.|.|.?
+//! #?#?#?#-#
+//!     ?X|.?
+//!     #?#?#
+//! ```
+//!
+//! Now, only SSE(EE|N) remains. Because it is in the same parenthesized group
+//! as NEEE, it starts from the same room NEEE started in. It states that
+//! starting from that point, there exist doors which will allow you to move
+//! south twice, then east; this ends up at another branch. After that, you can
+//! either move east twice or north once. This information fills in the rest of
+//! the doors:
+//!
+//! ```text
+//! #########
+//! #.|.|.|.#
+//! #-#######
+//! #.|.|.|.#
+//! #-#####-#
+//! #.#.#X|.#
+//! #-#-#####
+//! #.|.|.|.#
+//! #########
+//! ```
+//!
+//! Once you've followed all possible routes, you know the remaining unknown
+//! parts are all walls, producing a finished map of the facility.
+//!
+//! Sometimes, a list of options can have an empty option, like (NEWS|WNSE|).
+//! This means that routes at this point could effectively skip the options in
+//! parentheses and move on immediately. For example, consider this regex and
+//! the corresponding map:
+//!
+//! ```text
+//! ^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$
+//!
+//! ###########
+//! #.|.#.|.#.#
+//! #-###-#-#-#
+//! #.|.|.#.#.#
+//! #-#####-#-#
+//! #.#.#X|.#.#
+//! #-#-#####-#
+//! #.#.|.|.|.#
+//! #-###-###-#
+//! #.|.|.#.|.#
+//! ###########
+//! ```
+//!
+//! This regex has one main route which, at three locations, can optionally
+//! include additional detours and be valid: (NEWS|), (WNSE|), and (SWEN|).
+//! Regardless of which option is taken, the route continues from the position
+//! it is left at after taking those steps. So, for example, this regex matches
+//! all of the following routes (and more that aren't listed here):
+//!
+//! * ENNWSWWSSSEENEENNN
+//! * ENNWSWWNEWSSSSEENEENNN
+//! * ENNWSWWNEWSSSSEENEESWENNNN
+//! * ENNWSWWSSSEENWNSEEENNN
+//!
+//! By following the various routes the regex matches, a full map of all of the
+//! doors and rooms in the facility can be assembled.
+//!
+//! To get a sense for the size of this facility, you'd like to determine which
+//! room is furthest from you: specifically, you would like to find the room for
+//! which the shortest path to that room would require passing through the most
+//! doors.
+//!
+//! * In the first example (^WNE$), this would be the north-east corner 3 doors
+//!   away.
+//! * In the second example (^ENWWW(NEEE|SSE(EE|N))$), this would be the
+//!   south-east corner 10 doors away.
+//! * In the third example (^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$), this
+//!   would be the north-east corner 18 doors away.
+//!
+//! Here are a few more examples:
+//!
+//! ```text
+//! Regex: ^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$
+//! Furthest room requires passing 23 doors
+//!
+//! Regex: ^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$
+//! Furthest room requires passing 31 doors
+//! ```
+//!
+//! What is the largest number of doors you would be required to pass through
+//! to reach a room? That is, find the room for which the shortest path from
+//! your starting location to that room would require passing through the most
+//! doors; what is the fewest doors you can pass through to reach it?
+//!
+//! ## Part 2
+//!
+//! Okay, so the facility is big.
+//!
+//! How many rooms have a shortest path from your current location that pass
+//! through at least 1000 doors?
+//!
+//! [Advent of Code 2018 - Day 20](https://adventofcode.com/2018/day/20)
+
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt::{self, Display},
+    iter::FromIterator,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{self, ParseError},
+    grid::{self, Pos},
+};
+
+use self::Direction::*;
+
+pub type Coord = i32;
+
+pub type Position = Pos<Coord>;
+
+pub type Doors = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+const DIRECTIONS: [Direction; 4] = [North, East, South, West];
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match *self {
+            North => "N",
+            East => "E",
+            South => "S",
+            West => "W",
+        };
+        f.write_str(symbol)
+    }
+}
+
+impl Direction {
+    /// The offset from a room to the adjacent room in this direction.
+    pub fn delta(self) -> Position {
+        match self {
+            North => Position::new(0, -1),
+            East => Position::new(1, 0),
+            South => Position::new(0, 1),
+            West => Position::new(-1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            North => South,
+            East => West,
+            South => North,
+            West => East,
+        }
+    }
+}
+
+/// The map of the facility as derived from the route regex.
+///
+/// Every door is stored for both rooms it connects, so a door between `a`
+/// and `b` can be looked up from either side.
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Facility {
+    doors: HashSet<(Position, Direction)>,
+}
+
+impl Display for Facility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (top_left, bottom_right) = self.area();
+        let width = ((bottom_right.x - top_left.x) * 2 + 3) as usize;
+        let wall_line = "#".repeat(width);
+        writeln!(f, "{}", wall_line)?;
+        let mut line = String::with_capacity(width);
+        for y in top_left.y..=bottom_right.y {
+            line.push('#');
+            for x in top_left.x..=bottom_right.x {
+                let room = Position::new(x, y);
+                line.push(if room == Position::ORIGIN { 'X' } else { '.' });
+                line.push(if self.has_door(room, East) { '|' } else { '#' });
+            }
+            writeln!(f, "{}", line)?;
+            line.clear();
+            line.push('#');
+            for x in top_left.x..=bottom_right.x {
+                let room = Position::new(x, y);
+                line.push(if self.has_door(room, South) { '-' } else { '#' });
+                line.push('#');
+            }
+            writeln!(f, "{}", line)?;
+            line.clear();
+        }
+        Ok(())
+    }
+}
+
+impl Facility {
+    pub fn with_doors(doors: impl IntoIterator<Item = (Position, Direction)>) -> Self {
+        let mut facility = Facility {
+            doors: HashSet::with_capacity(64),
+        };
+        for (room, direction) in doors {
+            facility.add_door(room, direction);
+        }
+        facility
+    }
+
+    pub fn add_door(&mut self, room: Position, direction: Direction) {
+        self.doors.insert((room, direction));
+        self.doors
+            .insert((room + direction.delta(), direction.opposite()));
+    }
+
+    pub fn has_door(&self, room: Position, direction: Direction) -> bool {
+        self.doors.contains(&(room, direction))
+    }
+
+    pub fn num_doors(&self) -> usize {
+        self.doors.len() / 2
+    }
+
+    pub fn rooms(&self) -> HashSet<Position> {
+        let mut rooms = HashSet::from_iter(self.doors.iter().map(|(room, _)| *room));
+        rooms.insert(Position::ORIGIN);
+        rooms
+    }
+
+    pub fn area(&self) -> (Position, Position) {
+        let rooms = self.doors.iter().map(|&(room, _)| room);
+        grid::bounding_box(rooms.chain(Some(Position::ORIGIN)))
+            .expect("there is always the room of the current location")
+    }
+
+    pub fn adjacent_rooms(&self, room: Position) -> impl Iterator<Item = Position> + '_ {
+        DIRECTIONS
+            .iter()
+            .filter(move |&&direction| self.has_door(room, direction))
+            .map(move |&direction| room + direction.delta())
+    }
+
+    /// Calculates the least number of doors to pass through from the current
+    /// location to every room of the facility (breadth first search).
+    pub fn distances(&self) -> HashMap<Position, Doors> {
+        let mut distances = HashMap::with_capacity(self.doors.len() / 2 + 1);
+        let mut queue = VecDeque::with_capacity(64);
+        distances.insert(Position::ORIGIN, 0);
+        queue.push_back(Position::ORIGIN);
+        while let Some(room) = queue.pop_front() {
+            let next_distance = distances[&room] + 1;
+            for adjacent in self.adjacent_rooms(room) {
+                if let Entry::Vacant(entry) = distances.entry(adjacent) {
+                    entry.insert(next_distance);
+                    queue.push_back(adjacent);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Returns the room whose shortest path requires passing the most doors
+    /// together with that number of doors.
+    ///
+    /// If several rooms are equally far away the first one in reading order
+    /// is returned.
+    pub fn furthest_room(&self) -> (Position, Doors) {
+        self.distances()
+            .into_iter()
+            .max_by(|(room1, doors1), (room2, doors2)| {
+                doors1.cmp(doors2).then_with(|| room2.cmp(room1))
+            })
+            .expect("there is always the room of the current location")
+    }
+
+    /// Counts the rooms whose shortest path passes at least the given number
+    /// of doors.
+    pub fn num_rooms_at_least(&self, doors: Doors) -> usize {
+        self.distances()
+            .values()
+            .filter(|&&distance| distance >= doors)
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseRouteError {
+    MissingStart,
+    MissingEnd,
+    UnexpectedCharacter(char, usize),
+    UnmatchedParenthesis(char, usize),
+}
+
+impl Display for ParseRouteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseRouteError::MissingStart => f.write_str("route regex does not start with '^'"),
+            ParseRouteError::MissingEnd => f.write_str("route regex does not end with '$'"),
+            ParseRouteError::UnexpectedCharacter(chr, column) => {
+                write!(f, "unexpected character {:?} at column {}", chr, column)
+            },
+            ParseRouteError::UnmatchedParenthesis(chr, column) => {
+                write!(f, "unmatched {:?} at column {}", chr, column)
+            },
+        }
+    }
+}
+
+impl Error for ParseRouteError {}
+
+/// Builds the facility like `parse`, but reports a malformed regex with the
+/// line and column of the puzzle input.
+pub fn try_parse(input: &str) -> Result<Facility, ParseError> {
+    let expected = "a route regex";
+    let (line_no, line) = error::single_line(20, input, expected)?;
+    let regex = line.trim();
+    let start = error::column(line, regex);
+    parse(input).map_err(|err| match err {
+        ParseRouteError::MissingStart => ParseError::at(20, line_no, line, regex, "'^'"),
+        ParseRouteError::MissingEnd => ParseError::new(
+            20,
+            line_no,
+            start + regex.chars().count(),
+            "'$'",
+            "end of line",
+        ),
+        ParseRouteError::UnexpectedCharacter(chr, column) => ParseError::new(
+            20,
+            line_no,
+            start + column - 1,
+            "a direction, a branch or '$'",
+            format!("{:?}", chr),
+        ),
+        ParseRouteError::UnmatchedParenthesis(chr, column) => ParseError::new(
+            20,
+            line_no,
+            start + column - 1,
+            "a matching parenthesis",
+            format!("{:?}", chr),
+        ),
+    })
+}
+
+/// Builds the facility by following all routes described by the given regex.
+///
+/// The parser keeps track of the set of rooms all routes of the current branch
+/// may have reached, so that options which end in different rooms continue
+/// from each of them.
+#[aoc_generator(day20)]
+pub fn parse(input: &str) -> Result<Facility, ParseRouteError> {
+    let regex = input.trim();
+    if !regex.starts_with('^') {
+        return Err(ParseRouteError::MissingStart);
+    }
+    if !regex.ends_with('$') || regex.len() < 2 {
+        return Err(ParseRouteError::MissingEnd);
+    }
+
+    let mut facility = Facility::default();
+    let mut current: HashSet<Position> = HashSet::from_iter(Some(Position::ORIGIN));
+    let mut groups: Vec<(usize, HashSet<Position>, HashSet<Position>)> = Vec::with_capacity(16);
+
+    for (column, chr) in regex[1..regex.len() - 1].chars().enumerate() {
+        let column = column + 2;
+        match chr {
+            'N' | 'E' | 'S' | 'W' => {
+                let direction = match chr {
+                    'N' => North,
+                    'E' => East,
+                    'S' => South,
+                    _ => West,
+                };
+                current = current
+                    .into_iter()
+                    .map(|room| {
+                        facility.add_door(room, direction);
+                        room + direction.delta()
+                    })
+                    .collect();
+            },
+            '(' => {
+                groups.push((column, current.clone(), HashSet::with_capacity(4)));
+            },
+            '|' => {
+                let (_, starts, ends) = groups
+                    .last_mut()
+                    .ok_or(ParseRouteError::UnexpectedCharacter(chr, column))?;
+                ends.extend(current);
+                current = starts.clone();
+            },
+            ')' => {
+                let (_, _, mut ends) = groups
+                    .pop()
+                    .ok_or(ParseRouteError::UnmatchedParenthesis(chr, column))?;
+                ends.extend(current);
+                current = ends;
+            },
+            _ => return Err(ParseRouteError::UnexpectedCharacter(chr, column)),
+        }
+    }
+
+    if let Some((column, _, _)) = groups.pop() {
+        return Err(ParseRouteError::UnmatchedParenthesis('(', column));
+    }
+    Ok(facility)
+}
+
+#[aoc(day20, part1)]
+pub fn largest_number_of_doors(facility: &Facility) -> Doors {
+    let (_, doors) = facility.furthest_room();
+    doors
+}
+
+#[aoc(day20, part2)]
+pub fn num_rooms_at_least_1000_doors_away(facility: &Facility) -> usize {
+    facility.num_rooms_at_least(1000)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,271 @@
+use super::*;
+
+const INPUT: &str = include_str!("../../input/2018/day20.txt");
+
+const EXAMPLE1_INPUT: &str = "^WNE$";
+
+const EXAMPLE1_MAP: &str = "\
+#####
+#.|.#
+#-###
+#.|X#
+#####
+";
+
+const EXAMPLE2_INPUT: &str = "^ENWWW(NEEE|SSE(EE|N))$";
+
+const EXAMPLE2_MAP: &str = "\
+#########
+#.|.|.|.#
+#-#######
+#.|.|.|.#
+#-#####-#
+#.#.#X|.#
+#-#-#####
+#.|.|.|.#
+#########
+";
+
+const EXAMPLE3_INPUT: &str = "^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$";
+
+const EXAMPLE3_MAP: &str = "\
+###########
+#.|.#.|.#.#
+#-###-#-#-#
+#.|.|.#.#.#
+#-#####-#-#
+#.#.#X|.#.#
+#-#-#####-#
+#.#.|.|.|.#
+#-###-###-#
+#.|.|.#.|.#
+###########
+";
+
+const EXAMPLE4_INPUT: &str = "^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$";
+
+const EXAMPLE4_MAP: &str = "\
+#############
+#.|.|.|.|.|.#
+#-#####-###-#
+#.#.|.#.#.#.#
+#-#-###-#-#-#
+#.#.#.|.#.|.#
+#-#-#-#####-#
+#.#.#.#X|.#.#
+#-#-#-###-#-#
+#.|.#.|.#.#.#
+###-#-###-#-#
+#.|.#.|.|.#.#
+#############
+";
+
+const EXAMPLE5_INPUT: &str = "^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$";
+
+const EXAMPLE5_MAP: &str = "\
+###############
+#.|.|.|.#.|.|.#
+#-###-###-#-#-#
+#.|.#.|.|.#.#.#
+#-#########-#-#
+#.#.|.|.|.|.#.#
+#-#-#########-#
+#.#.#.|X#.|.#.#
+###-#-###-#-#-#
+#.|.#.#.|.#.|.#
+#-###-#####-###
+#.|.#.|.|.#.#.#
+#-#-#####-#-#-#
+#.#.|.|.|.#.|.#
+###############
+";
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let facility = parse(EXAMPLE1_INPUT).unwrap();
+
+        assert_eq!(
+            facility,
+            Facility::with_doors(vec![
+                (Position::new(0, 0), West),
+                (Position::new(-1, 0), North),
+                (Position::new(-1, -1), East),
+            ])
+        );
+    }
+
+    #[test]
+    fn empty_options_continue_from_the_room_before_the_group() {
+        let facility = parse("^N(E|)N$").unwrap();
+
+        assert_eq!(facility.num_doors(), 4);
+        assert!(facility.has_door(Position::new(0, -1), North));
+        assert!(facility.has_door(Position::new(1, -1), North));
+    }
+
+    #[test]
+    fn missing_start() {
+        let result = parse("WNE$");
+
+        assert_eq!(result, Err(ParseRouteError::MissingStart));
+    }
+
+    #[test]
+    fn missing_end() {
+        let result = parse("^WNE");
+
+        assert_eq!(result, Err(ParseRouteError::MissingEnd));
+    }
+
+    #[test]
+    fn unexpected_character() {
+        let result = parse("^WNX$");
+
+        assert_eq!(result, Err(ParseRouteError::UnexpectedCharacter('X', 4)));
+    }
+
+    #[test]
+    fn unclosed_group() {
+        let result = parse("^W(N|E$");
+
+        assert_eq!(result, Err(ParseRouteError::UnmatchedParenthesis('(', 3)));
+    }
+
+    #[test]
+    fn unopened_group() {
+        let result = parse("^WN)E$");
+
+        assert_eq!(result, Err(ParseRouteError::UnmatchedParenthesis(')', 4)));
+    }
+}
+
+mod facility {
+    use super::*;
+
+    #[test]
+    fn display_example1() {
+        let facility = parse(EXAMPLE1_INPUT).unwrap();
+
+        assert_eq!(facility.to_string(), EXAMPLE1_MAP);
+    }
+
+    #[test]
+    fn display_example2() {
+        let facility = parse(EXAMPLE2_INPUT).unwrap();
+
+        assert_eq!(facility.to_string(), EXAMPLE2_MAP);
+    }
+
+    #[test]
+    fn display_example3() {
+        let facility = parse(EXAMPLE3_INPUT).unwrap();
+
+        assert_eq!(facility.to_string(), EXAMPLE3_MAP);
+    }
+
+    #[test]
+    fn display_example4() {
+        let facility = parse(EXAMPLE4_INPUT).unwrap();
+
+        assert_eq!(facility.to_string(), EXAMPLE4_MAP);
+    }
+
+    #[test]
+    fn display_example5() {
+        let facility = parse(EXAMPLE5_INPUT).unwrap();
+
+        assert_eq!(facility.to_string(), EXAMPLE5_MAP);
+    }
+
+    #[test]
+    fn furthest_room_example2() {
+        let facility = parse(EXAMPLE2_INPUT).unwrap();
+
+        let furthest_room = facility.furthest_room();
+
+        assert_eq!(furthest_room, (Position::new(1, 1), 10));
+    }
+
+    #[test]
+    fn num_rooms_at_least_example3() {
+        let facility = parse(EXAMPLE3_INPUT).unwrap();
+
+        assert_eq!(facility.num_rooms_at_least(0), facility.rooms().len());
+        assert_eq!(facility.num_rooms_at_least(18), 1);
+        assert_eq!(facility.num_rooms_at_least(19), 0);
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let facility = parse(EXAMPLE1_INPUT).unwrap();
+
+        let answer = largest_number_of_doors(&facility);
+
+        assert_eq!(answer, 3);
+    }
+
+    #[test]
+    fn example2() {
+        let facility = parse(EXAMPLE2_INPUT).unwrap();
+
+        let answer = largest_number_of_doors(&facility);
+
+        assert_eq!(answer, 10);
+    }
+
+    #[test]
+    fn example3() {
+        let facility = parse(EXAMPLE3_INPUT).unwrap();
+
+        let answer = largest_number_of_doors(&facility);
+
+        assert_eq!(answer, 18);
+    }
+
+    #[test]
+    fn example4() {
+        let facility = parse(EXAMPLE4_INPUT).unwrap();
+
+        let answer = largest_number_of_doors(&facility);
+
+        assert_eq!(answer, 23);
+    }
+
+    #[test]
+    fn example5() {
+        let facility = parse(EXAMPLE5_INPUT).unwrap();
+
+        let answer = largest_number_of_doors(&facility);
+
+        assert_eq!(answer, 31);
+    }
+
+    #[test]
+    fn answer() {
+        let facility = parse(INPUT).unwrap();
+
+        let answer = largest_number_of_doors(&facility);
+
+        assert_eq!(answer, 3699);
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    #[test]
+    fn answer() {
+        let facility = parse(INPUT).unwrap();
+
+        let answer = num_rooms_at_least_1000_doors_away(&facility);
+
+        assert_eq!(answer, 8517);
+    }
+}
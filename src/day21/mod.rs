@@ -0,0 +1,203 @@
+//! # Day 21: Chronal Conversion
+//!
+//! You should have been watching where you were going, because as you wander
+//! the new North Pole base, you trip and fall into a very deep hole!
+//!
+//! Just kidding. You're falling through time again.
+//!
+//! If you keep up your current pace, you should have resolved all of the
+//! temporal anomalies by the next time the device activates. Since you have
+//! very little interest in browsing history in 500-year increments for the
+//! rest of your life, you need to find a way to get back to your present time.
+//!
+//! After a little research, you discover two important facts about the
+//! behavior of the device:
+//!
+//! First, you discover that the device is hard-wired to always send you back
+//! in time in 500-year increments. Changing this is probably not feasible.
+//!
+//! Second, you discover the activation system (your puzzle input) for the time
+//! travel module. Currently, it appears to run forever without halting.
+//!
+//! If you can cause the activation system to halt at a specific moment, maybe
+//! you can make the device send you so far back in time that you cause an
+//! integer underflow in time itself and wrap around back to your current time!
+//!
+//! The device executes the program as specified in manual section one and
+//! manual section two.
+//!
+//! Your goal is to figure out how the program works and cause it to halt. You
+//! can only control register 0; every other register begins at 0 as usual.
+//!
+//! Because time travel is a dangerous activity, the activation system begins
+//! with a few instructions which verify that bitwise AND (via bani) does a
+//! numeric operation and not an operation as if the inputs were interpreted as
+//! strings. If the test fails, it enters an infinite loop re-running the test
+//! instead of allowing the program to execute normally. If the test passes,
+//! the program continues, and assumes that all other bitwise operations
+//! (banr, bori, and borr) also interpret their inputs as numbers. (Clearly,
+//! the Elves who wrote this system were worried that someone might introduce a
+//! bug while trying to emulate this system with a scripting language.)
+//!
+//! What is the lowest non-negative integer value for register 0 that causes
+//! the program to halt after executing the fewest instructions? (Executing the
+//! same instruction multiple times counts as multiple instructions executed.)
+//!
+//! ## Part 2
+//!
+//! In order to determine the timing window for your underflow exploit, you
+//! also need an upper bound:
+//!
+//! What is the lowest non-negative integer value for register 0 that causes
+//! the program to halt after executing the most instructions? (The program
+//! must actually halt; running forever does not count as halting.)
+//!
+//! [Advent of Code 2018 - Day 21](https://adventofcode.com/2018/day/21)
+
+use std::collections::HashSet;
+
+use crate::{
+    day19::{Addr, Data, Instruction, Interpreter, Mnemonic, Program, Register},
+    elfcode::{assembler::AssembleError, optimizer::Optimizations, Mnemonics},
+    error::ParseError,
+};
+
+/// Finds the halting check of the activation system.
+///
+/// The halting check is the only instruction that reads register 0. It is an
+/// `eqrr` comparing register 0 with some other register. Returns the address
+/// of the check and the number of the register compared with register 0.
+pub fn find_halting_check(program: &Program) -> Option<(Addr, Data)> {
+    program
+        .instructions()
+        .iter()
+        .enumerate()
+        .find_map(|(addr, instruction)| match *instruction {
+            Instruction {
+                opcode: Mnemonic::EqRR,
+                a: 0,
+                b,
+                ..
+            } if b != 0 => Some((addr as Addr, b)),
+            Instruction {
+                opcode: Mnemonic::EqRR,
+                a,
+                b: 0,
+                ..
+            } if a != 0 => Some((addr as Addr, a)),
+            _ => None,
+        })
+}
+
+/// Runs the activation system and yields the value that register 0 is
+/// compared with each time the halting check is reached, together with the
+/// number of instructions executed until then.
+///
+/// If register 0 held the yielded value the program would halt right after
+/// the check. Loops recognized by the optimizer are executed natively, but
+/// the number of instructions is still counted as if they had been executed
+/// one by one. The iterator ends when the program halts by itself or fails to
+/// execute an instruction.
+#[derive(Debug)]
+pub struct HaltingValues<'a> {
+    program: &'a Program,
+    optimizations: Optimizations,
+    interpreter: Interpreter,
+    register: Register,
+    check_ip: Addr,
+    compared_reg: Data,
+    executed: u64,
+}
+
+impl<'a> HaltingValues<'a> {
+    pub fn new(program: &'a Program) -> Result<Self, String> {
+        let (check_ip, compared_reg) = find_halting_check(program)
+            .ok_or_else(|| "no halting check comparing register 0 found in program".to_string())?;
+        let mut optimizations = program.optimizations();
+        optimizations
+            .retain(|start, idiom| !(start..start + idiom.len() as Addr).contains(&check_ip));
+        Ok(Self {
+            program,
+            optimizations,
+            interpreter: Interpreter::with_ip_binding(Mnemonics, program.ip_reg()),
+            register: Register::default(),
+            check_ip,
+            compared_reg,
+            executed: 0,
+        })
+    }
+}
+
+impl<'a> Iterator for HaltingValues<'a> {
+    type Item = (Data, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instructions = self.program.instructions();
+        loop {
+            let ip = self.interpreter.ip();
+            match self
+                .optimizations
+                .step(&mut self.interpreter, instructions, &mut self.register)
+            {
+                Ok(Some(executed)) => self.executed += executed,
+                Ok(None) => return None,
+                Err(err) => {
+                    debug!("activation system failed at {}: {}", ip, err);
+                    return None;
+                },
+            }
+            if ip == self.check_ip {
+                let value = self.register.get(self.compared_reg).ok()?;
+                return Some((value, self.executed));
+            }
+        }
+    }
+}
+
+#[aoc_generator(day21)]
+pub fn parse(input: &str) -> Result<Program, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Program, ParseError> {
+    input
+        .parse()
+        .map_err(|e: AssembleError| e.into_parse_error(21))
+}
+
+#[aoc(day21, part1)]
+pub fn value_halting_after_fewest_instructions(program: &Program) -> Result<Data, String> {
+    let mut halting_values = HaltingValues::new(program)?;
+    let (value, executed) = halting_values
+        .next()
+        .ok_or_else(|| "program halts before reaching the halting check".to_string())?;
+    debug!("halting after {} instructions", executed);
+    Ok(value)
+}
+
+/// The values compared in the halting check are generated by a deterministic
+/// sequence, so once a value repeats the sequence cycles. The last value seen
+/// before the first repetition is the one which halts the program after the
+/// most instructions.
+#[aoc(day21, part2)]
+pub fn value_halting_after_most_instructions(program: &Program) -> Result<Data, String> {
+    let mut seen = HashSet::with_capacity(16 * 1024);
+    let mut last = None;
+    for (value, executed) in HaltingValues::new(program)? {
+        if !seen.insert(value) {
+            break;
+        }
+        last = Some((value, executed));
+    }
+    let (value, executed) =
+        last.ok_or_else(|| "program halts before reaching the halting check".to_string())?;
+    debug!(
+        "halting after {} instructions, {} distinct values",
+        executed,
+        seen.len()
+    );
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests;
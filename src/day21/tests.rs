@@ -0,0 +1,115 @@
+use super::*;
+
+use crate::elfcode::Mnemonic::*;
+
+const INPUT: &str = include_str!("../../input/2018/day21.txt");
+
+const EXAMPLE1_INPUT: &str = "\
+#ip 5
+seti 0 0 1
+addi 1 3 1
+bani 1 7 1
+eqrr 1 0 2
+addr 2 5 5
+seti 0 0 5
+";
+
+mod halting_check {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let program = parse(EXAMPLE1_INPUT).unwrap();
+
+        let halting_check = find_halting_check(&program);
+
+        assert_eq!(halting_check, Some((3, 1)));
+    }
+
+    #[test]
+    fn input() {
+        let program = parse(INPUT).unwrap();
+
+        let halting_check = find_halting_check(&program);
+
+        assert_eq!(halting_check, Some((28, 4)));
+    }
+
+    #[test]
+    fn program_without_halting_check() {
+        let program = Program::new(0, vec![Instruction::new(EqRR, 1, 2, 3)]);
+
+        let halting_values = HaltingValues::new(&program);
+
+        assert!(halting_values.is_err());
+    }
+}
+
+mod halting_values {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let program = parse(EXAMPLE1_INPUT).unwrap();
+
+        let halting_values = HaltingValues::new(&program).unwrap().collect::<Vec<_>>();
+
+        assert_eq!(
+            halting_values,
+            vec![
+                (3, 4),
+                (6, 9),
+                (1, 14),
+                (4, 19),
+                (7, 24),
+                (2, 29),
+                (5, 34),
+                (0, 39),
+            ]
+        );
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let program = parse(EXAMPLE1_INPUT).unwrap();
+
+        let answer = value_halting_after_fewest_instructions(&program);
+
+        assert_eq!(answer, Ok(3));
+    }
+
+    #[test]
+    fn answer() {
+        let program = parse(INPUT).unwrap();
+
+        let answer = value_halting_after_fewest_instructions(&program);
+
+        assert_eq!(answer, Ok(15_823_996));
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let program = parse(EXAMPLE1_INPUT).unwrap();
+
+        let answer = value_halting_after_most_instructions(&program);
+
+        assert_eq!(answer, Ok(0));
+    }
+
+    #[test]
+    fn answer() {
+        let program = parse(INPUT).unwrap();
+
+        let answer = value_halting_after_most_instructions(&program);
+
+        assert_eq!(answer, Ok(10_199_686));
+    }
+}
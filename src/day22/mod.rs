@@ -0,0 +1,525 @@
+//! # Day 22: Mode Maze
+//!
+//! This is it, your final stop: the year -483. It's snowing and dark outside;
+//! the only light you can see is coming from a small cottage in the distance.
+//! You make your way there and knock on the door.
+//!
+//! A portly man with a large, white beard and a red coat appears at the door.
+//! "Welcome to the North Pole! How can I help you?" It seems like he's not
+//! expecting visitors from the future.
+//!
+//! After explaining that you need to find a friend of his, he describes the
+//! cave system that his friend is trapped in (your puzzle input).
+//!
+//! The cave is divided into square regions which are either dominantly rocky,
+//! narrow, or wet (called its type). Each region occupies exactly one
+//! coordinate in X,Y format where X and Y are integers and zero or greater.
+//! (Adjacent regions can be the same type.)
+//!
+//! The scan (your puzzle input) is not very detailed: it only reveals the
+//! depth of the cave system and the coordinates of the target. However, it
+//! does not reveal the type of each region. The mouth of the cave is at 0,0.
+//!
+//! The man explains that due to the unusual geology in the area, there is a
+//! method to determine any region's type based on its erosion level. The
+//! erosion level of a region can be determined from its geologic index. The
+//! geologic index can be determined using the first rule that applies from the
+//! list below:
+//!
+//! * The region at 0,0 (the mouth of the cave) has a geologic index of 0.
+//! * The region at the coordinates of the target has a geologic index of 0.
+//! * If the region's Y coordinate is 0, the geologic index is its X coordinate
+//!   times 16807.
+//! * If the region's X coordinate is 0, the geologic index is its Y coordinate
+//!   times 48271.
+//! * Otherwise, the region's geologic index is the result of multiplying the
+//!   erosion levels of the regions at X-1,Y and X,Y-1.
+//!
+//! A region's erosion level is its geologic index plus the cave system's
+//! depth, all modulo 20183. Then:
+//!
+//! * If the erosion level modulo 3 is 0, the region's type is rocky.
+//! * If the erosion level modulo 3 is 1, the region's type is wet.
+//! * If the erosion level modulo 3 is 2, the region's type is narrow.
+//!
+//! For example, suppose the cave system's depth is 510 and the target's
+//! coordinates are 10,10. Using % to represent the modulo operator, the cavern
+//! would look as follows:
+//!
+//! * At 0,0, the geologic index is 0. The erosion level is (0 + 510) % 20183 =
+//!   510. The type is 510 % 3 = 0, rocky.
+//! * At 1,0, because the Y coordinate is 0, the geologic index is 1 * 16807 =
+//!   16807. The erosion level is (16807 + 510) % 20183 = 17317. The type is
+//!   17317 % 3 = 1, wet.
+//! * At 0,1, because the X coordinate is 0, the geologic index is 1 * 48271 =
+//!   48271. The erosion level is (48271 + 510) % 20183 = 8415. The type is 8415
+//!   % 3 = 0, rocky.
+//! * At 1,1, neither coordinate is 0 and it is not the coordinate of the
+//!   target, so the geologic index is the erosion level of 0,1 (8415) times the
+//!   erosion level of 1,0 (17317), 8415 * 17317 = 145722555. The erosion level
+//!   is (145722555 + 510) % 20183 = 1805. The type is 1805 % 3 = 2, narrow.
+//! * At 10,10, because they are the target's coordinates, the geologic index is
+//!   0. The erosion level is (0 + 510) % 20183 = 510. The type is 510 % 3 = 0,
+//!   rocky.
+//!
+//! Drawing this same cave system with rocky as ., wet as =, narrow as |, the
+//! mouth as M, the target as T, with 0,0 in the top-left corner, X increasing
+//! to the right, and Y increasing downward, the top-left corner of the map
+//! looks like this:
+//!
+//! ```text
+//! M=.|=.|.|=.|=|=.
+//! .|=|=|||..|.=...
+//! .==|....||=..|==
+//! =.|....|.==.|==.
+//! =|..==...=.|==..
+//! =||.=.=||=|=..|=
+//! |.=.===|||..=..|
+//! |..==||=.|==|===
+//! .=..===..=|.|||.
+//! .======|||=|=.|=
+//! .===|=|===T===||
+//! =|||...|==..|=.|
+//! =.=|=.=..=.||==|
+//! ||=|=...|==.=|==
+//! |=.=||===.|||===
+//! ||.|==.|.|.||=||
+//! ```
+//!
+//! Before you go in, you should determine the risk level of the area. For the
+//! rectangle that has a top-left corner of region 0,0 and a bottom-right
+//! corner of the region containing the target, add up the risk level of each
+//! individual region: 0 for rocky regions, 1 for wet regions, and 2 for narrow
+//! regions.
+//!
+//! In the cave system above, because the mouth is at 0,0 and the target is at
+//! 10,10, adding up the risk level of all regions with an X coordinate from 0
+//! to 10 and a Y coordinate from 0 to 10, this total is 114.
+//!
+//! What is the total risk level for the smallest rectangle that includes 0,0
+//! and the target's coordinates?
+//!
+//! ## Part 2
+//!
+//! Okay, it's time to go rescue the man's friend.
+//!
+//! As you leave, he hands you some tools: a torch and some climbing gear. You
+//! can't equip both tools at once, but you can choose to use neither.
+//!
+//! Tools can only be used in certain regions:
+//!
+//! * In rocky regions, you can use the climbing gear or the torch. You cannot
+//!   use neither (you'll likely slip and fall).
+//! * In wet regions, you can use the climbing gear or neither tool. You cannot
+//!   use the torch (if it gets wet, you won't have a light source).
+//! * In narrow regions, you can use the torch or neither tool. You cannot use
+//!   the climbing gear (it's too bulky to fit).
+//!
+//! You start at 0,0 (the mouth of the cave) with the torch equipped and must
+//! reach the target coordinates as quickly as possible. The regions with
+//! negative X or Y are solid rock and cannot be traversed. The fastest route
+//! might involve entering regions beyond the X or Y coordinate of the target.
+//!
+//! You can move to an adjacent region (up, down, left, or right; never
+//! diagonally) if your currently equipped tool allows you to enter that
+//! region. Moving to an adjacent region takes one minute. (For example, if you
+//! have the torch equipped, you can move between rocky and narrow regions, but
+//! cannot enter wet regions.)
+//!
+//! You can change your currently equipped tool or put both away if your new
+//! equipment would be valid for your current region. Switching to using the
+//! climbing gear, torch, or neither always takes seven minutes, regardless of
+//! which tools you start with. (For example, if you are in a rocky region, you
+//! can switch from the torch to the climbing gear, but you cannot switch to
+//! neither.)
+//!
+//! Finally, once you reach the target, you need the torch equipped before you
+//! can find him in the dark. The target is always in a rocky region, so if you
+//! arrive there with climbing gear equipped, you will need to spend seven
+//! minutes switching to your torch.
+//!
+//! In the example above, the fastest route takes 45 minutes.
+//!
+//! What is the fewest number of minutes you can take to reach the target?
+//!
+//! [Advent of Code 2018 - Day 22](https://adventofcode.com/2018/day/22)
+
+use std::{
+    cell::RefCell,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fmt::{self, Display},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{self, ParseError},
+    grid::Pos,
+};
+
+use self::RegionType::*;
+use self::Tool::*;
+
+pub type Coord = usize;
+
+pub type Position = Pos<Coord>;
+
+/// The position of the mouth of the cave.
+pub const MOUTH: Position = Position::ORIGIN;
+
+pub type Depth = u64;
+pub type GeologicIndex = u64;
+pub type ErosionLevel = u64;
+pub type RiskLevel = u64;
+pub type Minutes = u32;
+
+const EROSION_MODULO: u64 = 20183;
+const MOVE_MINUTES: Minutes = 1;
+const SWITCH_TOOL_MINUTES: Minutes = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RegionType {
+    Rocky,
+    Wet,
+    Narrow,
+}
+
+impl Display for RegionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match *self {
+            Rocky => ".",
+            Wet => "=",
+            Narrow => "|",
+        };
+        f.write_str(symbol)
+    }
+}
+
+impl RegionType {
+    pub fn risk_level(self) -> RiskLevel {
+        match self {
+            Rocky => 0,
+            Wet => 1,
+            Narrow => 2,
+        }
+    }
+
+    pub fn allows(self, tool: Tool) -> bool {
+        match self {
+            Rocky => tool != Neither,
+            Wet => tool != Torch,
+            Narrow => tool != ClimbingGear,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Tool {
+    Torch,
+    ClimbingGear,
+    Neither,
+}
+
+const TOOLS: [Tool; 3] = [Torch, ClimbingGear, Neither];
+
+impl Display for Tool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Torch => "torch",
+            ClimbingGear => "climbing gear",
+            Neither => "neither",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The fastest route to the target as found by `CaveSystem::fastest_route`.
+///
+/// The steps start at the mouth of the cave with the torch equipped and end
+/// at the target with the torch equipped. Two consecutive steps either move
+/// to an adjacent region or switch the tool at the same region.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Route {
+    steps: Vec<(Position, Tool)>,
+    minutes: Minutes,
+}
+
+impl Route {
+    pub fn steps(&self) -> &[(Position, Tool)] {
+        &self.steps
+    }
+
+    pub fn minutes(&self) -> Minutes {
+        self.minutes
+    }
+
+    pub fn num_tool_switches(&self) -> usize {
+        self.steps
+            .windows(2)
+            .filter(|step| step[0].0 == step[1].0)
+            .count()
+    }
+}
+
+/// The cave system as described by the scan.
+///
+/// Erosion levels are calculated on demand and cached, so that the regions
+/// around the target can be explored without knowing the extent of the cave
+/// in advance.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CaveSystem {
+    depth: Depth,
+    target: Position,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    erosion_levels: RefCell<HashMap<Position, ErosionLevel>>,
+}
+
+impl PartialEq for CaveSystem {
+    fn eq(&self, other: &Self) -> bool {
+        self.depth == other.depth && self.target == other.target
+    }
+}
+
+impl Display for CaveSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut line = String::with_capacity(self.target.x + 2);
+        for y in 0..=self.target.y {
+            for x in 0..=self.target.x {
+                let position = Position::new(x, y);
+                let symbol = if position == MOUTH {
+                    'M'
+                } else if position == self.target {
+                    'T'
+                } else {
+                    match self.region_type(position) {
+                        Rocky => '.',
+                        Wet => '=',
+                        Narrow => '|',
+                    }
+                };
+                line.push(symbol);
+            }
+            line.push('\n');
+            f.write_str(&line)?;
+            line.clear();
+        }
+        Ok(())
+    }
+}
+
+impl CaveSystem {
+    pub fn new(depth: Depth, target: Position) -> Self {
+        Self {
+            depth,
+            target,
+            erosion_levels: RefCell::new(HashMap::with_capacity(1024)),
+        }
+    }
+
+    pub fn depth(&self) -> Depth {
+        self.depth
+    }
+
+    pub fn target(&self) -> Position {
+        self.target
+    }
+
+    pub fn geologic_index(&self, position: Position) -> GeologicIndex {
+        match position {
+            Position { x: 0, y: 0 } => 0,
+            _ if position == self.target => 0,
+            Position { x, y: 0 } => x as GeologicIndex * 16807,
+            Position { x: 0, y } => y as GeologicIndex * 48271,
+            Position { x, y } => {
+                self.erosion_level(Position::new(x - 1, y))
+                    * self.erosion_level(Position::new(x, y - 1))
+            },
+        }
+    }
+
+    pub fn erosion_level(&self, position: Position) -> ErosionLevel {
+        if let Some(&erosion_level) = self.erosion_levels.borrow().get(&position) {
+            return erosion_level;
+        }
+        // calculate missing regions of the rectangle towards the mouth first,
+        // so that the geologic index does not recurse deeply
+        let mut pending = vec![position];
+        while let Some(&current) = pending.last() {
+            let missing: Vec<Position> = if current.x > 0 && current.y > 0 {
+                let levels = self.erosion_levels.borrow();
+                [current.offset((-1, 0)), current.offset((0, -1))]
+                    .iter()
+                    .flatten()
+                    .filter(|pos| !levels.contains_key(*pos))
+                    .cloned()
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            if missing.is_empty() {
+                pending.pop();
+                let erosion_level = (self.geologic_index(current) + self.depth) % EROSION_MODULO;
+                self.erosion_levels
+                    .borrow_mut()
+                    .insert(current, erosion_level);
+            } else {
+                pending.extend(missing);
+            }
+        }
+        self.erosion_levels.borrow()[&position]
+    }
+
+    pub fn region_type(&self, position: Position) -> RegionType {
+        match self.erosion_level(position) % 3 {
+            0 => Rocky,
+            1 => Wet,
+            _ => Narrow,
+        }
+    }
+
+    /// Sums up the risk level of all regions in the rectangle from the mouth
+    /// of the cave to the target.
+    pub fn risk_level(&self) -> RiskLevel {
+        let mut risk_level = 0;
+        for y in 0..=self.target.y {
+            for x in 0..=self.target.x {
+                risk_level += self.region_type(Position::new(x, y)).risk_level();
+            }
+        }
+        risk_level
+    }
+
+    /// The minutes it takes at least to move from the given position to the
+    /// target.
+    fn distance_to_target(&self, position: Position) -> Minutes {
+        self.target.manhattan_distance(position) as Minutes * MOVE_MINUTES
+    }
+
+    /// Searches the fastest route from the mouth of the cave to the target
+    /// using an A* search over all combinations of region and equipped tool.
+    ///
+    /// The queue is ordered by the elapsed minutes plus the manhattan distance
+    /// to the target. As every move takes at least one minute this never
+    /// overestimates the remaining time, so the first route reaching the
+    /// target is the fastest one, while far less regions are explored than
+    /// by Dijkstra's algorithm.
+    pub fn fastest_route(&self) -> Option<Route> {
+        let start = (MOUTH, Torch);
+        let goal = (self.target, Torch);
+        let mut minutes: HashMap<(Position, Tool), Minutes> = HashMap::with_capacity(1024);
+        let mut previous: HashMap<(Position, Tool), (Position, Tool)> =
+            HashMap::with_capacity(1024);
+        let mut queue = BinaryHeap::with_capacity(1024);
+        minutes.insert(start, 0);
+        queue.push(Reverse((
+            self.distance_to_target(start.0),
+            0,
+            start.0,
+            start.1,
+        )));
+
+        while let Some(Reverse((_, elapsed, position, tool))) = queue.pop() {
+            let current = (position, tool);
+            if current == goal {
+                let mut steps = vec![goal];
+                let mut step = goal;
+                while let Some(&prev) = previous.get(&step) {
+                    steps.push(prev);
+                    step = prev;
+                }
+                steps.reverse();
+                return Some(Route {
+                    steps,
+                    minutes: elapsed,
+                });
+            }
+            if minutes.get(&current).is_some_and(|&best| elapsed > best) {
+                continue;
+            }
+            let region_type = self.region_type(position);
+            let moves = position
+                .neighbours4()
+                .filter(|&adjacent| self.region_type(adjacent).allows(tool))
+                .map(|adjacent| ((adjacent, tool), elapsed + MOVE_MINUTES));
+            let switches = TOOLS
+                .iter()
+                .filter(|&&other| other != tool && region_type.allows(other))
+                .map(|&other| ((position, other), elapsed + SWITCH_TOOL_MINUTES));
+            let next_steps: Vec<_> = moves.chain(switches).collect();
+            for (next, next_elapsed) in next_steps {
+                if minutes.get(&next).is_none_or(|&best| next_elapsed < best) {
+                    minutes.insert(next, next_elapsed);
+                    previous.insert(next, current);
+                    let estimate = next_elapsed + self.distance_to_target(next.0);
+                    queue.push(Reverse((estimate, next_elapsed, next.0, next.1)));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[aoc_generator(day22)]
+pub fn parse(input: &str) -> Result<CaveSystem, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<CaveSystem, ParseError> {
+    let mut depth = None;
+    let mut target = None;
+    for (idx, line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if let Some(value) = text.strip_prefix("depth:") {
+            depth = Some(error::number(22, line_no, line, value.trim(), "a depth")?);
+        } else if let Some(value) = text.strip_prefix("target:") {
+            let mut coords = value.split(',').map(str::trim);
+            let x = coords.next().unwrap_or(value);
+            let y = coords
+                .next()
+                .ok_or_else(|| ParseError::end_of_line(22, line_no, line, "\",\""))?;
+            let x = error::number(22, line_no, line, x, "an x-coordinate")?;
+            let y = error::number(22, line_no, line, y, "a y-coordinate")?;
+            target = Some(Position::new(x, y));
+        } else {
+            return Err(ParseError::at(
+                22,
+                line_no,
+                line,
+                text,
+                "\"depth:\" or \"target:\"",
+            ));
+        }
+    }
+    let depth = depth.ok_or_else(|| ParseError::end_of_input(22, input, "\"depth:\""))?;
+    let target = target.ok_or_else(|| ParseError::end_of_input(22, input, "\"target:\""))?;
+    Ok(CaveSystem::new(depth, target))
+}
+
+#[aoc(day22, part1)]
+pub fn total_risk_level(cave: &CaveSystem) -> RiskLevel {
+    cave.risk_level()
+}
+
+#[aoc(day22, part2)]
+pub fn fewest_minutes_to_reach_target(cave: &CaveSystem) -> Option<Minutes> {
+    let route = cave.fastest_route()?;
+    debug!(
+        "reached target in {} minutes with {} tool switches",
+        route.minutes(),
+        route.num_tool_switches()
+    );
+    Some(route.minutes())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,156 @@
+use super::*;
+
+const INPUT: &str = include_str!("../../input/2018/day22.txt");
+
+const EXAMPLE1_INPUT: &str = "\
+depth: 510
+target: 10,10
+";
+
+const EXAMPLE1_MAP: &str = "\
+M=.|=.|.|=.
+.|=|=|||..|
+.==|....||=
+=.|....|.==
+=|..==...=.
+=||.=.=||=|
+|.=.===|||.
+|..==||=.|=
+.=..===..=|
+.======|||=
+.===|=|===T
+";
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let cave = parse(EXAMPLE1_INPUT).unwrap();
+
+        assert_eq!(cave, CaveSystem::new(510, Position::new(10, 10)));
+    }
+
+    #[test]
+    fn missing_target() {
+        let result = parse("depth: 510\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(22, 2, 1, "\"target:\"", "end of input"))
+        );
+    }
+
+    #[test]
+    fn invalid_depth() {
+        let result = parse("depth: deep\ntarget: 10,10\n");
+
+        assert!(result.is_err());
+    }
+}
+
+mod cave_system {
+    use super::*;
+
+    #[test]
+    fn example1_regions() {
+        let cave = parse(EXAMPLE1_INPUT).unwrap();
+
+        assert_eq!(cave.geologic_index(Position::new(0, 0)), 0);
+        assert_eq!(cave.erosion_level(Position::new(0, 0)), 510);
+        assert_eq!(cave.region_type(Position::new(0, 0)), Rocky);
+
+        assert_eq!(cave.geologic_index(Position::new(1, 0)), 16807);
+        assert_eq!(cave.erosion_level(Position::new(1, 0)), 17317);
+        assert_eq!(cave.region_type(Position::new(1, 0)), Wet);
+
+        assert_eq!(cave.geologic_index(Position::new(0, 1)), 48271);
+        assert_eq!(cave.erosion_level(Position::new(0, 1)), 8415);
+        assert_eq!(cave.region_type(Position::new(0, 1)), Rocky);
+
+        assert_eq!(cave.geologic_index(Position::new(1, 1)), 145_722_555);
+        assert_eq!(cave.erosion_level(Position::new(1, 1)), 1805);
+        assert_eq!(cave.region_type(Position::new(1, 1)), Narrow);
+
+        assert_eq!(cave.geologic_index(Position::new(10, 10)), 0);
+        assert_eq!(cave.erosion_level(Position::new(10, 10)), 510);
+        assert_eq!(cave.region_type(Position::new(10, 10)), Rocky);
+    }
+
+    #[test]
+    fn erosion_level_far_away_from_the_mouth() {
+        let cave = CaveSystem::new(510, Position::new(10, 10));
+
+        let erosion_level = cave.erosion_level(Position::new(500, 500));
+
+        assert!(erosion_level < EROSION_MODULO);
+    }
+
+    #[test]
+    fn display_example1() {
+        let cave = parse(EXAMPLE1_INPUT).unwrap();
+
+        assert_eq!(cave.to_string(), EXAMPLE1_MAP);
+    }
+
+    #[test]
+    fn fastest_route_example1() {
+        let cave = parse(EXAMPLE1_INPUT).unwrap();
+
+        let route = cave.fastest_route().unwrap();
+
+        assert_eq!(route.minutes(), 45);
+        assert_eq!(route.steps().first(), Some(&(MOUTH, Torch)));
+        assert_eq!(route.steps().last(), Some(&(Position::new(10, 10), Torch)));
+        let num_switches = route.num_tool_switches();
+        let num_moves = route.steps().len() - 1 - num_switches;
+        assert_eq!(
+            route.minutes(),
+            num_moves as Minutes * MOVE_MINUTES + num_switches as Minutes * SWITCH_TOOL_MINUTES
+        );
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let cave = parse(EXAMPLE1_INPUT).unwrap();
+
+        let answer = total_risk_level(&cave);
+
+        assert_eq!(answer, 114);
+    }
+
+    #[test]
+    fn answer() {
+        let cave = parse(INPUT).unwrap();
+
+        let answer = total_risk_level(&cave);
+
+        assert_eq!(answer, 9940);
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let cave = parse(EXAMPLE1_INPUT).unwrap();
+
+        let answer = fewest_minutes_to_reach_target(&cave);
+
+        assert_eq!(answer, Some(45));
+    }
+
+    #[test]
+    fn answer() {
+        let cave = parse(INPUT).unwrap();
+
+        let answer = fewest_minutes_to_reach_target(&cave);
+
+        assert_eq!(answer, Some(944));
+    }
+}
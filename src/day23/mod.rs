@@ -0,0 +1,450 @@
+//! # Day 23: Experimental Emergency Teleportation
+//!
+//! Using your torch to search the darkness of the rocky cavern, you finally
+//! locate the man's friend: a small reindeer.
+//!
+//! You're not sure how it got so far in this cave. It looks sick - too sick to
+//! walk - and too heavy for you to carry all the way back. Sleighs won't be
+//! invented for another 1500 years, of course.
+//!
+//! The only option is experimental emergency teleportation.
+//!
+//! You hit the "experimental emergency teleportation" button on the device and
+//! push I accept the risk on no fewer than 18 different warning messages.
+//! Immediately, the device deploys hundreds of tiny nanobots which fly around
+//! the cavern, apparently assembling themselves into a very specific formation.
+//! The device lists the X,Y,Z position (pos) for each nanobot as well as its
+//! signal radius (r) on its tiny screen (your puzzle input).
+//!
+//! Each nanobot can transmit signals to any integer coordinate which is a
+//! distance away from it less than or equal to its signal radius (as measured
+//! by Manhattan distance). Coordinates a distance away of less than or equal
+//! to a nanobot's signal radius are said to be in range of that nanobot.
+//!
+//! Before you start the teleportation process, you should determine which
+//! nanobot is the strongest (that is, which has the largest signal radius) and
+//! then, for that nanobot, the total number of nanobots that are in range of
+//! it, including itself.
+//!
+//! For example, given the following nanobots:
+//!
+//! ```text
+//! pos=<0,0,0>, r=4
+//! pos=<1,0,0>, r=1
+//! pos=<4,0,0>, r=3
+//! pos=<0,2,0>, r=1
+//! pos=<0,5,0>, r=3
+//! pos=<0,0,3>, r=1
+//! pos=<1,1,1>, r=1
+//! pos=<1,1,2>, r=1
+//! pos=<1,3,1>, r=1
+//! ```
+//!
+//! The strongest nanobot is the first one (position 0,0,0) because its signal
+//! radius, 4 is the largest. Using that nanobot's location and signal radius,
+//! the following nanobots are in or out of range:
+//!
+//! * The nanobot at 0,0,0 is distance 0 away, and so it is in range.
+//! * The nanobot at 1,0,0 is distance 1 away, and so it is in range.
+//! * The nanobot at 4,0,0 is distance 4 away, and so it is in range.
+//! * The nanobot at 0,2,0 is distance 2 away, and so it is in range.
+//! * The nanobot at 0,5,0 is distance 5 away, and so it is not in range.
+//! * The nanobot at 0,0,3 is distance 3 away, and so it is in range.
+//! * The nanobot at 1,1,1 is distance 3 away, and so it is in range.
+//! * The nanobot at 1,1,2 is distance 4 away, and so it is in range.
+//! * The nanobot at 1,3,1 is distance 5 away, and so it is not in range.
+//!
+//! In this example, in total, 7 nanobots are in range of the nanobot with the
+//! largest signal radius.
+//!
+//! Find the nanobot with the largest signal radius. How many nanobots are in
+//! range of its signals?
+//!
+//! ## Part 2
+//!
+//! Now, you just need to figure out where to position yourself so that you're
+//! actually teleported when the nanobots activate.
+//!
+//! To increase the probability of success, you need to find the coordinate
+//! which puts you in range of the largest number of nanobots. If there are
+//! multiple, choose one closest to your position (0,0,0, measured by manhattan
+//! distance).
+//!
+//! For example, given the following nanobot formation:
+//!
+//! ```text
+//! pos=<10,12,12>, r=2
+//! pos=<12,14,12>, r=2
+//! pos=<16,12,12>, r=4
+//! pos=<14,14,14>, r=6
+//! pos=<50,50,50>, r=200
+//! pos=<10,10,10>, r=5
+//! ```
+//!
+//! Many coordinates are in range of some of the nanobots in this formation.
+//! However, only the coordinate 12,12,12 is in range of the most nanobots: it
+//! is in range of the first five, but is not in range of the nanobot at
+//! 10,10,10. (All other coordinates are in range of fewer than five nanobots.)
+//! This coordinate's distance from 0,0,0 is 36.
+//!
+//! Find the coordinates that are in range of the largest number of nanobots.
+//! What is the shortest manhattan distance between any of those points and
+//! 0,0,0?
+//!
+//! [Advent of Code 2018 - Day 23](https://adventofcode.com/2018/day/23)
+
+use std::{
+    cmp::{Ord, Ordering, PartialOrd, Reverse},
+    collections::BinaryHeap,
+    convert::TryFrom,
+    error::Error,
+    fmt::{self, Display},
+    num::ParseIntError,
+    ops::Add,
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, ParseError};
+
+pub type Coord = i64;
+pub type Distance = u64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Position {
+    pub x: Coord,
+    pub y: Coord,
+    pub z: Coord,
+}
+
+impl Position {
+    pub const ORIGIN: Position = Position { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: Coord, y: Coord, z: Coord) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan_distance(self, other: Position) -> Distance {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl Ord for Position {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.z
+            .cmp(&other.z)
+            .then_with(|| self.y.cmp(&other.y))
+            .then_with(|| self.x.cmp(&other.x))
+    }
+}
+
+impl PartialOrd for Position {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Position {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Position::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Nanobot {
+    pub position: Position,
+    pub radius: Distance,
+}
+
+impl Display for Nanobot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "pos=<{},{},{}>, r={}",
+            self.position.x, self.position.y, self.position.z, self.radius
+        )
+    }
+}
+
+impl Nanobot {
+    pub fn new(position: Position, radius: Distance) -> Self {
+        Self { position, radius }
+    }
+
+    pub fn in_range(&self, position: Position) -> bool {
+        self.position.manhattan_distance(position) <= self.radius
+    }
+
+    /// Whether any coordinate of the given cube is in range of this nanobot.
+    pub fn reaches(&self, cube: &Cube) -> bool {
+        cube.distance_to(self.position) <= self.radius
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseNanobotError {
+    MissingPosition(String),
+    MissingRadius(String),
+    WrongNumberOfCoordinates(usize),
+    NotAnInteger(String, ParseIntError),
+}
+
+impl Display for ParseNanobotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseNanobotError::MissingPosition(s) => write!(f, "missing position in {:?}", s),
+            ParseNanobotError::MissingRadius(s) => write!(f, "missing signal radius in {:?}", s),
+            ParseNanobotError::WrongNumberOfCoordinates(n) => {
+                write!(f, "expected 3 coordinates but got {}", n)
+            },
+            ParseNanobotError::NotAnInteger(s, err) => {
+                write!(f, "{:?} is not an integer: {}", s, err)
+            },
+        }
+    }
+}
+
+impl Error for ParseNanobotError {}
+
+impl FromStr for Nanobot {
+    type Err = ParseNanobotError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_nanobot(s).map_err(|(err, _)| err)
+    }
+}
+
+/// Parses a nanobot and returns the error together with the part of the text
+/// that caused it.
+fn parse_nanobot(s: &str) -> Result<Nanobot, (ParseNanobotError, &str)> {
+    let line = s.trim();
+    let coords = line
+        .strip_prefix("pos=<")
+        .and_then(|rest| rest.split('>').next())
+        .ok_or_else(|| (ParseNanobotError::MissingPosition(line.to_string()), line))?;
+    let radius = line
+        .split("r=")
+        .nth(1)
+        .ok_or_else(|| (ParseNanobotError::MissingRadius(line.to_string()), line))?;
+    let values = coords
+        .split(',')
+        .map(parse_integer)
+        .collect::<Result<Vec<Coord>, _>>()?;
+    if values.len() != 3 {
+        return Err((
+            ParseNanobotError::WrongNumberOfCoordinates(values.len()),
+            coords,
+        ));
+    }
+    let radius = parse_integer(radius)?;
+    Ok(Nanobot::new(
+        Position::new(values[0], values[1], values[2]),
+        radius,
+    ))
+}
+
+fn parse_integer<T>(s: &str) -> Result<T, (ParseNanobotError, &str)>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    let value = s.trim();
+    value.parse().map_err(|err| {
+        (
+            ParseNanobotError::NotAnInteger(value.to_string(), err),
+            value,
+        )
+    })
+}
+
+pub fn try_parse(input: &str) -> Result<Vec<Nanobot>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            let line_no = idx + 1;
+            parse_nanobot(line).map_err(|(cause, part)| match cause {
+                ParseNanobotError::MissingPosition(_) => {
+                    ParseError::at(23, line_no, line, part, "\"pos=<\"")
+                },
+                ParseNanobotError::MissingRadius(_) => {
+                    ParseError::end_of_line(23, line_no, line, "\"r=\"")
+                },
+                ParseNanobotError::WrongNumberOfCoordinates(num) => ParseError::new(
+                    23,
+                    line_no,
+                    error::column(line, part),
+                    "3 coordinates",
+                    num.to_string(),
+                ),
+                ParseNanobotError::NotAnInteger(..) => {
+                    ParseError::at(23, line_no, line, part, "an integer")
+                },
+            })
+        })
+        .collect()
+}
+
+#[aoc_generator(day23)]
+pub fn parse(input: &str) -> Result<Vec<Nanobot>, ParseError> {
+    try_parse(input)
+}
+
+/// An axis aligned cube of coordinates used to subdivide the search space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cube {
+    min: Position,
+    size: Coord,
+}
+
+impl Cube {
+    pub fn new(min: Position, size: Coord) -> Self {
+        Self { min, size }
+    }
+
+    /// Smallest cube with a power of 2 as size, which contains the origin and
+    /// all coordinates in range of the given nanobots.
+    pub fn enclosing(nanobots: &[Nanobot]) -> Self {
+        let mut min = Position::ORIGIN;
+        let mut max = Position::ORIGIN;
+        for bot in nanobots {
+            let radius = Coord::try_from(bot.radius).unwrap_or(Coord::MAX);
+            let Position { x, y, z } = bot.position;
+            min.x = min.x.min(x.saturating_sub(radius));
+            min.y = min.y.min(y.saturating_sub(radius));
+            min.z = min.z.min(z.saturating_sub(radius));
+            max.x = max.x.max(x.saturating_add(radius));
+            max.y = max.y.max(y.saturating_add(radius));
+            max.z = max.z.max(z.saturating_add(radius));
+        }
+        let extent = (max.x.saturating_sub(min.x))
+            .max(max.y.saturating_sub(min.y))
+            .max(max.z.saturating_sub(min.z))
+            .saturating_add(1);
+        let mut size: Coord = 1;
+        while size < extent {
+            size = size.saturating_mul(2);
+        }
+        Cube::new(min, size)
+    }
+
+    pub fn min_corner(&self) -> Position {
+        self.min
+    }
+
+    pub fn max_corner(&self) -> Position {
+        let offset = self.size - 1;
+        self.min + Position::new(offset, offset, offset)
+    }
+
+    pub fn size(&self) -> Coord {
+        self.size
+    }
+
+    /// Manhattan distance from the given position to the nearest coordinate
+    /// within this cube.
+    pub fn distance_to(&self, position: Position) -> Distance {
+        let max = self.max_corner();
+        axis_distance(position.x, self.min.x, max.x)
+            + axis_distance(position.y, self.min.y, max.y)
+            + axis_distance(position.z, self.min.z, max.z)
+    }
+
+    /// Splits this cube into its 8 octants. Must not be called on a cube of
+    /// size 1.
+    pub fn octants(&self) -> impl Iterator<Item = Cube> {
+        let half = self.size / 2;
+        let min = self.min;
+        (0..8).map(move |n| {
+            let offset = Position::new(
+                if n & 1 == 0 { 0 } else { half },
+                if n & 2 == 0 { 0 } else { half },
+                if n & 4 == 0 { 0 } else { half },
+            );
+            Cube::new(min + offset, half)
+        })
+    }
+}
+
+fn axis_distance(value: Coord, min: Coord, max: Coord) -> Distance {
+    if value < min {
+        min.abs_diff(value)
+    } else if value > max {
+        value.abs_diff(max)
+    } else {
+        0
+    }
+}
+
+pub fn strongest_nanobot(nanobots: &[Nanobot]) -> Option<&Nanobot> {
+    nanobots.iter().max_by_key(|bot| bot.radius)
+}
+
+/// Finds the coordinate that is in range of the most nanobots. If there are
+/// several such coordinates the one nearest to the origin is returned.
+///
+/// The search space is subdivided into octants, where each cube is
+/// prioritized by the number of nanobots reaching any of its coordinates,
+/// then by its distance to the origin and last by its size. The number of
+/// nanobots reaching a cube is an upper bound for each of its coordinates,
+/// so the first cube of size 1 taken from the queue is the best coordinate.
+///
+/// Returns the coordinate and the number of nanobots it is in range of.
+pub fn best_coverage(nanobots: &[Nanobot]) -> (Position, usize) {
+    let count_reaching = |cube: &Cube| nanobots.iter().filter(|bot| bot.reaches(cube)).count();
+    let mut queue = BinaryHeap::with_capacity(1024);
+    let root = Cube::enclosing(nanobots);
+    queue.push((
+        count_reaching(&root),
+        Reverse(root.distance_to(Position::ORIGIN)),
+        Reverse(root.size()),
+        Reverse(root),
+    ));
+    while let Some((count, _, _, Reverse(cube))) = queue.pop() {
+        if cube.size() == 1 {
+            return (cube.min_corner(), count);
+        }
+        for octant in cube.octants() {
+            queue.push((
+                count_reaching(&octant),
+                Reverse(octant.distance_to(Position::ORIGIN)),
+                Reverse(octant.size()),
+                Reverse(octant),
+            ));
+        }
+    }
+    unreachable!("the search space is never exhausted before a single coordinate is found")
+}
+
+#[aoc(day23, part1)]
+pub fn num_nanobots_in_range_of_strongest(nanobots: &[Nanobot]) -> Option<usize> {
+    let strongest = strongest_nanobot(nanobots)?;
+    let count = nanobots
+        .iter()
+        .filter(|bot| strongest.in_range(bot.position))
+        .count();
+    Some(count)
+}
+
+#[aoc(day23, part2)]
+pub fn shortest_distance_to_best_coverage(nanobots: &[Nanobot]) -> Distance {
+    let (position, count) = best_coverage(nanobots);
+    debug!("position {} is in range of {} nanobots", position, count);
+    position.manhattan_distance(Position::ORIGIN)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,227 @@
+use super::*;
+
+const INPUT: &str = include_str!("../../input/2018/day23.txt");
+
+const EXAMPLE1_INPUT: &str = "\
+pos=<0,0,0>, r=4
+pos=<1,0,0>, r=1
+pos=<4,0,0>, r=3
+pos=<0,2,0>, r=1
+pos=<0,5,0>, r=3
+pos=<0,0,3>, r=1
+pos=<1,1,1>, r=1
+pos=<1,1,2>, r=1
+pos=<1,3,1>, r=1
+";
+
+const EXAMPLE2_INPUT: &str = "\
+pos=<10,12,12>, r=2
+pos=<12,14,12>, r=2
+pos=<16,12,12>, r=4
+pos=<14,14,14>, r=6
+pos=<50,50,50>, r=200
+pos=<10,10,10>, r=5
+";
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let nanobots = parse(EXAMPLE1_INPUT).unwrap();
+
+        assert_eq!(nanobots.len(), 9);
+        assert_eq!(nanobots[0], Nanobot::new(Position::new(0, 0, 0), 4));
+        assert_eq!(nanobots[8], Nanobot::new(Position::new(1, 3, 1), 1));
+    }
+
+    #[test]
+    fn negative_coordinates() {
+        let nanobot = "pos=<-1,2,-30>, r=7".parse::<Nanobot>().unwrap();
+
+        assert_eq!(nanobot, Nanobot::new(Position::new(-1, 2, -30), 7));
+    }
+
+    #[test]
+    fn input() {
+        let nanobots = parse(INPUT).unwrap();
+
+        let formatted = nanobots
+            .iter()
+            .map(|bot| format!("{}\n", bot))
+            .collect::<String>();
+
+        assert_eq!(nanobots.len(), 1000);
+        assert_eq!(formatted, INPUT);
+    }
+
+    #[test]
+    fn missing_radius() {
+        let result = parse("pos=<1,2,3>, r=1\npos=<1,2,3>\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::end_of_line(23, 2, "pos=<1,2,3>", "\"r=\""))
+        );
+    }
+
+    #[test]
+    fn missing_position() {
+        let result = "r=1".parse::<Nanobot>();
+
+        assert_eq!(
+            result,
+            Err(ParseNanobotError::MissingPosition("r=1".to_string()))
+        );
+    }
+
+    #[test]
+    fn wrong_number_of_coordinates() {
+        let result = "pos=<1,2>, r=1".parse::<Nanobot>();
+
+        assert_eq!(result, Err(ParseNanobotError::WrongNumberOfCoordinates(2)));
+    }
+
+    #[test]
+    fn coordinate_not_an_integer() {
+        let result = "pos=<1,y,3>, r=1".parse::<Nanobot>();
+
+        match result {
+            Err(ParseNanobotError::NotAnInteger(value, _)) => assert_eq!(value, "y"),
+            _ => panic!("unexpected result {:?}", result),
+        }
+    }
+}
+
+mod try_parse {
+    use super::*;
+
+    #[test]
+    fn column_of_coordinate_not_an_integer() {
+        let result = try_parse("pos=<1,2,3>, r=1\npos=<-1,2,->, r=1\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(23, 2, 11, "an integer", "\"-\""))
+        );
+    }
+
+    #[test]
+    fn column_of_radius_not_an_integer() {
+        let result = try_parse("pos=<5,5,5>, r=5x\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(23, 1, 16, "an integer", "\"5x\""))
+        );
+    }
+}
+
+mod cube {
+    use super::*;
+
+    #[test]
+    fn distance_to() {
+        let cube = Cube::new(Position::new(0, 0, 0), 4);
+
+        assert_eq!(cube.distance_to(Position::new(2, 3, 1)), 0);
+        assert_eq!(cube.distance_to(Position::new(-2, 3, 1)), 2);
+        assert_eq!(cube.distance_to(Position::new(5, 7, -1)), 7);
+    }
+
+    #[test]
+    fn octants_cover_the_cube() {
+        let cube = Cube::new(Position::new(-2, 0, 4), 4);
+
+        let octants = cube.octants().collect::<Vec<_>>();
+
+        assert_eq!(octants.len(), 8);
+        assert!(octants.iter().all(|octant| octant.size() == 2));
+        assert!(octants.contains(&Cube::new(Position::new(-2, 0, 4), 2)));
+        assert!(octants.contains(&Cube::new(Position::new(0, 2, 6), 2)));
+    }
+
+    #[test]
+    fn enclosing_contains_origin_and_range_of_nanobots() {
+        let nanobots = vec![Nanobot::new(Position::new(10, 0, 0), 3)];
+
+        let cube = Cube::enclosing(&nanobots);
+
+        assert_eq!(cube, Cube::new(Position::new(0, -3, -3), 16));
+    }
+
+    #[test]
+    fn enclosing_example2() {
+        let nanobots = parse(EXAMPLE2_INPUT).unwrap();
+
+        let cube = Cube::enclosing(&nanobots);
+
+        assert_eq!(cube, Cube::new(Position::new(-150, -150, -150), 512));
+    }
+}
+
+mod best_coverage {
+    use super::*;
+
+    #[test]
+    fn example2() {
+        let nanobots = parse(EXAMPLE2_INPUT).unwrap();
+
+        let best = best_coverage(&nanobots);
+
+        assert_eq!(best, (Position::new(12, 12, 12), 5));
+    }
+
+    #[test]
+    fn origin_in_range_of_single_nanobot() {
+        let nanobots = vec![Nanobot::new(Position::new(10, 0, 0), 100)];
+
+        let best = best_coverage(&nanobots);
+
+        assert_eq!(best, (Position::ORIGIN, 1));
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let nanobots = parse(EXAMPLE1_INPUT).unwrap();
+
+        let answer = num_nanobots_in_range_of_strongest(&nanobots);
+
+        assert_eq!(answer, Some(7));
+    }
+
+    #[test]
+    fn answer() {
+        let nanobots = parse(INPUT).unwrap();
+
+        let answer = num_nanobots_in_range_of_strongest(&nanobots);
+
+        assert_eq!(answer, Some(704));
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    #[test]
+    fn example2() {
+        let nanobots = parse(EXAMPLE2_INPUT).unwrap();
+
+        let answer = shortest_distance_to_best_coverage(&nanobots);
+
+        assert_eq!(answer, 36);
+    }
+
+    #[test]
+    fn answer() {
+        let nanobots = parse(INPUT).unwrap();
+
+        let answer = shortest_distance_to_best_coverage(&nanobots);
+
+        assert_eq!(answer, 111_960_222);
+    }
+}
@@ -0,0 +1,677 @@
+//! # Day 24: Immune System Simulator 20XX
+//!
+//! After a weird buzzing noise, you appear back at the man's cottage. He seems
+//! relieved to see his friend, but quickly notices that the little reindeer
+//! caught some kind of cold while out exploring.
+//!
+//! The portly man explains that this reindeer's immune system isn't similar to
+//! regular reindeer immune systems:
+//!
+//! The immune system and the infection each have an army made up of several
+//! groups; each group consists of one or more identical units. The armies
+//! repeatedly fight until only one army has units remaining.
+//!
+//! Units within a group all have the same hit points (amount of damage a unit
+//! can take before it is destroyed), attack damage (the amount of damage each
+//! unit deals), an attack type, an initiative (higher initiative units attack
+//! first and win ties), and sometimes weaknesses or immunities. Here is an
+//! example group:
+//!
+//! ```text
+//! 18 units each with 729 hit points (weak to fire; immune to cold, slashing)
+//!  with an attack that does 8 radiation damage at initiative 10
+//! ```
+//!
+//! Each group also has an effective power: the number of units in that group
+//! multiplied by their attack damage. The above group has an effective power
+//! of 18 * 8 = 144. Groups never have zero or negative units; instead, the
+//! group is removed from combat.
+//!
+//! Each fight consists of two phases: target selection and attacking.
+//!
+//! During the target selection phase, each group attempts to choose one
+//! target. In decreasing order of effective power, groups choose their
+//! targets; in a tie, the group with the higher initiative chooses first. The
+//! attacking group chooses to target the group in the enemy army to which it
+//! would deal the most damage (after accounting for weaknesses and
+//! immunities, but not accounting for whether the defending group has enough
+//! units to actually receive all of that damage).
+//!
+//! If an attacking group is considering two defending groups to which it would
+//! deal equal damage, it chooses to target the defending group with the
+//! largest effective power; if there is still a tie, it chooses the defending
+//! group with the highest initiative. If it cannot deal any defending groups
+//! damage, it does not choose a target. Defending groups can only be chosen as
+//! a target by one attacking group.
+//!
+//! At the end of the target selection phase, each group has selected zero or
+//! one groups to attack, and each group is being attacked by zero or one
+//! groups.
+//!
+//! During the attacking phase, each group deals damage to the target it
+//! selected, if any. Groups attack in decreasing order of initiative,
+//! regardless of whether they are part of the infection or the immune system.
+//! (If a group contains no units, it cannot attack.)
+//!
+//! The damage an attacking group deals to a defending group depends on the
+//! attacking group's attack type and the defending group's immunities and
+//! weaknesses. By default, an attacking group would deal damage equal to its
+//! effective power to the defending group. However, if the defending group is
+//! immune to the attacking group's attack type, the defending group instead
+//! takes no damage; if the defending group is weak to the attacking group's
+//! attack type, the defending group instead takes double damage.
+//!
+//! The defending group only loses whole units from damage; damage is always
+//! dealt in such a way that it kills the most units possible, and any
+//! remaining damage to a unit that does not immediately kill it is ignored.
+//! For example, if a defending group contains 10 units with 10 hit points each
+//! and receives 75 damage, it loses exactly 7 units and is left with 3 units
+//! at full health.
+//!
+//! After the fight is over, if both armies still contain units, a new fight
+//! begins; combat only ends once one army has lost all of its units.
+//!
+//! For example, consider the following armies:
+//!
+//! ```text
+//! Immune System:
+//! 17 units each with 5390 hit points (weak to radiation, bludgeoning) with
+//!  an attack that does 4507 fire damage at initiative 2
+//! 989 units each with 1274 hit points (immune to fire; weak to bludgeoning,
+//!  slashing) with an attack that does 25 slashing damage at initiative 3
+//!
+//! Infection:
+//! 801 units each with 4706 hit points (weak to radiation) with an attack
+//!  that does 116 bludgeoning damage at initiative 1
+//! 4485 units each with 2961 hit points (immune to radiation; weak to fire,
+//!  cold) with an attack that does 12 slashing damage at initiative 4
+//! ```
+//!
+//! If these armies were to enter combat, the infection would destroy the
+//! immune system after 8 fights.
+//!
+//! In the example above, the winning army ends up with 782 + 4434 = 5216
+//! units.
+//!
+//! You scan the reindeer's condition (your puzzle input); the white-bearded
+//! man looks nervous. As it stands now, how many units would the winning army
+//! have?
+//!
+//! ## Part 2
+//!
+//! Things aren't looking good for the reindeer. The man asks whether more
+//! milk and cookies would help you think.
+//!
+//! If only you could give the reindeer's immune system a boost, you might be
+//! able to change the outcome of the combat.
+//!
+//! A boost is an integer increase in immune system units' attack damage. For
+//! example, if you were to boost the above example's immune system's units by
+//! 1570, the armies would instead look like this:
+//!
+//! ```text
+//! Immune System:
+//! 17 units each with 5390 hit points (weak to radiation, bludgeoning) with
+//!  an attack that does 6077 fire damage at initiative 2
+//! 989 units each with 1274 hit points (immune to fire; weak to bludgeoning,
+//!  slashing) with an attack that does 1595 slashing damage at initiative 3
+//! ```
+//!
+//! With this boost, the combat proceeds differently and the immune system
+//! wins with 51 units remaining.
+//!
+//! How many units does the immune system have left after getting the smallest
+//! boost it needs to win?
+//!
+//! [Advent of Code 2018 - Day 24](https://adventofcode.com/2018/day/24)
+
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    convert::TryFrom,
+    fmt::{self, Display},
+    iter::FromIterator,
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, ParseError};
+
+use self::Army::*;
+use self::AttackType::*;
+use self::FightResult::*;
+
+pub type Units = u32;
+pub type HitPoints = u32;
+pub type Damage = u64;
+pub type Initiative = u32;
+pub type Boost = u32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Army {
+    ImmuneSystem,
+    Infection,
+}
+
+impl Display for Army {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            ImmuneSystem => "Immune System",
+            Infection => "Infection",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AttackType {
+    Bludgeoning,
+    Cold,
+    Fire,
+    Radiation,
+    Slashing,
+}
+
+impl Display for AttackType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Bludgeoning => "bludgeoning",
+            Cold => "cold",
+            Fire => "fire",
+            Radiation => "radiation",
+            Slashing => "slashing",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for AttackType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim() {
+            "bludgeoning" => Bludgeoning,
+            "cold" => Cold,
+            "fire" => Fire,
+            "radiation" => Radiation,
+            "slashing" => Slashing,
+            _ => return Err(format!("unknown attack type {:?}", s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Group {
+    army: Army,
+    id: usize,
+    units: Units,
+    hit_points: HitPoints,
+    attack_damage: Damage,
+    attack_type: AttackType,
+    initiative: Initiative,
+    weaknesses: HashSet<AttackType>,
+    immunities: HashSet<AttackType>,
+}
+
+impl Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} units each with {} hit points",
+            self.units, self.hit_points
+        )?;
+        let mut weaknesses = Vec::from_iter(self.weaknesses.iter().map(ToString::to_string));
+        let mut immunities = Vec::from_iter(self.immunities.iter().map(ToString::to_string));
+        weaknesses.sort();
+        immunities.sort();
+        match (weaknesses.is_empty(), immunities.is_empty()) {
+            (true, true) => {},
+            (false, true) => write!(f, " (weak to {})", weaknesses.join(", "))?,
+            (true, false) => write!(f, " (immune to {})", immunities.join(", "))?,
+            (false, false) => write!(
+                f,
+                " (immune to {}; weak to {})",
+                immunities.join(", "),
+                weaknesses.join(", ")
+            )?,
+        }
+        write!(
+            f,
+            " with an attack that does {} {} damage at initiative {}",
+            self.attack_damage, self.attack_type, self.initiative
+        )
+    }
+}
+
+impl Group {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        army: Army,
+        id: usize,
+        units: Units,
+        hit_points: HitPoints,
+        attack_damage: Damage,
+        attack_type: AttackType,
+        initiative: Initiative,
+        weaknesses: impl IntoIterator<Item = AttackType>,
+        immunities: impl IntoIterator<Item = AttackType>,
+    ) -> Self {
+        Self {
+            army,
+            id,
+            units,
+            hit_points,
+            attack_damage,
+            attack_type,
+            initiative,
+            weaknesses: HashSet::from_iter(weaknesses),
+            immunities: HashSet::from_iter(immunities),
+        }
+    }
+
+    pub fn army(&self) -> Army {
+        self.army
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn units(&self) -> Units {
+        self.units
+    }
+
+    pub fn hit_points(&self) -> HitPoints {
+        self.hit_points
+    }
+
+    pub fn attack_damage(&self) -> Damage {
+        self.attack_damage
+    }
+
+    pub fn attack_type(&self) -> AttackType {
+        self.attack_type
+    }
+
+    pub fn initiative(&self) -> Initiative {
+        self.initiative
+    }
+
+    pub fn is_weak_to(&self, attack_type: AttackType) -> bool {
+        self.weaknesses.contains(&attack_type)
+    }
+
+    pub fn is_immune_to(&self, attack_type: AttackType) -> bool {
+        self.immunities.contains(&attack_type)
+    }
+
+    pub fn effective_power(&self) -> Damage {
+        Damage::from(self.units) * self.attack_damage
+    }
+
+    /// The damage this group would deal to the given defending group.
+    pub fn damage_to(&self, defender: &Group) -> Damage {
+        if defender.is_immune_to(self.attack_type) {
+            0
+        } else if defender.is_weak_to(self.attack_type) {
+            self.effective_power() * 2
+        } else {
+            self.effective_power()
+        }
+    }
+
+    /// Deals the given damage to this group and returns the number of units
+    /// killed.
+    pub fn take_damage(&mut self, damage: Damage) -> Units {
+        let killed =
+            (damage / Damage::from(self.hit_points)).min(Damage::from(self.units)) as Units;
+        self.units -= killed;
+        killed
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FightResult {
+    Ongoing,
+    Won(Army, Units),
+    Stalemate,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Battle {
+    groups: Vec<Group>,
+    fights: u32,
+}
+
+impl Display for Battle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &army in &[ImmuneSystem, Infection] {
+            writeln!(f, "{}:", army)?;
+            let mut groups = self.army(army).peekable();
+            if groups.peek().is_none() {
+                writeln!(f, "No groups remain.")?;
+            }
+            for group in groups {
+                writeln!(f, "Group {} contains {} units", group.id, group.units)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Battle {
+    pub fn new(groups: impl IntoIterator<Item = Group>) -> Self {
+        Self {
+            groups: Vec::from_iter(groups),
+            fights: 0,
+        }
+    }
+
+    pub fn groups(&self) -> &[Group] {
+        &self.groups
+    }
+
+    pub fn army(&self, army: Army) -> impl Iterator<Item = &Group> {
+        self.groups.iter().filter(move |group| group.army == army)
+    }
+
+    pub fn units(&self, army: Army) -> Units {
+        self.army(army).map(|group| group.units).sum()
+    }
+
+    pub fn fights(&self) -> u32 {
+        self.fights
+    }
+
+    /// Increases the attack damage of all groups of the given army.
+    pub fn boost(&mut self, army: Army, boost: Boost) {
+        self.groups
+            .iter_mut()
+            .filter(|group| group.army == army)
+            .for_each(|group| group.attack_damage += Damage::from(boost));
+    }
+
+    pub fn status(&self) -> FightResult {
+        let immune_system = self.units(ImmuneSystem);
+        let infection = self.units(Infection);
+        if immune_system == 0 {
+            Won(Infection, infection)
+        } else if infection == 0 {
+            Won(ImmuneSystem, immune_system)
+        } else {
+            Ongoing
+        }
+    }
+
+    /// Chooses the target for each group.
+    ///
+    /// Returns a list of `(attacker, defender)` pairs of indices into the
+    /// groups of this battle.
+    pub fn select_targets(&self) -> Vec<(usize, usize)> {
+        let mut attackers = Vec::from_iter(0..self.groups.len());
+        attackers.sort_by_key(|&idx| {
+            let group = &self.groups[idx];
+            Reverse((group.effective_power(), group.initiative))
+        });
+        let mut selected = HashSet::with_capacity(self.groups.len());
+        let mut targets = Vec::with_capacity(self.groups.len());
+        for attacker_idx in attackers {
+            let attacker = &self.groups[attacker_idx];
+            let target = self
+                .groups
+                .iter()
+                .enumerate()
+                .filter(|(idx, defender)| defender.army != attacker.army && !selected.contains(idx))
+                .map(|(idx, defender)| (attacker.damage_to(defender), idx))
+                .filter(|&(damage, _)| damage > 0)
+                .max_by_key(|&(damage, idx)| {
+                    let defender = &self.groups[idx];
+                    (damage, defender.effective_power(), defender.initiative)
+                });
+            if let Some((_, defender_idx)) = target {
+                selected.insert(defender_idx);
+                targets.push((attacker_idx, defender_idx));
+            }
+        }
+        targets
+    }
+
+    /// Lets the groups attack their selected targets and returns the total
+    /// number of units killed.
+    pub fn attack(&mut self, mut targets: Vec<(usize, usize)>) -> Units {
+        targets.sort_by_key(|&(attacker, _)| Reverse(self.groups[attacker].initiative));
+        let mut killed = 0;
+        for (attacker, defender) in targets {
+            if self.groups[attacker].units == 0 {
+                continue;
+            }
+            let damage = self.groups[attacker].damage_to(&self.groups[defender]);
+            killed += self.groups[defender].take_damage(damage);
+        }
+        self.groups.retain(|group| group.units > 0);
+        killed
+    }
+
+    /// Runs one fight consisting of target selection and attacking.
+    ///
+    /// If no unit is killed during a fight, the battle is a stalemate as the
+    /// next fight would have the very same outcome.
+    pub fn fight_one_round(&mut self) -> FightResult {
+        let targets = self.select_targets();
+        let killed = self.attack(targets);
+        self.fights += 1;
+        match self.status() {
+            Ongoing if killed == 0 => Stalemate,
+            status => status,
+        }
+    }
+
+    pub fn fight(&mut self) -> FightResult {
+        loop {
+            debug!("fight {}:\n{}", self.fights + 1, self);
+            let result = self.fight_one_round();
+            if result != Ongoing {
+                debug!("finished battle after {} fights:\n{}", self.fights, self);
+                break result;
+            }
+        }
+    }
+}
+
+fn split_once<'a>(
+    line_no: usize,
+    line: &str,
+    rest: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    rest.split_once(separator)
+        .ok_or_else(|| ParseError::at(24, line_no, line, rest, format!("{:?}", separator.trim())))
+}
+
+fn parse_group(army: Army, id: usize, line_no: usize, line: &str) -> Result<Group, ParseError> {
+    let text = line.trim();
+    let split = |rest, separator| split_once(line_no, line, rest, separator);
+    let (units, rest) = split(text, " units each with ")?;
+    let (hit_points, rest) = split(rest, " hit points")?;
+    let (traits, rest) = if let Some(rest) = rest.strip_prefix(" (") {
+        split(rest, ")")?
+    } else {
+        ("", rest)
+    };
+    let rest = rest
+        .strip_prefix(" with an attack that does ")
+        .ok_or_else(|| ParseError::at(24, line_no, line, rest, "\"with an attack that does\""))?;
+    let (attack, initiative) = split(rest, " damage at initiative ")?;
+    let (attack_damage, attack_type) = split(attack, " ")?;
+
+    let parse_attack_type = |attack_type: &str| {
+        let attack_type = attack_type.trim();
+        attack_type
+            .parse::<AttackType>()
+            .map_err(|_| ParseError::at(24, line_no, line, attack_type, "an attack type"))
+    };
+    let mut weaknesses = Vec::new();
+    let mut immunities = Vec::new();
+    for part in traits.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        if let Some(types) = part.strip_prefix("weak to ") {
+            for attack_type in types.split(',') {
+                weaknesses.push(parse_attack_type(attack_type)?);
+            }
+        } else if let Some(types) = part.strip_prefix("immune to ") {
+            for attack_type in types.split(',') {
+                immunities.push(parse_attack_type(attack_type)?);
+            }
+        } else {
+            return Err(ParseError::at(
+                24,
+                line_no,
+                line,
+                part,
+                "\"weak to\" or \"immune to\"",
+            ));
+        }
+    }
+
+    Ok(Group::new(
+        army,
+        id,
+        error::number(24, line_no, line, units.trim(), "a number of units")?,
+        error::number(24, line_no, line, hit_points.trim(), "hit points")?,
+        error::number(24, line_no, line, attack_damage.trim(), "attack damage")?,
+        parse_attack_type(attack_type)?,
+        error::number(24, line_no, line, initiative.trim(), "an initiative")?,
+        weaknesses,
+        immunities,
+    ))
+}
+
+#[aoc_generator(day24)]
+pub fn parse(input: &str) -> Result<Battle, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Battle, ParseError> {
+    let mut groups = Vec::with_capacity(20);
+    let mut army = None;
+    let mut id = 0;
+    for (idx, line) in input.lines().enumerate() {
+        match line.trim() {
+            "" => {},
+            "Immune System:" => {
+                army = Some(ImmuneSystem);
+                id = 0;
+            },
+            "Infection:" => {
+                army = Some(Infection);
+                id = 0;
+            },
+            text => {
+                let army = army.ok_or_else(|| {
+                    ParseError::at(
+                        24,
+                        idx + 1,
+                        line,
+                        text,
+                        "\"Immune System:\" or \"Infection:\"",
+                    )
+                })?;
+                id += 1;
+                groups.push(parse_group(army, id, idx + 1, line)?);
+            },
+        }
+    }
+    Ok(Battle::new(groups))
+}
+
+/// Returns the number of units the winning army has left, or nothing if the
+/// battle ends in a stalemate.
+#[aoc(day24, part1)]
+pub fn num_units_of_winning_army(battle: &Battle) -> Option<Units> {
+    match battle.clone().fight() {
+        Won(_, units) => Some(units),
+        result => {
+            debug!("the battle has no winner: {:?}", result);
+            None
+        },
+    }
+}
+
+#[aoc(day24, part2)]
+pub fn num_units_left_with_smallest_boost(battle: &Battle) -> Option<Units> {
+    smallest_boost_for_immune_system_to_win(battle).map(|(_, units)| units)
+}
+
+/// The boost from which on a higher boost no longer changes the course of the
+/// battle.
+///
+/// With a boost larger than the hit points of every infection group, each
+/// attack of the immune system destroys its target completely. With a boost
+/// larger than the effective power of every immune system group, the order
+/// in which the immune system groups select their targets depends only on
+/// their numbers of units and their attack damage, but no longer on the boost.
+fn max_effective_boost(battle: &Battle) -> Boost {
+    let hit_points = battle
+        .army(Infection)
+        .map(|group| Damage::from(group.units) * Damage::from(group.hit_points))
+        .max()
+        .unwrap_or(0);
+    let power = battle
+        .army(ImmuneSystem)
+        .map(Group::effective_power)
+        .max()
+        .unwrap_or(0);
+    Boost::try_from(hit_points.max(power).saturating_add(1)).unwrap_or(Boost::MAX)
+}
+
+fn fight_with_boost(initial_battle: &Battle, boost: Boost) -> FightResult {
+    let mut battle = initial_battle.clone();
+    battle.boost(ImmuneSystem, boost);
+    let result = battle.fight();
+    debug!("boost of {} results in {:?}", boost, result);
+    result
+}
+
+/// Searches the smallest boost for the immune system to win the battle.
+///
+/// The battle is fought with the largest effective boost first, so that a
+/// battle the immune system can not win at all is given up right away. Below
+/// that boost the smallest winning boost is bisected. As a higher boost does
+/// not always end the battle sooner, the boosts right below the one found are
+/// scanned as long as they do not lose, which covers wins hidden behind a
+/// range of stalemates. Battles that end in a stalemate are counted as lost.
+pub fn smallest_boost_for_immune_system_to_win(initial_battle: &Battle) -> Option<(Boost, Units)> {
+    let max_boost = max_effective_boost(initial_battle);
+    let mut smallest = match fight_with_boost(initial_battle, max_boost) {
+        Won(ImmuneSystem, units) => (max_boost, units),
+        _ => {
+            debug!("the immune system can not win even with the largest boost");
+            return None;
+        },
+    };
+    let mut lower = 0;
+    while lower < smallest.0 {
+        let boost = lower + (smallest.0 - lower) / 2;
+        match fight_with_boost(initial_battle, boost) {
+            Won(ImmuneSystem, units) => smallest = (boost, units),
+            _ => lower = boost + 1,
+        }
+    }
+    for boost in (0..smallest.0).rev() {
+        match fight_with_boost(initial_battle, boost) {
+            Won(ImmuneSystem, units) => smallest = (boost, units),
+            Stalemate => continue,
+            _ => break,
+        }
+    }
+    info!("immune system wins with a boost of {}", smallest.0);
+    Some(smallest)
+}
+
+#[cfg(test)]
+mod tests;
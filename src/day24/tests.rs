@@ -0,0 +1,321 @@
+use super::*;
+
+const INPUT: &str = include_str!("../../input/2018/day24.txt");
+
+const EXAMPLE1_INPUT: &str = "\
+Immune System:
+17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
+989 units each with 1274 hit points (immune to fire; weak to bludgeoning, slashing) with an attack that does 25 slashing damage at initiative 3
+
+Infection:
+801 units each with 4706 hit points (weak to radiation) with an attack that does 116 bludgeoning damage at initiative 1
+4485 units each with 2961 hit points (immune to radiation; weak to fire, cold) with an attack that does 12 slashing damage at initiative 4
+";
+
+const EXAMPLE1_AFTER_1_FIGHT: &str = "\
+Immune System:
+Group 2 contains 905 units
+Infection:
+Group 1 contains 797 units
+Group 2 contains 4434 units
+";
+
+const EXAMPLE1_FINAL: &str = "\
+Immune System:
+No groups remain.
+Infection:
+Group 1 contains 782 units
+Group 2 contains 4434 units
+";
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let battle = parse(EXAMPLE1_INPUT).unwrap();
+
+        assert_eq!(
+            battle,
+            Battle::new(vec![
+                Group::new(
+                    ImmuneSystem,
+                    1,
+                    17,
+                    5390,
+                    4507,
+                    Fire,
+                    2,
+                    vec![Radiation, Bludgeoning],
+                    vec![]
+                ),
+                Group::new(
+                    ImmuneSystem,
+                    2,
+                    989,
+                    1274,
+                    25,
+                    Slashing,
+                    3,
+                    vec![Bludgeoning, Slashing],
+                    vec![Fire]
+                ),
+                Group::new(
+                    Infection,
+                    1,
+                    801,
+                    4706,
+                    116,
+                    Bludgeoning,
+                    1,
+                    vec![Radiation],
+                    vec![]
+                ),
+                Group::new(
+                    Infection,
+                    2,
+                    4485,
+                    2961,
+                    12,
+                    Slashing,
+                    4,
+                    vec![Fire, Cold],
+                    vec![Radiation]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn group_without_weaknesses_and_immunities() {
+        let battle = parse(
+            "Infection:\n3814 units each with 33403 hit points with an attack that does 15 fire damage at initiative 19\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            battle.groups()[0].to_string(),
+            "3814 units each with 33403 hit points with an attack that does 15 fire damage at initiative 19"
+        );
+    }
+
+    #[test]
+    fn unknown_attack_type() {
+        let result = parse(
+            "Infection:\n1 units each with 1 hit points with an attack that does 1 psychic damage at initiative 1\n",
+        );
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(24, 2, 59, "an attack type", "\"psychic\""))
+        );
+    }
+
+    #[test]
+    fn group_without_army() {
+        let result = parse(
+            "1 units each with 1 hit points with an attack that does 1 fire damage at initiative 1\n",
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn input() {
+        let battle = parse(INPUT).unwrap();
+
+        assert_eq!(battle.army(ImmuneSystem).count(), 10);
+        assert_eq!(battle.army(Infection).count(), 10);
+    }
+}
+
+mod battle {
+    use super::*;
+
+    #[test]
+    fn example1_target_selection() {
+        let battle = parse(EXAMPLE1_INPUT).unwrap();
+
+        let mut targets = battle.select_targets();
+        targets.sort();
+
+        assert_eq!(targets, vec![(0, 3), (1, 2), (2, 0), (3, 1)]);
+    }
+
+    #[test]
+    fn example1_one_fight() {
+        let mut battle = parse(EXAMPLE1_INPUT).unwrap();
+
+        let result = battle.fight_one_round();
+
+        assert_eq!(result, Ongoing);
+        assert_eq!(battle.to_string(), EXAMPLE1_AFTER_1_FIGHT);
+    }
+
+    #[test]
+    fn example1_fight() {
+        let mut battle = parse(EXAMPLE1_INPUT).unwrap();
+
+        let result = battle.fight();
+
+        assert_eq!(result, Won(Infection, 5216));
+        assert_eq!(battle.fights(), 8);
+        assert_eq!(battle.to_string(), EXAMPLE1_FINAL);
+    }
+
+    #[test]
+    fn stalemate() {
+        let mut battle = Battle::new(vec![
+            Group::new(ImmuneSystem, 1, 10, 100, 5, Fire, 1, vec![], vec![Cold]),
+            Group::new(Infection, 1, 10, 100, 5, Cold, 2, vec![], vec![Fire]),
+        ]);
+
+        let result = battle.fight();
+
+        assert_eq!(result, Stalemate);
+        assert_eq!(battle.fights(), 1);
+    }
+
+    #[test]
+    fn damage_takes_only_whole_units() {
+        let mut group = Group::new(Infection, 1, 10, 10, 1, Fire, 1, vec![], vec![]);
+
+        let killed = group.take_damage(75);
+
+        assert_eq!(killed, 7);
+        assert_eq!(group.units(), 3);
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let battle = parse(EXAMPLE1_INPUT).unwrap();
+
+        let answer = num_units_of_winning_army(&battle);
+
+        assert_eq!(answer, Some(5216));
+    }
+
+    #[test]
+    fn stalemate() {
+        let battle = Battle::new(vec![
+            Group::new(ImmuneSystem, 1, 10, 100, 5, Fire, 1, vec![], vec![Cold]),
+            Group::new(Infection, 1, 10, 100, 5, Cold, 2, vec![], vec![Fire]),
+        ]);
+
+        let answer = num_units_of_winning_army(&battle);
+
+        assert_eq!(answer, None);
+    }
+
+    #[test]
+    fn answer() {
+        let battle = parse(INPUT).unwrap();
+
+        let answer = num_units_of_winning_army(&battle);
+
+        assert_eq!(answer, Some(14854));
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    #[test]
+    fn example1_with_boost() {
+        let mut battle = parse(EXAMPLE1_INPUT).unwrap();
+        battle.boost(ImmuneSystem, 1570);
+
+        let result = battle.fight();
+
+        assert_eq!(result, Won(ImmuneSystem, 51));
+    }
+
+    #[test]
+    fn example1() {
+        let battle = parse(EXAMPLE1_INPUT).unwrap();
+
+        let answer = smallest_boost_for_immune_system_to_win(&battle);
+
+        assert_eq!(answer, Some((1570, 51)));
+    }
+
+    #[test]
+    fn infection_immune_to_all_attacks() {
+        let battle = Battle::new(vec![
+            Group::new(ImmuneSystem, 1, 10, 100, 5, Fire, 1, vec![], vec![]),
+            Group::new(ImmuneSystem, 2, 10, 100, 5, Cold, 2, vec![], vec![]),
+            Group::new(
+                Infection,
+                1,
+                10,
+                100,
+                5,
+                Slashing,
+                3,
+                vec![],
+                vec![Fire, Cold],
+            ),
+        ]);
+
+        let answer = smallest_boost_for_immune_system_to_win(&battle);
+
+        assert_eq!(answer, None);
+    }
+
+    #[test]
+    fn without_immune_system() {
+        let battle = Battle::new(vec![Group::new(
+            Infection,
+            1,
+            10,
+            100,
+            5,
+            Slashing,
+            3,
+            vec![],
+            vec![],
+        )]);
+
+        let answer = smallest_boost_for_immune_system_to_win(&battle);
+
+        assert_eq!(answer, None);
+    }
+
+    #[test]
+    fn infection_attacks_first_for_every_boost() {
+        let battle = Battle::new(vec![
+            Group::new(ImmuneSystem, 1, 1, 1, 1, Fire, 1, vec![], vec![]),
+            Group::new(Infection, 1, 10, 100, 5, Cold, 2, vec![], vec![]),
+        ]);
+
+        let answer = smallest_boost_for_immune_system_to_win(&battle);
+
+        assert_eq!(answer, None);
+    }
+
+    #[test]
+    fn infection_kills_only_harmful_group_first() {
+        let battle = Battle::new(vec![
+            Group::new(ImmuneSystem, 1, 1, 10, 1, Fire, 1, vec![Fire], vec![]),
+            Group::new(ImmuneSystem, 2, 100, 10, 1, Cold, 2, vec![], vec![]),
+            Group::new(Infection, 1, 1000, 1000, 100, Fire, 10, vec![], vec![Cold]),
+        ]);
+
+        let answer = smallest_boost_for_immune_system_to_win(&battle);
+
+        assert_eq!(answer, None);
+    }
+
+    #[test]
+    fn answer() {
+        let battle = parse(INPUT).unwrap();
+
+        let answer = num_units_left_with_smallest_boost(&battle);
+
+        assert_eq!(answer, Some(3467));
+    }
+}
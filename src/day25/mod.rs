@@ -0,0 +1,308 @@
+//! # Day 25: Four-Dimensional Adventure
+//!
+//! The reindeer's symptoms are getting worse, and neither you nor the
+//! white-bearded man have a solution. At least the reindeer has a warm place
+//! to rest: a small bed near where you're sitting.
+//!
+//! As you reach down, the reindeer looks up at you, accidentally bumping a
+//! button on your wrist-mounted device with its nose in the process - a
+//! button labeled "help".
+//!
+//! "Hello, and welcome to the Time Travel Support Hotline! If you are lost in
+//! time and space, press 1. If you are trapped in a time paradox, press 2. If
+//! you need help caring for a sick reindeer, press 3. If you're having
+//! trouble with a time machine, press 4."
+//!
+//! You press 3. You wait for a while, and then finally, a friendly voice
+//! answers: "This is the reindeer helpline. Before we can help you, we need to
+//! calibrate your device. Please read us the fixed points in spacetime your
+//! device is reporting."
+//!
+//! The fixed points in spacetime (your puzzle input) are given as a list of 4D
+//! coordinates. Two points are in the same constellation if their manhattan
+//! distance apart is no more than 3 or if they can form a chain of points,
+//! each a manhattan distance no more than 3 from the last, between the two of
+//! them. (That is, if a point is close enough to a constellation, it "joins"
+//! that constellation.) For example:
+//!
+//! ```text
+//!  0,0,0,0
+//!  3,0,0,0
+//!  0,3,0,0
+//!  0,0,3,0
+//!  0,0,0,3
+//!  0,0,0,6
+//!  9,0,0,0
+//! 12,0,0,0
+//! ```
+//!
+//! In the above list, the first six points form a single constellation: 0,0,0,0
+//! is exactly distance 3 from the next four, and the point at 0,0,0,6 is
+//! connected to the others by being 3 away from 0,0,0,3, which is already in
+//! the constellation. The bottom two points, 9,0,0,0 and 12,0,0,0 are in a
+//! separate constellation because no point is close enough to connect them to
+//! the first constellation. So, in the above list, the number of
+//! constellations is 2. (If a point at 6,0,0,0 were present, it would connect
+//! 3,0,0,0 and 9,0,0,0, merging all of the points into a single giant
+//! constellation instead.)
+//!
+//! In this example, the number of constellations is 4:
+//!
+//! ```text
+//! -1,2,2,0
+//! 0,0,2,-2
+//! 0,0,0,-2
+//! -1,2,0,0
+//! -2,-2,-2,2
+//! 3,0,2,-1
+//! -1,3,2,2
+//! -1,0,-1,0
+//! 0,2,1,-2
+//! 3,0,0,0
+//! ```
+//!
+//! In this one, it's 3:
+//!
+//! ```text
+//! 1,-1,0,1
+//! 2,0,-1,0
+//! 3,2,-1,0
+//! 0,0,3,1
+//! 0,0,-1,-1
+//! 2,3,-2,0
+//! -2,2,0,0
+//! 2,-2,0,-1
+//! 1,-1,0,-1
+//! 3,2,0,2
+//! ```
+//!
+//! Finally, in this one, it's 8:
+//!
+//! ```text
+//! 1,-1,-1,-2
+//! -2,-2,0,1
+//! 0,2,1,3
+//! -2,3,-2,1
+//! 0,2,3,-2
+//! -1,-1,1,-2
+//! 0,-2,-1,0
+//! -2,2,3,-1
+//! 1,2,2,0
+//! -1,-2,0,-2
+//! ```
+//!
+//! The portly man nervously strokes his white beard. It's time to get that
+//! hot chocolate.
+//!
+//! How many constellations are formed by the fixed points in spacetime?
+//!
+//! [Advent of Code 2018 - Day 25](https://adventofcode.com/2018/day/25)
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    num::ParseIntError,
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ParseError, union_find::UnionFind};
+
+pub type Coord = i32;
+pub type Distance = u64;
+
+/// The maximum distance of two points to be in the same constellation.
+pub const CONSTELLATION_DISTANCE: Distance = 3;
+
+/// A point in 4-dimensional space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Point4 {
+    pub x: Coord,
+    pub y: Coord,
+    pub z: Coord,
+    pub t: Coord,
+}
+
+impl Point4 {
+    pub const ORIGIN: Point4 = Point4 {
+        x: 0,
+        y: 0,
+        z: 0,
+        t: 0,
+    };
+
+    pub fn new(x: Coord, y: Coord, z: Coord, t: Coord) -> Self {
+        Self { x, y, z, t }
+    }
+
+    pub fn coords(self) -> [Coord; 4] {
+        [self.x, self.y, self.z, self.t]
+    }
+
+    /// Manhattan distance from this point to another point.
+    ///
+    /// The distance is wider than the coordinates, so that it does not
+    /// overflow even for points at opposite corners of the coordinate range.
+    pub fn manhattan_distance(self, other: Point4) -> Distance {
+        Distance::from(self.x.abs_diff(other.x))
+            + Distance::from(self.y.abs_diff(other.y))
+            + Distance::from(self.z.abs_diff(other.z))
+            + Distance::from(self.t.abs_diff(other.t))
+    }
+}
+
+impl From<(Coord, Coord, Coord, Coord)> for Point4 {
+    fn from((x, y, z, t): (Coord, Coord, Coord, Coord)) -> Self {
+        Point4 { x, y, z, t }
+    }
+}
+
+impl From<[Coord; 4]> for Point4 {
+    fn from([x, y, z, t]: [Coord; 4]) -> Self {
+        Point4 { x, y, z, t }
+    }
+}
+
+impl Display for Point4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{},{}", self.x, self.y, self.z, self.t)
+    }
+}
+
+impl Add for Point4 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point4::new(
+            self.x + rhs.x,
+            self.y + rhs.y,
+            self.z + rhs.z,
+            self.t + rhs.t,
+        )
+    }
+}
+
+impl Sub for Point4 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point4::new(
+            self.x - rhs.x,
+            self.y - rhs.y,
+            self.z - rhs.z,
+            self.t - rhs.t,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsePoint4Error {
+    WrongNumberOfCoordinates(String),
+    NotAnInteger(String, ParseIntError),
+}
+
+impl Display for ParsePoint4Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePoint4Error::WrongNumberOfCoordinates(s) => {
+                write!(f, "expected 4 coordinates in {:?}", s)
+            },
+            ParsePoint4Error::NotAnInteger(s, err) => {
+                write!(f, "coordinate {:?} is not an integer: {}", s, err)
+            },
+        }
+    }
+}
+
+impl Error for ParsePoint4Error {}
+
+impl FromStr for Point4 {
+    type Err = ParsePoint4Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_point4(s).map_err(|(err, _)| err)
+    }
+}
+
+/// Parses a point and returns the error together with the part of the text
+/// that caused it, or nothing if the text ends before the fourth coordinate.
+fn parse_point4(s: &str) -> Result<Point4, (ParsePoint4Error, Option<&str>)> {
+    let mut coords = [0; 4];
+    let mut parts = s.trim().split(',').map(str::trim);
+    for coord in coords.iter_mut() {
+        let part = parts.next().ok_or_else(|| {
+            (
+                ParsePoint4Error::WrongNumberOfCoordinates(s.to_string()),
+                None,
+            )
+        })?;
+        *coord = part.parse().map_err(|err| {
+            (
+                ParsePoint4Error::NotAnInteger(part.to_string(), err),
+                Some(part),
+            )
+        })?;
+    }
+    if let Some(extra) = parts.next() {
+        return Err((
+            ParsePoint4Error::WrongNumberOfCoordinates(s.to_string()),
+            Some(extra),
+        ));
+    }
+    Ok(Point4::from(coords))
+}
+
+/// Groups the given points into constellations.
+///
+/// Each constellation lists its points in the order they are given.
+pub fn constellations(points: &[Point4]) -> Vec<Vec<Point4>> {
+    let mut union_find = UnionFind::new(points.len());
+    for (idx1, point1) in points.iter().enumerate() {
+        for (idx2, point2) in points.iter().enumerate().skip(idx1 + 1) {
+            if point1.manhattan_distance(*point2) <= CONSTELLATION_DISTANCE {
+                union_find.union(idx1, idx2);
+            }
+        }
+    }
+    union_find
+        .sets()
+        .into_iter()
+        .map(|set| set.into_iter().map(|idx| points[idx]).collect())
+        .collect()
+}
+
+#[aoc_generator(day25)]
+pub fn parse(input: &str) -> Result<Vec<Point4>, ParseError> {
+    try_parse(input)
+}
+
+pub fn try_parse(input: &str) -> Result<Vec<Point4>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            parse_point4(line).map_err(|(cause, part)| match (cause, part) {
+                (_, None) => ParseError::end_of_line(25, idx + 1, line, "\",\""),
+                (ParsePoint4Error::WrongNumberOfCoordinates(_), Some(extra)) => {
+                    ParseError::at(25, idx + 1, line, extra, "end of line")
+                },
+                (ParsePoint4Error::NotAnInteger(..), Some(part)) => {
+                    ParseError::at(25, idx + 1, line, part, "a coordinate")
+                },
+            })
+        })
+        .collect()
+}
+
+#[aoc(day25, part1)]
+pub fn num_constellations(points: &[Point4]) -> usize {
+    constellations(points).len()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,229 @@
+use super::*;
+
+const INPUT: &str = include_str!("../../input/2018/day25.txt");
+
+const EXAMPLE1_INPUT: &str = "
+ 0,0,0,0
+ 3,0,0,0
+ 0,3,0,0
+ 0,0,3,0
+ 0,0,0,3
+ 0,0,0,6
+ 9,0,0,0
+12,0,0,0
+";
+
+const EXAMPLE2_INPUT: &str = "\
+-1,2,2,0
+0,0,2,-2
+0,0,0,-2
+-1,2,0,0
+-2,-2,-2,2
+3,0,2,-1
+-1,3,2,2
+-1,0,-1,0
+0,2,1,-2
+3,0,0,0
+";
+
+const EXAMPLE3_INPUT: &str = "\
+1,-1,0,1
+2,0,-1,0
+3,2,-1,0
+0,0,3,1
+0,0,-1,-1
+2,3,-2,0
+-2,2,0,0
+2,-2,0,-1
+1,-1,0,-1
+3,2,0,2
+";
+
+const EXAMPLE4_INPUT: &str = "\
+1,-1,-1,-2
+-2,-2,0,1
+0,2,1,3
+-2,3,-2,1
+0,2,3,-2
+-1,-1,1,-2
+0,-2,-1,0
+-2,2,3,-1
+1,2,2,0
+-1,-2,0,-2
+";
+
+mod point4 {
+    use super::*;
+
+    #[test]
+    fn parse_negative_coordinates() {
+        let point = " -1,2,-3,0 ".parse::<Point4>().unwrap();
+
+        assert_eq!(point, Point4::new(-1, 2, -3, 0));
+    }
+
+    #[test]
+    fn parse_too_few_coordinates() {
+        let result = "1,2,3".parse::<Point4>();
+
+        assert_eq!(
+            result,
+            Err(ParsePoint4Error::WrongNumberOfCoordinates(
+                "1,2,3".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_too_many_coordinates() {
+        let result = "1,2,3,4,5".parse::<Point4>();
+
+        assert_eq!(
+            result,
+            Err(ParsePoint4Error::WrongNumberOfCoordinates(
+                "1,2,3,4,5".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_error_reports_line() {
+        let result = parse("0,0,0,0\n0,x,0,0\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(25, 2, 3, "a coordinate", "\"x\""))
+        );
+    }
+
+    #[test]
+    fn parse_error_reports_missing_coordinate() {
+        let result = parse("0,0,0\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(25, 1, 6, "\",\"", "end of line"))
+        );
+    }
+
+    #[test]
+    fn parse_error_reports_extra_coordinate() {
+        let result = parse("0,0,0,0, 7\n");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(25, 1, 10, "end of line", "\"7\""))
+        );
+    }
+
+    #[test]
+    fn manhattan_distance_of_opposite_corners() {
+        let min = Point4::new(Coord::MIN, Coord::MIN, Coord::MIN, Coord::MIN);
+        let max = Point4::new(Coord::MAX, Coord::MAX, Coord::MAX, Coord::MAX);
+
+        assert_eq!(min.manhattan_distance(max), 4 * u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn manhattan_distance() {
+        let point1 = Point4::new(0, -2, 3, 1);
+        let point2 = Point4::new(-1, 2, 0, 1);
+
+        assert_eq!(point1.manhattan_distance(point2), 8);
+        assert_eq!(point2.manhattan_distance(point1), 8);
+        assert_eq!(point1.manhattan_distance(Point4::ORIGIN), 6);
+    }
+
+    #[test]
+    fn display() {
+        let point = Point4::new(-1, 2, 0, 30);
+
+        assert_eq!(point.to_string(), "-1,2,0,30");
+    }
+}
+
+mod constellations {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let points = parse(EXAMPLE1_INPUT).unwrap();
+
+        let constellations = constellations(&points);
+
+        assert_eq!(
+            constellations,
+            vec![
+                vec![
+                    Point4::new(0, 0, 0, 0),
+                    Point4::new(3, 0, 0, 0),
+                    Point4::new(0, 3, 0, 0),
+                    Point4::new(0, 0, 3, 0),
+                    Point4::new(0, 0, 0, 3),
+                    Point4::new(0, 0, 0, 6),
+                ],
+                vec![Point4::new(9, 0, 0, 0), Point4::new(12, 0, 0, 0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn example1_with_connecting_point() {
+        let mut points = parse(EXAMPLE1_INPUT).unwrap();
+        points.push(Point4::new(6, 0, 0, 0));
+
+        let constellations = constellations(&points);
+
+        assert_eq!(constellations.len(), 1);
+        assert_eq!(constellations[0].len(), 9);
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let points = parse(EXAMPLE1_INPUT).unwrap();
+
+        let answer = num_constellations(&points);
+
+        assert_eq!(answer, 2);
+    }
+
+    #[test]
+    fn example2() {
+        let points = parse(EXAMPLE2_INPUT).unwrap();
+
+        let answer = num_constellations(&points);
+
+        assert_eq!(answer, 4);
+    }
+
+    #[test]
+    fn example3() {
+        let points = parse(EXAMPLE3_INPUT).unwrap();
+
+        let answer = num_constellations(&points);
+
+        assert_eq!(answer, 3);
+    }
+
+    #[test]
+    fn example4() {
+        let points = parse(EXAMPLE4_INPUT).unwrap();
+
+        let answer = num_constellations(&points);
+
+        assert_eq!(answer, 8);
+    }
+
+    #[test]
+    fn answer() {
+        let points = parse(INPUT).unwrap();
+
+        let answer = num_constellations(&points);
+
+        assert_eq!(answer, 390);
+    }
+}
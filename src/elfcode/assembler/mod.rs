@@ -0,0 +1,574 @@
+//! Reads and writes the text form of ElfCode programs.
+//!
+//! Each line holds one instruction consisting of an operation and three
+//! operands. The operation is either a mnemonic like `addi` or an opcode
+//! number like `9`, depending on the kind of program. An optional `#ip`
+//! declaration binds the instruction pointer to a register.
+//!
+//! On top of the format used by the puzzles the assembler supports:
+//!
+//! * comments starting with `;` and running to the end of the line,
+//! * labels defined by `name:` in front of an instruction or on a line of
+//!   their own,
+//! * label references as operands, written `@name` for the address of the
+//!   labeled instruction, optionally followed by an offset like `@name-1`.
+//!
+//! Since jumps continue at the instruction after the value written to the
+//! instruction pointer, a jump to a label is written as `seti @loop-1 0 3`.
+//!
+//! ```text
+//! #ip 3
+//!       addi 3 16 3     ; goto init
+//! loop: mulr 5 2 1
+//!       seti @loop-1 0 3
+//! ```
+//!
+//! Formatting a program prints plain numbers without labels or comments, so
+//! that parsing the output gives back the same program.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::error::{describe, ParseError};
+
+use super::{Addr, Data, Instruction, Mnemonic, OpCode, OpCodeTable, Program};
+
+/// The operation part of an instruction as written in the text form.
+pub trait Operation: Copy + Display {
+    /// What the operation is called in error messages.
+    const NAME: &'static str;
+
+    fn parse_operation(s: &str) -> Option<Self>;
+}
+
+impl Operation for Mnemonic {
+    const NAME: &'static str = "mnemonic";
+
+    fn parse_operation(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+}
+
+impl Operation for OpCode {
+    const NAME: &'static str = "opcode";
+
+    fn parse_operation(s: &str) -> Option<Self> {
+        s.parse().ok().map(OpCode)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssembleErrorKind {
+    UnknownOperation(&'static str, String),
+    InvalidOperand(String),
+    MissingOperand,
+    UnexpectedToken(String),
+    InvalidLabel(String),
+    DuplicateLabel(String),
+    UndefinedLabel(String),
+    InvalidDirective(String),
+    DuplicateIpDeclaration,
+    MissingIpDeclaration,
+}
+
+impl Display for AssembleErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssembleErrorKind::UnknownOperation(name, s) => write!(f, "unknown {} {:?}", name, s),
+            AssembleErrorKind::InvalidOperand(s) => write!(f, "invalid operand {:?}", s),
+            AssembleErrorKind::MissingOperand => f.write_str("missing operand"),
+            AssembleErrorKind::UnexpectedToken(s) => write!(f, "unexpected {:?}", s),
+            AssembleErrorKind::InvalidLabel(s) => write!(f, "invalid label name {:?}", s),
+            AssembleErrorKind::DuplicateLabel(s) => write!(f, "label {:?} defined twice", s),
+            AssembleErrorKind::UndefinedLabel(s) => write!(f, "undefined label {:?}", s),
+            AssembleErrorKind::InvalidDirective(s) => write!(f, "invalid directive {:?}", s),
+            AssembleErrorKind::DuplicateIpDeclaration => f.write_str("duplicate #ip declaration"),
+            AssembleErrorKind::MissingIpDeclaration => f.write_str("missing #ip declaration"),
+        }
+    }
+}
+
+/// An error in the text form of a program. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssembleError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: AssembleErrorKind,
+}
+
+impl Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl Error for AssembleError {}
+
+impl AssembleError {
+    fn new(line: usize, column: usize, kind: AssembleErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+
+    /// Moves the error to the given line, e.g. when a single line has been
+    /// assembled out of a larger input.
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Converts the error into the parse error of the puzzle input of a day.
+    pub fn into_parse_error(self, day: u8) -> ParseError {
+        let (expected, found) = match self.kind {
+            AssembleErrorKind::UnknownOperation(name, s) => (format!("a valid {}", name), s),
+            AssembleErrorKind::InvalidOperand(s) => ("a number or label reference".into(), s),
+            AssembleErrorKind::MissingOperand => ("an operand".into(), String::new()),
+            AssembleErrorKind::UnexpectedToken(s) => ("end of line".into(), s),
+            AssembleErrorKind::InvalidLabel(s) => ("a label name".into(), s),
+            AssembleErrorKind::DuplicateLabel(s) => ("a new label name".into(), s),
+            AssembleErrorKind::UndefinedLabel(s) => ("a defined label".into(), s),
+            AssembleErrorKind::InvalidDirective(s) => ("\"#ip\"".into(), s),
+            AssembleErrorKind::DuplicateIpDeclaration => {
+                ("a single #ip declaration".into(), "#ip".into())
+            },
+            AssembleErrorKind::MissingIpDeclaration => ("an #ip declaration".into(), String::new()),
+        };
+        ParseError::new(day, self.line, self.column, expected, describe(&found))
+    }
+}
+
+/// A program in text form, with either mnemonics or opcode numbers as
+/// operations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assembly<O> {
+    pub ip_reg: Option<Addr>,
+    pub instructions: Vec<Instruction<O>>,
+}
+
+impl<O> Display for Assembly<O>
+where
+    O: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ip_reg) = self.ip_reg {
+            writeln!(f, "#ip {}", ip_reg)?;
+        }
+        for instruction in &self.instructions {
+            writeln!(f, "{}", instruction)?;
+        }
+        Ok(())
+    }
+}
+
+impl<O> FromStr for Assembly<O>
+where
+    O: Operation,
+{
+    type Err = AssembleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        assemble(s)
+    }
+}
+
+impl From<Program> for Assembly<Mnemonic> {
+    fn from(program: Program) -> Self {
+        Self {
+            ip_reg: Some(program.ip_reg()),
+            instructions: program.instructions().to_vec(),
+        }
+    }
+}
+
+/// An instruction whose operation has no counterpart in the opcode table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslateError {
+    UnsupportedOpCode { addr: Addr, opcode: OpCode },
+    UnsupportedMnemonic { addr: Addr, mnemonic: Mnemonic },
+}
+
+impl Display for TranslateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranslateError::UnsupportedOpCode { addr, opcode } => {
+                write!(f, "unsupported opcode {} at address {}", opcode, addr)
+            },
+            TranslateError::UnsupportedMnemonic { addr, mnemonic } => {
+                write!(f, "no opcode for {} at address {}", mnemonic, addr)
+            },
+        }
+    }
+}
+
+impl Error for TranslateError {}
+
+impl Assembly<Mnemonic> {
+    /// Replaces the mnemonics by their opcode numbers.
+    pub fn encode(&self, opcodes: &OpCodeTable) -> Result<Assembly<OpCode>, TranslateError> {
+        let instructions = self
+            .instructions
+            .iter()
+            .enumerate()
+            .map(|(addr, instruction)| {
+                opcodes
+                    .opcode(instruction.opcode)
+                    .map(|opcode| instruction.with_opcode(opcode))
+                    .ok_or(TranslateError::UnsupportedMnemonic {
+                        addr: addr as Addr,
+                        mnemonic: instruction.opcode,
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Assembly {
+            ip_reg: self.ip_reg,
+            instructions,
+        })
+    }
+
+    /// Turns the assembly into a program if it declares the instruction
+    /// pointer register.
+    pub fn into_program(self) -> Option<Program> {
+        let ip_reg = self.ip_reg?;
+        Some(Program::new(ip_reg, self.instructions))
+    }
+}
+
+impl Assembly<OpCode> {
+    /// Replaces the opcode numbers by their mnemonics.
+    pub fn decode(&self, opcodes: &OpCodeTable) -> Result<Assembly<Mnemonic>, TranslateError> {
+        let instructions = self
+            .instructions
+            .iter()
+            .enumerate()
+            .map(|(addr, instruction)| {
+                opcodes
+                    .get(instruction.opcode)
+                    .map(|mnemonic| instruction.with_opcode(mnemonic))
+                    .ok_or(TranslateError::UnsupportedOpCode {
+                        addr: addr as Addr,
+                        opcode: instruction.opcode,
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Assembly {
+            ip_reg: self.ip_reg,
+            instructions,
+        })
+    }
+}
+
+impl FromStr for Program {
+    type Err = AssembleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        assemble::<Mnemonic>(s)?
+            .into_program()
+            .ok_or_else(|| AssembleError::new(1, 1, AssembleErrorKind::MissingIpDeclaration))
+    }
+}
+
+/// A whitespace separated word of a line with the column it starts at.
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    column: usize,
+}
+
+/// Splits a line into tokens, dropping any comment.
+fn tokenize(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut column = 0;
+    for (idx, chr) in line.char_indices() {
+        column += 1;
+        if chr == ';' || chr.is_whitespace() {
+            if let Some((start_idx, start_column)) = start.take() {
+                tokens.push(Token {
+                    text: &line[start_idx..idx],
+                    column: start_column,
+                });
+            }
+            if chr == ';' {
+                return tokens;
+            }
+        } else if start.is_none() {
+            start = Some((idx, column));
+        }
+    }
+    if let Some((start_idx, start_column)) = start {
+        tokens.push(Token {
+            text: &line[start_idx..],
+            column: start_column,
+        });
+    }
+    tokens
+}
+
+fn is_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|chr| chr.is_ascii_alphabetic() || chr == '_')
+        && chars.all(|chr| chr.is_ascii_alphanumeric() || chr == '_')
+}
+
+/// An operand that may refer to a label which is resolved after all labels
+/// are known.
+#[derive(Debug, Clone)]
+enum Operand<'a> {
+    Value(Data),
+    Label {
+        name: &'a str,
+        offset: i64,
+        column: usize,
+    },
+}
+
+fn parse_operand(token: Token, line: usize) -> Result<Operand, AssembleError> {
+    let invalid = || {
+        AssembleError::new(
+            line,
+            token.column,
+            AssembleErrorKind::InvalidOperand(token.text.to_string()),
+        )
+    };
+    match token.text.strip_prefix('@') {
+        Some(reference) => {
+            let (name, offset) = match reference.find(['+', '-']) {
+                Some(idx) => {
+                    let offset = reference[idx + 1..].parse::<i64>().map_err(|_| invalid())?;
+                    let sign = if reference[idx..].starts_with('-') {
+                        -1
+                    } else {
+                        1
+                    };
+                    (&reference[..idx], sign * offset)
+                },
+                None => (reference, 0),
+            };
+            if !is_label_name(name) {
+                return Err(invalid());
+            }
+            Ok(Operand::Label {
+                name,
+                offset,
+                column: token.column,
+            })
+        },
+        None => token
+            .text
+            .parse()
+            .map(Operand::Value)
+            .map_err(|_| invalid()),
+    }
+}
+
+struct Line<'a, O> {
+    line: usize,
+    opcode: O,
+    operands: [Operand<'a>; 3],
+}
+
+fn resolve(
+    operand: &Operand,
+    labels: &HashMap<&str, Addr>,
+    line: usize,
+) -> Result<Data, AssembleError> {
+    match *operand {
+        Operand::Value(value) => Ok(value),
+        Operand::Label {
+            name,
+            offset,
+            column,
+        } => {
+            let addr = *labels.get(name).ok_or_else(|| {
+                AssembleError::new(
+                    line,
+                    column,
+                    AssembleErrorKind::UndefinedLabel(name.to_string()),
+                )
+            })?;
+            let value = if offset < 0 {
+                addr.checked_sub(offset.unsigned_abs())
+            } else {
+                addr.checked_add(offset as Data)
+            };
+            value.ok_or_else(|| {
+                AssembleError::new(
+                    line,
+                    column,
+                    AssembleErrorKind::InvalidOperand(format!("@{}{:+}", name, offset)),
+                )
+            })
+        },
+    }
+}
+
+/// Parses the text form of a program.
+pub fn assemble<O>(source: &str) -> Result<Assembly<O>, AssembleError>
+where
+    O: Operation,
+{
+    let mut ip_reg = None;
+    let mut labels = HashMap::new();
+    let mut lines = Vec::new();
+    for (idx, text) in source.lines().enumerate() {
+        let line = idx + 1;
+        let mut tokens = tokenize(text).into_iter().peekable();
+        if let Some(token) = tokens.peek().filter(|token| token.text.starts_with('#')) {
+            if token.text != "#ip" {
+                return Err(AssembleError::new(
+                    line,
+                    token.column,
+                    AssembleErrorKind::InvalidDirective(token.text.to_string()),
+                ));
+            }
+            if ip_reg.is_some() {
+                return Err(AssembleError::new(
+                    line,
+                    token.column,
+                    AssembleErrorKind::DuplicateIpDeclaration,
+                ));
+            }
+            let end = token.column + token.text.chars().count();
+            tokens.next();
+            let value = tokens
+                .next()
+                .ok_or_else(|| AssembleError::new(line, end, AssembleErrorKind::MissingOperand))?;
+            match parse_operand(value, line)? {
+                Operand::Value(reg) => ip_reg = Some(reg),
+                Operand::Label { .. } => {
+                    return Err(AssembleError::new(
+                        line,
+                        value.column,
+                        AssembleErrorKind::InvalidOperand(value.text.to_string()),
+                    ))
+                },
+            }
+            if let Some(extra) = tokens.next() {
+                return Err(AssembleError::new(
+                    line,
+                    extra.column,
+                    AssembleErrorKind::UnexpectedToken(extra.text.to_string()),
+                ));
+            }
+            continue;
+        }
+        while let Some(token) = tokens.peek().filter(|token| token.text.ends_with(':')) {
+            let name = &token.text[..token.text.len() - 1];
+            if !is_label_name(name) {
+                return Err(AssembleError::new(
+                    line,
+                    token.column,
+                    AssembleErrorKind::InvalidLabel(name.to_string()),
+                ));
+            }
+            if labels.insert(name, lines.len() as Addr).is_some() {
+                return Err(AssembleError::new(
+                    line,
+                    token.column,
+                    AssembleErrorKind::DuplicateLabel(name.to_string()),
+                ));
+            }
+            tokens.next();
+        }
+        let operation = match tokens.next() {
+            Some(token) => token,
+            None => continue,
+        };
+        let opcode = O::parse_operation(operation.text).ok_or_else(|| {
+            AssembleError::new(
+                line,
+                operation.column,
+                AssembleErrorKind::UnknownOperation(O::NAME, operation.text.to_string()),
+            )
+        })?;
+        let mut end = operation.column + operation.text.chars().count();
+        let mut operands = Vec::with_capacity(3);
+        for _ in 0..3 {
+            let token = tokens
+                .next()
+                .ok_or_else(|| AssembleError::new(line, end, AssembleErrorKind::MissingOperand))?;
+            end = token.column + token.text.chars().count();
+            operands.push(parse_operand(token, line)?);
+        }
+        if let Some(extra) = tokens.next() {
+            return Err(AssembleError::new(
+                line,
+                extra.column,
+                AssembleErrorKind::UnexpectedToken(extra.text.to_string()),
+            ));
+        }
+        let c = operands.pop().expect("three operands");
+        let b = operands.pop().expect("three operands");
+        let a = operands.pop().expect("three operands");
+        lines.push(Line {
+            line,
+            opcode,
+            operands: [a, b, c],
+        });
+    }
+
+    let instructions = lines
+        .iter()
+        .map(
+            |Line {
+                 line,
+                 opcode,
+                 operands,
+             }| {
+                Ok(Instruction {
+                    opcode: *opcode,
+                    a: resolve(&operands[0], &labels, *line)?,
+                    b: resolve(&operands[1], &labels, *line)?,
+                    c: resolve(&operands[2], &labels, *line)?,
+                })
+            },
+        )
+        .collect::<Result<_, _>>()?;
+    Ok(Assembly {
+        ip_reg,
+        instructions,
+    })
+}
+
+impl<O> FromStr for Instruction<O>
+where
+    O: Operation,
+{
+    type Err = AssembleError;
+
+    /// Parses a single instruction without labels.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(label) = tokenize(s)
+            .first()
+            .filter(|token| token.text.ends_with(':'))
+        {
+            return Err(AssembleError::new(
+                1,
+                label.column,
+                AssembleErrorKind::UnexpectedToken(label.text.to_string()),
+            ));
+        }
+        let mut assembly = assemble::<O>(s)?;
+        if assembly.ip_reg.is_some() || assembly.instructions.len() != 1 {
+            let column = s.chars().take_while(|chr| chr.is_whitespace()).count() + 1;
+            return Err(AssembleError::new(
+                1,
+                column,
+                AssembleErrorKind::UnexpectedToken(s.trim().to_string()),
+            ));
+        }
+        Ok(assembly.instructions.remove(0))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,357 @@
+use super::*;
+
+use crate::elfcode::{Mnemonic::*, INSTRUCTION_SET};
+
+const DAY19_INPUT: &str = include_str!("../../../input/2018/day19.txt");
+
+fn opcode_table() -> OpCodeTable {
+    INSTRUCTION_SET
+        .iter()
+        .enumerate()
+        .map(|(idx, &mnemonic)| (OpCode(idx as u8), mnemonic))
+        .collect()
+}
+
+fn error(line: usize, column: usize, kind: AssembleErrorKind) -> AssembleError {
+    AssembleError { line, column, kind }
+}
+
+mod assemble {
+    use super::*;
+
+    #[test]
+    fn mnemonic_program() {
+        let source = "#ip 0\nseti 5 0 1\nseti 6 0 2\naddi 0 1 0\n";
+
+        let assembly = assemble::<Mnemonic>(source);
+
+        assert_eq!(
+            assembly,
+            Ok(Assembly {
+                ip_reg: Some(0),
+                instructions: vec![
+                    Instruction::new(SetI, 5, 0, 1),
+                    Instruction::new(SetI, 6, 0, 2),
+                    Instruction::new(AddI, 0, 1, 0),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn opcode_program_without_ip_declaration() {
+        let source = "9 2 1 2\n15 0 10 3\n";
+
+        let assembly = assemble::<OpCode>(source);
+
+        assert_eq!(
+            assembly,
+            Ok(Assembly {
+                ip_reg: None,
+                instructions: vec![
+                    Instruction::new(OpCode(9), 2, 1, 2),
+                    Instruction::new(OpCode(15), 0, 10, 3),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn labels_and_comments() {
+        let source = "\
+; counts register 1 down to zero
+#ip 3
+       seti 10 0 1      ; counter
+loop:  addr 0 1 0
+       addi 1 0 1
+decrement:
+       mulr 1 1 1       ; not really
+       gtri 1 0 2
+       addr 3 2 3
+       seti @loop-1 0 3
+end:
+";
+
+        let assembly = assemble::<Mnemonic>(source).unwrap();
+
+        assert_eq!(assembly.ip_reg, Some(3));
+        assert_eq!(
+            assembly.instructions[3..],
+            [
+                Instruction::new(MulR, 1, 1, 1),
+                Instruction::new(GtRI, 1, 0, 2),
+                Instruction::new(AddR, 3, 2, 3),
+                Instruction::new(SetI, 0, 0, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn forward_label_reference_with_offset() {
+        let source = "seti @end+1 0 1\naddi 0 1 0\nend: addi 0 2 0\n";
+
+        let assembly = assemble::<Mnemonic>(source).unwrap();
+
+        assert_eq!(assembly.instructions[0], Instruction::new(SetI, 3, 0, 1));
+    }
+
+    #[test]
+    fn day19_input() {
+        let assembly = assemble::<Mnemonic>(DAY19_INPUT).unwrap();
+
+        assert_eq!(assembly.ip_reg, Some(3));
+        assert_eq!(assembly.instructions.len(), 36);
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn unknown_mnemonic() {
+        let result = assemble::<Mnemonic>("seti 5 0 1\n  movi 1 2 3\n");
+
+        assert_eq!(
+            result,
+            Err(error(
+                2,
+                3,
+                AssembleErrorKind::UnknownOperation("mnemonic", "movi".into())
+            ))
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, column 3: unknown mnemonic \"movi\""
+        );
+    }
+
+    #[test]
+    fn truncated_line_is_a_missing_operand() {
+        let result = assemble::<Mnemonic>("#ip 1\nseti 5 0");
+
+        assert_eq!(result, Err(error(2, 9, AssembleErrorKind::MissingOperand)));
+    }
+
+    #[test]
+    fn short_line_does_not_panic() {
+        let result = assemble::<Mnemonic>("ad");
+
+        assert_eq!(
+            result,
+            Err(error(
+                1,
+                1,
+                AssembleErrorKind::UnknownOperation("mnemonic", "ad".into())
+            ))
+        );
+    }
+
+    #[test]
+    fn invalid_operand() {
+        let result = assemble::<OpCode>("9 2 x1 2");
+
+        assert_eq!(
+            result,
+            Err(error(1, 5, AssembleErrorKind::InvalidOperand("x1".into())))
+        );
+    }
+
+    #[test]
+    fn too_many_operands() {
+        let result = assemble::<OpCode>("9 2 1 2 7");
+
+        assert_eq!(
+            result,
+            Err(error(1, 9, AssembleErrorKind::UnexpectedToken("7".into())))
+        );
+    }
+
+    #[test]
+    fn opcode_out_of_range() {
+        let result = assemble::<OpCode>("256 2 1 2");
+
+        assert_eq!(
+            result,
+            Err(error(
+                1,
+                1,
+                AssembleErrorKind::UnknownOperation("opcode", "256".into())
+            ))
+        );
+    }
+
+    #[test]
+    fn undefined_label() {
+        let result = assemble::<Mnemonic>("start: seti 0 0 0\nseti @stop 0 3");
+
+        assert_eq!(
+            result,
+            Err(error(
+                2,
+                6,
+                AssembleErrorKind::UndefinedLabel("stop".into())
+            ))
+        );
+    }
+
+    #[test]
+    fn duplicate_label() {
+        let result = assemble::<Mnemonic>("a: seti 0 0 0\n a: seti 0 0 0");
+
+        assert_eq!(
+            result,
+            Err(error(2, 2, AssembleErrorKind::DuplicateLabel("a".into())))
+        );
+    }
+
+    #[test]
+    fn label_before_address_zero() {
+        let result = assemble::<Mnemonic>("start: seti @start-1 0 0");
+
+        assert_eq!(
+            result,
+            Err(error(
+                1,
+                13,
+                AssembleErrorKind::InvalidOperand("@start-1".into())
+            ))
+        );
+    }
+
+    #[test]
+    fn invalid_directive() {
+        let result = assemble::<Mnemonic>("#pc 3");
+
+        assert_eq!(
+            result,
+            Err(error(
+                1,
+                1,
+                AssembleErrorKind::InvalidDirective("#pc".into())
+            ))
+        );
+    }
+
+    #[test]
+    fn duplicate_ip_declaration() {
+        let result = assemble::<Mnemonic>("#ip 3\n#ip 2");
+
+        assert_eq!(
+            result,
+            Err(error(2, 1, AssembleErrorKind::DuplicateIpDeclaration))
+        );
+    }
+
+    #[test]
+    fn program_requires_ip_declaration() {
+        let result = "seti 5 0 1".parse::<Program>();
+
+        assert_eq!(
+            result.unwrap_err(),
+            error(1, 1, AssembleErrorKind::MissingIpDeclaration)
+        );
+    }
+
+    #[test]
+    fn single_instruction_rejects_label() {
+        let result = "  loop: addi 1 2 3".parse::<Instruction<Mnemonic>>();
+
+        assert_eq!(
+            result,
+            Err(error(
+                1,
+                3,
+                AssembleErrorKind::UnexpectedToken("loop:".into())
+            ))
+        );
+    }
+}
+
+mod translate {
+    use super::*;
+
+    #[test]
+    fn encode_and_decode() {
+        let assembly = assemble::<Mnemonic>("#ip 2\nseti 5 0 1\nmulr 1 1 3\n").unwrap();
+
+        let encoded = assembly.encode(&opcode_table()).unwrap();
+
+        assert_eq!(encoded.to_string(), "#ip 2\n9 5 0 1\n2 1 1 3\n");
+        assert_eq!(encoded.decode(&opcode_table()), Ok(assembly));
+    }
+
+    #[test]
+    fn unsupported_mnemonic() {
+        let assembly = assemble::<Mnemonic>("seti 5 0 1\nmulr 1 1 3\n").unwrap();
+        let opcodes = vec![(OpCode(0), SetI)].into_iter().collect::<OpCodeTable>();
+
+        let result = assembly.encode(&opcodes);
+
+        assert_eq!(
+            result,
+            Err(TranslateError::UnsupportedMnemonic {
+                addr: 1,
+                mnemonic: MulR,
+            })
+        );
+    }
+
+    #[test]
+    fn unsupported_opcode() {
+        let assembly = assemble::<OpCode>("0 5 0 1\n7 1 1 3\n").unwrap();
+        let opcodes = vec![(OpCode(0), SetI)].into_iter().collect::<OpCodeTable>();
+
+        let result = assembly.decode(&opcodes);
+
+        assert_eq!(
+            result,
+            Err(TranslateError::UnsupportedOpCode {
+                addr: 1,
+                opcode: OpCode(7),
+            })
+        );
+    }
+}
+
+mod round_trip {
+    use super::*;
+
+    use proptest::{collection::vec, option};
+
+    #[test]
+    fn day19_program() {
+        let program = DAY19_INPUT.parse::<Program>().unwrap();
+
+        let reparsed = program.to_string().parse::<Program>();
+
+        assert_eq!(reparsed, Ok(program));
+    }
+
+    #[test]
+    fn single_instruction() {
+        let instruction = Instruction::<OpCode>::new(OpCode(12), 3, 0, 1);
+
+        assert_eq!(instruction.to_string().parse(), Ok(instruction));
+    }
+
+    proptest! {
+        #[test]
+        fn printed_assembly_parses_to_same_assembly(
+            ip_reg in option::of(0..6 as Addr),
+            raw in vec((0..16usize, 0..100 as Data, 0..100 as Data, 0..6 as Data), 0..20),
+        ) {
+            let instructions = raw
+                .into_iter()
+                .map(|(op, a, b, c)| Instruction::new(INSTRUCTION_SET[op], a, b, c))
+                .collect();
+            let assembly = Assembly { ip_reg, instructions };
+
+            let mnemonics = assembly.to_string().parse::<Assembly<Mnemonic>>();
+            let encoded = assembly.encode(&opcode_table()).unwrap();
+            let opcodes = encoded.to_string().parse::<Assembly<OpCode>>();
+
+            prop_assert_eq!(mnemonics, Ok(assembly));
+            prop_assert_eq!(opcodes, Ok(encoded));
+        }
+    }
+}
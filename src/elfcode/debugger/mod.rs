@@ -0,0 +1,275 @@
+//! Runs programs under control of breakpoints, an instruction budget and a
+//! trace callback.
+//!
+//! The [`Debugger`] wraps an [`Interpreter`] and counts how often the
+//! instruction at each address is executed. The addresses with the highest
+//! counts are the hot loops worth optimizing.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Debug, Display},
+};
+
+use super::{Addr, ExecutionError, Instruction, InstructionSet, Interpreter, Register};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugError {
+    Execution(ExecutionError),
+    /// The program would have executed more instructions than the budget
+    /// allows.
+    BudgetExceeded(u64),
+}
+
+impl Display for DebugError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DebugError::Execution(err) => write!(f, "{}", err),
+            DebugError::BudgetExceeded(budget) => {
+                write!(f, "instruction budget of {} exceeded", budget)
+            },
+        }
+    }
+}
+
+impl Error for DebugError {}
+
+impl From<ExecutionError> for DebugError {
+    fn from(err: ExecutionError) -> Self {
+        DebugError::Execution(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BreakpointId(usize);
+
+enum Breakpoint<'a, const N: usize> {
+    /// Stops before the instruction at the address is executed.
+    Ip(Addr),
+    /// Stops after an instruction has been executed if the predicate holds.
+    Register(Box<dyn Fn(&Register<N>) -> bool + 'a>),
+}
+
+impl<'a, const N: usize> Debug for Breakpoint<'a, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Breakpoint::Ip(ip) => f.debug_tuple("Ip").field(ip).finish(),
+            Breakpoint::Register(_) => f.write_str("Register(..)"),
+        }
+    }
+}
+
+/// Why a run of the debugger stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    Breakpoint(BreakpointId),
+}
+
+type Trace<'a, O, const N: usize> = Box<dyn FnMut(Addr, Instruction<O>, &Register<N>) + 'a>;
+
+pub struct Debugger<'a, S, const N: usize>
+where
+    S: InstructionSet,
+{
+    interpreter: Interpreter<S, N>,
+    breakpoints: Vec<Option<Breakpoint<'a, N>>>,
+    budget: Option<u64>,
+    trace: Option<Trace<'a, S::OpCode, N>>,
+    executed: u64,
+    hit_counts: HashMap<Addr, u64>,
+    stopped_at: Option<Addr>,
+}
+
+impl<'a, S, const N: usize> Debug for Debugger<'a, S, N>
+where
+    S: InstructionSet + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Debugger")
+            .field("interpreter", &self.interpreter)
+            .field("breakpoints", &self.breakpoints)
+            .field("budget", &self.budget)
+            .field("executed", &self.executed)
+            .field("hit_counts", &self.hit_counts)
+            .finish()
+    }
+}
+
+impl<'a, S, const N: usize> Debugger<'a, S, N>
+where
+    S: InstructionSet,
+{
+    pub fn new(interpreter: Interpreter<S, N>) -> Self {
+        Self {
+            interpreter,
+            breakpoints: Vec::new(),
+            budget: None,
+            trace: None,
+            executed: 0,
+            hit_counts: HashMap::new(),
+            stopped_at: None,
+        }
+    }
+
+    /// Limits the total number of instructions the debugger executes.
+    pub fn with_budget(mut self, max_instructions: u64) -> Self {
+        self.budget = Some(max_instructions);
+        self
+    }
+
+    /// Calls the callback after each executed instruction with its address,
+    /// the instruction and the registers after executing it.
+    pub fn with_trace(
+        mut self,
+        trace: impl FnMut(Addr, Instruction<S::OpCode>, &Register<N>) + 'a,
+    ) -> Self {
+        self.trace = Some(Box::new(trace));
+        self
+    }
+
+    pub fn interpreter(&self) -> &Interpreter<S, N> {
+        &self.interpreter
+    }
+
+    pub fn into_interpreter(self) -> Interpreter<S, N> {
+        self.interpreter
+    }
+
+    pub fn ip(&self) -> Addr {
+        self.interpreter.ip()
+    }
+
+    /// Stops before the instruction at the given address is executed.
+    pub fn break_at(&mut self, ip: Addr) -> BreakpointId {
+        self.add_breakpoint(Breakpoint::Ip(ip))
+    }
+
+    /// Stops after an instruction has been executed if the predicate holds
+    /// for the registers.
+    pub fn break_when(&mut self, predicate: impl Fn(&Register<N>) -> bool + 'a) -> BreakpointId {
+        self.add_breakpoint(Breakpoint::Register(Box::new(predicate)))
+    }
+
+    fn add_breakpoint(&mut self, breakpoint: Breakpoint<'a, N>) -> BreakpointId {
+        self.breakpoints.push(Some(breakpoint));
+        BreakpointId(self.breakpoints.len() - 1)
+    }
+
+    /// Removes a breakpoint. Returns `false` if it has already been removed.
+    pub fn remove_breakpoint(&mut self, id: BreakpointId) -> bool {
+        self.breakpoints
+            .get_mut(id.0)
+            .and_then(Option::take)
+            .is_some()
+    }
+
+    /// Total number of instructions executed so far.
+    pub fn executed(&self) -> u64 {
+        self.executed
+    }
+
+    /// How often the instruction at the given address has been executed.
+    pub fn hit_count(&self, ip: Addr) -> u64 {
+        self.hit_counts.get(&ip).copied().unwrap_or(0)
+    }
+
+    /// The hit counts of all executed addresses, ordered by address.
+    pub fn hit_counts(&self) -> Vec<(Addr, u64)> {
+        let mut hit_counts = self
+            .hit_counts
+            .iter()
+            .map(|(&ip, &count)| (ip, count))
+            .collect::<Vec<_>>();
+        hit_counts.sort_unstable();
+        hit_counts
+    }
+
+    /// The hit counts of all executed addresses, most executed first.
+    pub fn hot_spots(&self) -> Vec<(Addr, u64)> {
+        let mut hot_spots = self.hit_counts();
+        hot_spots.sort_by(|(ip1, count1), (ip2, count2)| count2.cmp(count1).then(ip1.cmp(ip2)));
+        hot_spots
+    }
+
+    /// Executes the next instruction, ignoring breakpoints. Returns `false` if
+    /// the program has halted.
+    pub fn step(
+        &mut self,
+        program: &[Instruction<S::OpCode>],
+        register: &mut Register<N>,
+    ) -> Result<bool, DebugError> {
+        let ip = self.interpreter.ip();
+        let instruction = match program.get(ip as usize) {
+            Some(&instruction) => instruction,
+            None => return Ok(false),
+        };
+        if let Some(budget) = self.budget {
+            if self.executed >= budget {
+                return Err(DebugError::BudgetExceeded(budget));
+            }
+        }
+        self.interpreter.execute(instruction, register)?;
+        self.executed += 1;
+        *self.hit_counts.entry(ip).or_insert(0) += 1;
+        if let Some(trace) = &mut self.trace {
+            trace(ip, instruction, register);
+        }
+        Ok(true)
+    }
+
+    /// Runs the program until it halts or a breakpoint is hit.
+    ///
+    /// When called again after stopping at an address breakpoint, execution
+    /// continues with the instruction at that address.
+    pub fn run(
+        &mut self,
+        program: &[Instruction<S::OpCode>],
+        register: &mut Register<N>,
+    ) -> Result<Stop, DebugError> {
+        let mut resumed_at = self.stopped_at.take();
+        loop {
+            let ip = self.interpreter.ip();
+            if resumed_at.take() != Some(ip) {
+                if let Some(id) = self.ip_breakpoint(ip) {
+                    self.stopped_at = Some(ip);
+                    return Ok(Stop::Breakpoint(id));
+                }
+            }
+            if !self.step(program, register)? {
+                return Ok(Stop::Halted);
+            }
+            if let Some(id) = self.register_breakpoint(register) {
+                return Ok(Stop::Breakpoint(id));
+            }
+        }
+    }
+
+    fn ip_breakpoint(&self, ip: Addr) -> Option<BreakpointId> {
+        self.breakpoints
+            .iter()
+            .position(|breakpoint| matches!(breakpoint, Some(Breakpoint::Ip(addr)) if *addr == ip))
+            .map(BreakpointId)
+    }
+
+    fn register_breakpoint(&self, register: &Register<N>) -> Option<BreakpointId> {
+        self.breakpoints
+            .iter()
+            .position(|breakpoint| match breakpoint {
+                Some(Breakpoint::Register(predicate)) => predicate(register),
+                _ => false,
+            })
+            .map(BreakpointId)
+    }
+}
+
+/// A trace callback that logs each executed instruction at trace level.
+pub fn log_trace<O, const N: usize>(ip: Addr, instruction: Instruction<O>, register: &Register<N>)
+where
+    O: Display,
+{
+    trace!("{:02}: {} : {}", ip, instruction, register);
+}
+
+#[cfg(test)]
+mod tests;
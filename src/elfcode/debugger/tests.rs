@@ -0,0 +1,195 @@
+use super::*;
+use crate::{
+    day19,
+    elfcode::{Mnemonic, Mnemonics},
+};
+
+use std::{cell::RefCell, rc::Rc};
+
+const DAY19_INPUT: &str = include_str!("../../../input/2018/day19.txt");
+
+const EXAMPLE1_INPUT: &str = "\
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+";
+
+fn debugger<'a>(program: &day19::Program) -> Debugger<'a, Mnemonics, 6> {
+    Debugger::new(Interpreter::with_ip_binding(Mnemonics, program.ip_reg()))
+}
+
+mod run {
+    use super::*;
+
+    #[test]
+    fn example1_without_breakpoints() {
+        let program = day19::parse(EXAMPLE1_INPUT).unwrap();
+        let mut debugger = debugger(&program);
+        let mut register = Register::default();
+
+        let stop = debugger.run(program.instructions(), &mut register);
+
+        assert_eq!(stop, Ok(Stop::Halted));
+        assert_eq!(register, Register::from([6, 5, 6, 0, 0, 9]));
+        assert_eq!(debugger.executed(), 5);
+    }
+
+    #[test]
+    fn stops_at_ip_breakpoint_and_resumes() {
+        let program = day19::parse(EXAMPLE1_INPUT).unwrap();
+        let mut debugger = debugger(&program);
+        let breakpoint = debugger.break_at(4);
+        let mut register = Register::default();
+
+        let first = debugger.run(program.instructions(), &mut register);
+        let registers_at_breakpoint = register;
+        let ip_at_breakpoint = debugger.ip();
+        let second = debugger.run(program.instructions(), &mut register);
+
+        assert_eq!(first, Ok(Stop::Breakpoint(breakpoint)));
+        assert_eq!(registers_at_breakpoint, Register::from([3, 5, 6, 0, 0, 0]));
+        assert_eq!(ip_at_breakpoint, 4);
+        assert_eq!(second, Ok(Stop::Halted));
+    }
+
+    #[test]
+    fn stops_at_ip_breakpoint_of_first_instruction() {
+        let program = day19::parse(EXAMPLE1_INPUT).unwrap();
+        let mut debugger = debugger(&program);
+        let breakpoint = debugger.break_at(0);
+        let mut register = Register::default();
+
+        let stop = debugger.run(program.instructions(), &mut register);
+
+        assert_eq!(stop, Ok(Stop::Breakpoint(breakpoint)));
+        assert_eq!(debugger.executed(), 0);
+    }
+
+    #[test]
+    fn stops_when_register_predicate_holds() {
+        let program = day19::parse(DAY19_INPUT).unwrap();
+        let mut debugger = debugger(&program);
+        let breakpoint = debugger.break_when(|register| register[0] > 0);
+        let mut register = Register::default();
+
+        let stop = debugger.run(program.instructions(), &mut register);
+
+        assert_eq!(stop, Ok(Stop::Breakpoint(breakpoint)));
+        assert_eq!(register[0], 1);
+    }
+
+    #[test]
+    fn removed_breakpoint_does_not_stop() {
+        let program = day19::parse(EXAMPLE1_INPUT).unwrap();
+        let mut debugger = debugger(&program);
+        let breakpoint = debugger.break_at(4);
+        let mut register = Register::default();
+
+        let removed = debugger.remove_breakpoint(breakpoint);
+        let stop = debugger.run(program.instructions(), &mut register);
+
+        assert!(removed);
+        assert!(!debugger.remove_breakpoint(breakpoint));
+        assert_eq!(stop, Ok(Stop::Halted));
+    }
+
+    #[test]
+    fn exceeding_the_budget_is_an_error() {
+        let program = day19::parse(DAY19_INPUT).unwrap();
+        let mut debugger = debugger(&program).with_budget(1000);
+        let mut register = Register::default();
+
+        let result = debugger.run(program.instructions(), &mut register);
+
+        assert_eq!(result, Err(DebugError::BudgetExceeded(1000)));
+        assert_eq!(debugger.executed(), 1000);
+    }
+
+    #[test]
+    fn execution_error_is_reported() {
+        let program = day19::Program::new(7, vec![Instruction::new(Mnemonic::SetI, 1, 0, 0)]);
+        let mut debugger = debugger(&program);
+        let mut register = Register::default();
+
+        let result = debugger.run(program.instructions(), &mut register);
+
+        assert_eq!(
+            result,
+            Err(DebugError::Execution(ExecutionError::InvalidRegister {
+                register: 7,
+                num_registers: 6,
+            }))
+        );
+    }
+}
+
+mod trace {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let program = day19::parse(EXAMPLE1_INPUT).unwrap();
+        let trace = Rc::new(RefCell::new(Vec::new()));
+        let trace_sink = Rc::clone(&trace);
+        let mut debugger = debugger(&program).with_trace(move |ip, instruction, register| {
+            trace_sink
+                .borrow_mut()
+                .push(format!("{} {} {}", ip, instruction, register))
+        });
+        let mut register = Register::default();
+
+        debugger.run(program.instructions(), &mut register).unwrap();
+
+        assert_eq!(
+            *trace.borrow(),
+            vec![
+                "0 seti 5 0 1 [0, 5, 0, 0, 0, 0]",
+                "1 seti 6 0 2 [1, 5, 6, 0, 0, 0]",
+                "2 addi 0 1 0 [3, 5, 6, 0, 0, 0]",
+                "4 setr 1 0 0 [5, 5, 6, 0, 0, 0]",
+                "6 seti 9 0 5 [6, 5, 6, 0, 0, 9]",
+            ]
+        );
+    }
+}
+
+mod hit_counts {
+    use super::*;
+
+    #[test]
+    fn example1() {
+        let program = day19::parse(EXAMPLE1_INPUT).unwrap();
+        let mut debugger = debugger(&program);
+        let mut register = Register::default();
+
+        debugger.run(program.instructions(), &mut register).unwrap();
+
+        assert_eq!(
+            debugger.hit_counts(),
+            vec![(0, 1), (1, 1), (2, 1), (4, 1), (6, 1)]
+        );
+        assert_eq!(debugger.hit_count(3), 0);
+    }
+
+    #[test]
+    fn hot_spots_of_day19_input_are_the_inner_loop() {
+        let program = day19::parse(DAY19_INPUT).unwrap();
+        let mut debugger = debugger(&program).with_budget(100_000);
+        let mut register = Register::default();
+
+        let _ = debugger.run(program.instructions(), &mut register);
+        let hottest = debugger
+            .hot_spots()
+            .into_iter()
+            .take(6)
+            .map(|(ip, _)| ip)
+            .collect::<Vec<_>>();
+
+        assert_eq!(hottest, vec![3, 4, 5, 8, 9, 10]);
+    }
+}
@@ -0,0 +1,462 @@
+//! Turns `#ip` programs into structured pseudocode.
+//!
+//! Writes to the instruction pointer register become jumps to labels. A
+//! comparison followed by a relative jump over the next instruction becomes
+//! an `if` statement or a conditional jump, and a jump back to an earlier
+//! instruction becomes a loop as long as no other jump enters the loop from
+//! outside. Reads of the instruction pointer register are replaced by the
+//! address of the instruction.
+//!
+//! Registers that only hold the result of a comparison are folded into the
+//! condition, so the pseudocode does not show that they are overwritten.
+//! It is meant as a starting point for analyzing a program, not as an exact
+//! translation.
+//!
+//! The pseudocode of the example program of day 19 is:
+//!
+//! ```text
+//! #ip 0
+//! 0: r1 = 5
+//! 1: r2 = 6
+//! 2: goto L4
+//! 3: r3 = r1 + r2
+//!    L4:
+//! 4: goto r1 + 1
+//! 5: r4 = 8
+//! 6: r5 = 9
+//! ```
+//!
+//! Each line starts with the address of the first instruction of the
+//! statement.
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Write},
+};
+
+use super::{Addr, Data, Instruction, Mnemonic, Program};
+
+use self::Mnemonic::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operand {
+    Reg(Data),
+    Imm(Data),
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Reg(reg) => write!(f, "r{}", reg),
+            Operand::Imm(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    Add,
+    Mul,
+    And,
+    Or,
+    Gt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Display for BinOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            BinOp::Add => "+",
+            BinOp::Mul => "*",
+            BinOp::And => "&",
+            BinOp::Or => "|",
+            BinOp::Gt => ">",
+            BinOp::Le => "<=",
+            BinOp::Eq => "==",
+            BinOp::Ne => "!=",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Expr {
+    Value(Operand),
+    Binary(Operand, BinOp, Operand),
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Value(operand) => write!(f, "{}", operand),
+            Expr::Binary(lhs, op, rhs) => write!(f, "{} {} {}", lhs, op, rhs),
+        }
+    }
+}
+
+impl Expr {
+    /// The value of the expression if it does not depend on any register.
+    fn constant(self) -> Option<Data> {
+        match self {
+            Expr::Value(Operand::Imm(value)) => Some(value),
+            Expr::Binary(Operand::Imm(lhs), op, Operand::Imm(rhs)) => match op {
+                BinOp::Add => lhs.checked_add(rhs),
+                BinOp::Mul => lhs.checked_mul(rhs),
+                BinOp::And => Some(lhs & rhs),
+                BinOp::Or => Some(lhs | rhs),
+                BinOp::Gt => Some(Data::from(lhs > rhs)),
+                BinOp::Le => Some(Data::from(lhs <= rhs)),
+                BinOp::Eq => Some(Data::from(lhs == rhs)),
+                BinOp::Ne => Some(Data::from(lhs != rhs)),
+            },
+            _ => None,
+        }
+    }
+
+    fn is_comparison(self) -> bool {
+        match self {
+            Expr::Binary(_, op, _) => [BinOp::Gt, BinOp::Le, BinOp::Eq, BinOp::Ne].contains(&op),
+            Expr::Value(_) => false,
+        }
+    }
+
+    fn negate(self) -> Self {
+        match self {
+            Expr::Binary(lhs, BinOp::Gt, rhs) => Expr::Binary(lhs, BinOp::Le, rhs),
+            Expr::Binary(lhs, BinOp::Le, rhs) => Expr::Binary(lhs, BinOp::Gt, rhs),
+            Expr::Binary(lhs, BinOp::Eq, rhs) => Expr::Binary(lhs, BinOp::Ne, rhs),
+            Expr::Binary(lhs, BinOp::Ne, rhs) => Expr::Binary(lhs, BinOp::Eq, rhs),
+            _ => self,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Target {
+    Label(Addr),
+    Halt,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stmt {
+    Assign(Data, Expr),
+    Goto(Target),
+    /// Jumps to the address following the value of the expression.
+    ComputedGoto(Expr),
+    CondGoto(Expr, Target),
+    If(Expr, Vec<Node>),
+    /// A loop with an optional condition checked at its end. The address is
+    /// the one of the jump back to the start of the loop.
+    Loop(Vec<Node>, Option<Expr>, Addr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    /// Address of the first instruction of the statement.
+    addr: Addr,
+    /// Address of the last instruction of the statement.
+    end: Addr,
+    stmt: Stmt,
+}
+
+impl Node {
+    fn jumps(&self, jumps: &mut Vec<(Addr, Addr)>) {
+        match &self.stmt {
+            Stmt::Goto(Target::Label(target)) | Stmt::CondGoto(_, Target::Label(target)) => {
+                jumps.push((self.addr, *target))
+            },
+            Stmt::If(_, body) | Stmt::Loop(body, _, _) => {
+                body.iter().for_each(|node| node.jumps(jumps))
+            },
+            _ => {},
+        }
+    }
+
+    /// The target of a jump back to the given address.
+    fn back_edge(&self, start: Addr) -> Option<Option<Expr>> {
+        match self.stmt {
+            Stmt::Goto(Target::Label(target)) if target == start => Some(None),
+            Stmt::CondGoto(cond, Target::Label(target)) if target == start => Some(Some(cond)),
+            _ => None,
+        }
+    }
+}
+
+struct Decompiler<'a> {
+    program: &'a Program,
+}
+
+impl<'a> Decompiler<'a> {
+    fn operand_reg(&self, reg: Data, addr: Addr) -> Operand {
+        if reg == self.program.ip_reg() {
+            Operand::Imm(addr)
+        } else {
+            Operand::Reg(reg)
+        }
+    }
+
+    fn expr(&self, instruction: Instruction<Mnemonic>, addr: Addr) -> Expr {
+        let Instruction { opcode, a, b, .. } = instruction;
+        let reg = |reg| self.operand_reg(reg, addr);
+        let imm = Operand::Imm;
+        let expr = match opcode {
+            AddR => Expr::Binary(reg(a), BinOp::Add, reg(b)),
+            AddI => Expr::Binary(reg(a), BinOp::Add, imm(b)),
+            MulR => Expr::Binary(reg(a), BinOp::Mul, reg(b)),
+            MulI => Expr::Binary(reg(a), BinOp::Mul, imm(b)),
+            BanR => Expr::Binary(reg(a), BinOp::And, reg(b)),
+            BanI => Expr::Binary(reg(a), BinOp::And, imm(b)),
+            BorR => Expr::Binary(reg(a), BinOp::Or, reg(b)),
+            BorI => Expr::Binary(reg(a), BinOp::Or, imm(b)),
+            SetR => Expr::Value(reg(a)),
+            SetI => Expr::Value(imm(a)),
+            GtIR => Expr::Binary(imm(a), BinOp::Gt, reg(b)),
+            GtRI => Expr::Binary(reg(a), BinOp::Gt, imm(b)),
+            GtRR => Expr::Binary(reg(a), BinOp::Gt, reg(b)),
+            EqIR => Expr::Binary(imm(a), BinOp::Eq, reg(b)),
+            EqRI => Expr::Binary(reg(a), BinOp::Eq, imm(b)),
+            EqRR => Expr::Binary(reg(a), BinOp::Eq, reg(b)),
+        };
+        expr.constant()
+            .map_or(expr, |value| Expr::Value(Operand::Imm(value)))
+    }
+
+    fn target(&self, ip: Data) -> Target {
+        match ip.checked_add(1) {
+            Some(next) if (next as usize) < self.program.instructions().len() => {
+                Target::Label(next)
+            },
+            _ => Target::Halt,
+        }
+    }
+
+    /// Translates the instruction at the given address on its own.
+    fn lower_single(&self, addr: Addr) -> Option<Node> {
+        let instruction = *self.program.instructions().get(addr as usize)?;
+        let expr = self.expr(instruction, addr);
+        let stmt = if instruction.c != self.program.ip_reg() {
+            Stmt::Assign(instruction.c, expr)
+        } else {
+            match expr.constant() {
+                Some(ip) => Stmt::Goto(self.target(ip)),
+                None => Stmt::ComputedGoto(expr),
+            }
+        };
+        Some(Node {
+            addr,
+            end: addr,
+            stmt,
+        })
+    }
+
+    /// Translates a comparison at the given address followed by a jump over
+    /// the next instruction if the comparison holds.
+    fn lower_conditional(&self, addr: Addr, targets: &HashSet<Addr>) -> Option<Node> {
+        let ip_reg = self.program.ip_reg();
+        let instructions = self.program.instructions();
+        let compare = *instructions.get(addr as usize)?;
+        let skip = *instructions.get(addr as usize + 1)?;
+        let cond = self.expr(compare, addr);
+        let temp = compare.c;
+        let is_skip = skip.opcode == AddR
+            && skip.c == ip_reg
+            && ((skip.a == temp && skip.b == ip_reg) || (skip.a == ip_reg && skip.b == temp));
+        if temp == ip_reg
+            || !cond.is_comparison()
+            || !is_skip
+            || targets.contains(&(addr + 1))
+            || targets.contains(&(addr + 2))
+        {
+            return None;
+        }
+        let otherwise = self.lower_single(addr + 2)?;
+        let (stmt, end) = match otherwise.stmt {
+            Stmt::Goto(Target::Label(target))
+                if target == addr + 4 && !targets.contains(&(addr + 3)) =>
+            {
+                let then = self.lower_single(addr + 3)?;
+                match then.stmt {
+                    Stmt::Goto(target) => (Stmt::CondGoto(cond, target), addr + 3),
+                    _ => (Stmt::If(cond, vec![then]), addr + 3),
+                }
+            },
+            Stmt::Goto(target) => (Stmt::CondGoto(cond.negate(), target), addr + 2),
+            _ => (Stmt::If(cond.negate(), vec![otherwise]), addr + 2),
+        };
+        Some(Node { addr, end, stmt })
+    }
+
+    fn lower(&self) -> Vec<Node> {
+        let len = self.program.instructions().len() as Addr;
+        let targets: HashSet<Addr> = (0..len)
+            .filter_map(|addr| match self.lower_single(addr)?.stmt {
+                Stmt::Goto(Target::Label(target)) => Some(target),
+                _ => None,
+            })
+            .collect();
+        let mut nodes = Vec::with_capacity(len as usize);
+        let mut addr = 0;
+        while addr < len {
+            let node = self
+                .lower_conditional(addr, &targets)
+                .or_else(|| self.lower_single(addr))
+                .expect("address within program");
+            addr = node.end + 1;
+            nodes.push(node);
+        }
+        nodes
+    }
+}
+
+/// Lifts jumps back to the start of a sequence of statements into loops.
+fn structure(nodes: Vec<Node>, jumps: &[(Addr, Addr)]) -> Vec<Node> {
+    let mut structured = Vec::with_capacity(nodes.len());
+    let mut nodes = nodes.into_iter().collect::<Vec<_>>();
+    while !nodes.is_empty() {
+        let start = nodes[0].addr;
+        let back_edge = (0..nodes.len()).rev().find_map(|idx| {
+            let cond = nodes[idx].back_edge(start)?;
+            let end = nodes[idx].end;
+            let entered_from_outside = jumps.iter().any(|&(source, target)| {
+                (source < start || source > end) && target > start && target <= end
+            });
+            if entered_from_outside {
+                None
+            } else {
+                Some((idx, cond))
+            }
+        });
+        match back_edge {
+            Some((idx, cond)) => {
+                let mut rest = nodes.split_off(idx);
+                let jump = rest.remove(0);
+                let body = structure(nodes, jumps);
+                structured.push(Node {
+                    addr: start,
+                    end: jump.end,
+                    stmt: Stmt::Loop(body, cond, jump.addr),
+                });
+                nodes = rest;
+            },
+            None => {
+                let rest = nodes.split_off(1);
+                structured.append(&mut nodes);
+                nodes = rest;
+            },
+        }
+    }
+    structured
+}
+
+struct Renderer {
+    labels: HashSet<Addr>,
+    width: usize,
+    output: String,
+}
+
+impl Renderer {
+    fn line(&mut self, addr: Option<Addr>, depth: usize, text: impl Display) {
+        match addr {
+            Some(addr) => write!(self.output, "{:>width$}: ", addr, width = self.width),
+            None => write!(self.output, "{:width$}  ", "", width = self.width),
+        }
+        .and_then(|()| writeln!(self.output, "{:indent$}{}", "", text, indent = 4 * depth))
+        .expect("writing to a string never fails");
+    }
+
+    fn label(&mut self, addr: Addr, depth: usize) {
+        if self.labels.contains(&addr) {
+            self.line(None, depth, format_args!("L{}:", addr));
+        }
+    }
+
+    fn render(&mut self, nodes: &[Node], depth: usize) {
+        for node in nodes {
+            self.label(node.addr, depth);
+            let addr = Some(node.addr);
+            match &node.stmt {
+                Stmt::Assign(reg, expr) => {
+                    self.line(addr, depth, format_args!("r{} = {}", reg, expr))
+                },
+                Stmt::Goto(target) => self.line(addr, depth, goto(*target)),
+                Stmt::ComputedGoto(expr) => {
+                    self.line(addr, depth, format_args!("goto {}", computed_target(*expr)))
+                },
+                Stmt::CondGoto(cond, target) => {
+                    self.line(addr, depth, format_args!("if {} {}", cond, goto(*target)))
+                },
+                Stmt::If(cond, body) => {
+                    self.line(addr, depth, format_args!("if {} {{", cond));
+                    self.render(body, depth + 1);
+                    self.line(None, depth, "}");
+                },
+                Stmt::Loop(body, cond, tail) => {
+                    match cond {
+                        Some(_) => self.line(None, depth, "do {"),
+                        None => self.line(None, depth, "loop {"),
+                    }
+                    self.render(body, depth + 1);
+                    self.label(*tail, depth + 1);
+                    match cond {
+                        Some(cond) => {
+                            self.line(Some(*tail), depth, format_args!("}} while {}", cond))
+                        },
+                        None => self.line(Some(*tail), depth, "}"),
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// The address following the value of the expression.
+///
+/// An immediate offset is folded into the `+ 1` unless that overflows, in
+/// which case the sum is kept as it is.
+fn computed_target(expr: Expr) -> String {
+    match expr {
+        Expr::Binary(Operand::Imm(offset), BinOp::Add, operand)
+        | Expr::Binary(operand, BinOp::Add, Operand::Imm(offset)) => match offset.checked_add(1) {
+            Some(next) => format!("{} + {}", next, operand),
+            None => format!("({}) + 1", expr),
+        },
+        Expr::Value(operand) => format!("{} + 1", operand),
+        _ => format!("({}) + 1", expr),
+    }
+}
+
+fn goto(target: Target) -> String {
+    match target {
+        Target::Label(addr) => format!("goto L{}", addr),
+        Target::Halt => "halt".to_string(),
+    }
+}
+
+/// Decompiles the program into structured pseudocode.
+pub fn decompile(program: &Program) -> String {
+    let decompiler = Decompiler { program };
+    let nodes = decompiler.lower();
+    let mut jumps = Vec::new();
+    nodes.iter().for_each(|node| node.jumps(&mut jumps));
+    let nodes = structure(nodes, &jumps);
+
+    let mut remaining_jumps = Vec::new();
+    nodes
+        .iter()
+        .for_each(|node| node.jumps(&mut remaining_jumps));
+    let last_addr = program.instructions().len().saturating_sub(1);
+    let mut renderer = Renderer {
+        labels: remaining_jumps
+            .into_iter()
+            .map(|(_, target)| target)
+            .collect(),
+        width: last_addr.to_string().len(),
+        output: format!("#ip {}\n", program.ip_reg()),
+    };
+    renderer.render(&nodes, 0);
+    renderer.output
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,194 @@
+use super::*;
+use crate::day19;
+
+const DAY19_INPUT: &str = include_str!("../../../input/2018/day19.txt");
+const DAY21_INPUT: &str = include_str!("../../../input/2018/day21.txt");
+
+const EXAMPLE1_INPUT: &str = "\
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+";
+
+#[test]
+fn example1() {
+    let program = day19::parse(EXAMPLE1_INPUT).unwrap();
+
+    let pseudocode = decompile(&program);
+
+    assert_eq!(
+        pseudocode,
+        "\
+#ip 0
+0: r1 = 5
+1: r2 = 6
+2: goto L4
+3: r3 = r1 + r2
+   L4:
+4: goto r1 + 1
+5: r4 = 8
+6: r5 = 9
+"
+    );
+}
+
+#[test]
+fn day19_input() {
+    let program = day19::parse(DAY19_INPUT).unwrap();
+
+    let pseudocode = decompile(&program);
+
+    assert_eq!(
+        pseudocode,
+        "\
+#ip 3
+ 0: goto L17
+    L1:
+ 1: r5 = 1
+    do {
+ 2:     r2 = 1
+        do {
+ 3:         r1 = r5 * r2
+ 4:         if r1 == r4 {
+ 7:             r0 = r5 + r0
+            }
+ 8:         r2 = r2 + 1
+ 9:     } while r2 <= r4
+12:     r5 = r5 + 1
+13: } while r5 <= r4
+16: halt
+    L17:
+17: r4 = r4 + 2
+18: r4 = r4 * r4
+19: r4 = 19 * r4
+20: r4 = r4 * 11
+21: r1 = r1 + 6
+22: r1 = r1 * 22
+23: r1 = r1 + 21
+24: r4 = r4 + r1
+25: goto 26 + r0
+26: goto L1
+27: r1 = 27
+28: r1 = r1 * 28
+29: r1 = 29 + r1
+30: r1 = 30 * r1
+31: r1 = r1 * 14
+32: r1 = r1 * 32
+33: r4 = r4 + r1
+34: r0 = 0
+35: goto L1
+"
+    );
+}
+
+#[test]
+fn day21_input() {
+    let program = day19::parse(DAY21_INPUT).unwrap();
+
+    let pseudocode = decompile(&program);
+
+    assert_eq!(
+        pseudocode,
+        "\
+#ip 1
+ 0: r4 = 123
+    do {
+ 1:     r4 = r4 & 456
+ 2: } while r4 != 72
+ 5: r4 = 0
+    do {
+ 6:     r3 = r4 | 65536
+ 7:     r4 = 16098955
+        loop {
+ 8:         r5 = r3 & 255
+ 9:         r4 = r4 + r5
+10:         r4 = r4 & 16777215
+11:         r4 = r4 * 65899
+12:         r4 = r4 & 16777215
+13:         if 256 > r3 goto L28
+17:         r5 = 0
+            loop {
+18:             r2 = r5 + 1
+19:             r2 = r2 * 256
+20:             if r2 > r3 goto L26
+24:             r5 = r5 + 1
+25:         }
+            L26:
+26:         r3 = r5
+27:     }
+        L28:
+28: } while r4 != r0
+"
+    );
+}
+
+#[test]
+fn compare_and_skip_assignment_becomes_negated_if() {
+    let program = Program::new(
+        2,
+        vec![
+            Instruction::new(GtRI, 0, 5, 1),
+            Instruction::new(AddR, 2, 1, 2),
+            Instruction::new(SetI, 7, 0, 0),
+            Instruction::new(AddI, 0, 1, 0),
+        ],
+    );
+
+    let pseudocode = decompile(&program);
+
+    assert_eq!(
+        pseudocode,
+        "\
+#ip 2
+0: if r0 <= 5 {
+2:     r0 = 7
+   }
+3: r0 = r0 + 1
+"
+    );
+}
+
+#[test]
+fn loop_entered_from_outside_is_not_lifted() {
+    let program = Program::new(
+        2,
+        vec![
+            Instruction::new(SetI, 1, 0, 2),
+            Instruction::new(AddI, 0, 1, 0),
+            Instruction::new(AddI, 1, 1, 1),
+            Instruction::new(SetI, 0, 0, 2),
+        ],
+    );
+
+    let pseudocode = decompile(&program);
+
+    assert_eq!(
+        pseudocode,
+        "\
+#ip 2
+0: goto L2
+   L1:
+1: r0 = r0 + 1
+   L2:
+2: r1 = r1 + 1
+3: goto L1
+"
+    );
+}
+
+#[test]
+fn computed_goto_with_largest_offset_does_not_overflow() {
+    let program = Program::new(0, vec![Instruction::new(AddI, 1, Data::MAX, 0)]);
+
+    let pseudocode = decompile(&program);
+
+    assert_eq!(
+        pseudocode,
+        format!("#ip 0\n0: goto (r1 + {}) + 1\n", Data::MAX)
+    );
+}
@@ -0,0 +1,555 @@
+//! # ElfCode
+//!
+//! The virtual machine of the wrist-mounted device as described in the
+//! puzzles of day 16, day 19 and day 21.
+//!
+//! A program is a list of instructions, each consisting of an opcode and
+//! three values `a`, `b` and `c`. The instructions operate on a fixed number
+//! of registers: four in the samples of day 16 and six from day 19 on.
+//!
+//! Day 16 programs refer to the operations by opcode numbers which have to
+//! be mapped to mnemonics by an [`OpCodeTable`] first. Day 19 programs name
+//! the operations directly and bind the instruction pointer to one of the
+//! registers using the `#ip` declaration. Both kinds of programs run on the
+//! same [`Interpreter`], which is generic over the [`InstructionSet`] and the
+//! number of registers.
+//!
+//! Accessing a register that does not exist results in an
+//! [`ExecutionError`] instead of a panic.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+    iter::FromIterator,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use self::{optimizer::Optimizations, Mnemonic::*};
+
+pub mod assembler;
+pub mod debugger;
+pub mod decompiler;
+pub mod optimizer;
+
+pub type Data = u64;
+
+pub type Addr = Data;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Mnemonic {
+    AddR,
+    AddI,
+    MulR,
+    MulI,
+    BanR,
+    BanI,
+    BorR,
+    BorI,
+    SetR,
+    SetI,
+    GtIR,
+    GtRI,
+    GtRR,
+    EqIR,
+    EqRI,
+    EqRR,
+}
+
+/// All operations supported by the device.
+pub const INSTRUCTION_SET: &[Mnemonic] = &[
+    AddR, AddI, MulR, MulI, BanR, BanI, BorR, BorI, SetR, SetI, GtIR, GtRI, GtRR, EqIR, EqRI, EqRR,
+];
+
+impl Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let display = match *self {
+            AddR => "addr",
+            AddI => "addi",
+            MulR => "mulr",
+            MulI => "muli",
+            BanR => "banr",
+            BanI => "bani",
+            BorR => "borr",
+            BorI => "bori",
+            SetR => "setr",
+            SetI => "seti",
+            GtIR => "gtir",
+            GtRI => "gtri",
+            GtRR => "gtrr",
+            EqIR => "eqir",
+            EqRI => "eqri",
+            EqRR => "eqrr",
+        };
+        f.write_str(display)
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "addr" => AddR,
+            "addi" => AddI,
+            "mulr" => MulR,
+            "muli" => MulI,
+            "banr" => BanR,
+            "bani" => BanI,
+            "borr" => BorR,
+            "bori" => BorI,
+            "setr" => SetR,
+            "seti" => SetI,
+            "gtir" => GtIR,
+            "gtri" => GtRI,
+            "gtrr" => GtRR,
+            "eqir" => EqIR,
+            "eqri" => EqRI,
+            "eqrr" => EqRR,
+            _ => return Err(format!("unknown opcode {:?}", s)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OpCode(pub u8);
+
+impl Display for OpCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionError {
+    /// An instruction refers to a register that does not exist.
+    InvalidRegister {
+        register: Data,
+        num_registers: usize,
+    },
+    /// The opcode of an instruction is not known to the instruction set.
+    UnsupportedOpCode(OpCode),
+    /// The result of an addition or multiplication does not fit into a
+    /// register.
+    Overflow(Instruction<Mnemonic>),
+}
+
+impl Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExecutionError::InvalidRegister {
+                register,
+                num_registers,
+            } => write!(
+                f,
+                "invalid register {}, the device has {} registers",
+                register, num_registers
+            ),
+            ExecutionError::UnsupportedOpCode(opcode) => {
+                write!(f, "unsupported opcode {}", opcode)
+            },
+            ExecutionError::Overflow(instruction) => {
+                write!(f, "arithmetic overflow in instruction {}", instruction)
+            },
+        }
+    }
+}
+
+impl Error for ExecutionError {}
+
+/// The `N` registers of the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Register<const N: usize>([Data; N]);
+
+impl<const N: usize> Display for Register<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("[")?;
+        for (idx, value) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl<const N: usize> Default for Register<N> {
+    fn default() -> Self {
+        Register([0; N])
+    }
+}
+
+impl<const N: usize> From<[Data; N]> for Register<N> {
+    fn from(value: [Data; N]) -> Self {
+        Register(value)
+    }
+}
+
+/// Unchecked access to a register. Panics if the register does not exist.
+impl<const N: usize> Index<Data> for Register<N> {
+    type Output = Data;
+
+    fn index(&self, index: Data) -> &Self::Output {
+        &self.0[index as usize]
+    }
+}
+
+impl<const N: usize> IndexMut<Data> for Register<N> {
+    fn index_mut(&mut self, index: Data) -> &mut <Self as Index<Data>>::Output {
+        &mut self.0[index as usize]
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> Serialize for Register<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> Deserialize<'de> for Register<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<Data>::deserialize(deserializer)?;
+        let len = values.len();
+        <[Data; N] as std::convert::TryFrom<_>>::try_from(values)
+            .map(Register)
+            .map_err(|_| de::Error::invalid_length(len, &format!("{} register values", N).as_str()))
+    }
+}
+
+impl<const N: usize> Register<N> {
+    pub const NUM_REGISTERS: usize = N;
+
+    pub fn values(&self) -> &[Data; N] {
+        &self.0
+    }
+
+    /// Returns the value of the given register.
+    pub fn get(&self, index: Data) -> Result<Data, ExecutionError> {
+        self.0
+            .get(index as usize)
+            .copied()
+            .ok_or(ExecutionError::InvalidRegister {
+                register: index,
+                num_registers: N,
+            })
+    }
+
+    /// Stores a value in the given register.
+    pub fn set(&mut self, index: Data, value: Data) -> Result<(), ExecutionError> {
+        let slot = self
+            .0
+            .get_mut(index as usize)
+            .ok_or(ExecutionError::InvalidRegister {
+                register: index,
+                num_registers: N,
+            })?;
+        *slot = value;
+        Ok(())
+    }
+}
+
+/// An instruction with an opcode of type `O`, which is either an [`OpCode`]
+/// number or a [`Mnemonic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Instruction<O> {
+    pub opcode: O,
+    pub a: Data,
+    pub b: Data,
+    pub c: Data,
+}
+
+impl<O> Display for Instruction<O>
+where
+    O: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.opcode, self.a, self.b, self.c)
+    }
+}
+
+impl<O> From<(O, Data, Data, Data)> for Instruction<O> {
+    fn from((opcode, a, b, c): (O, Data, Data, Data)) -> Self {
+        Self { opcode, a, b, c }
+    }
+}
+
+impl From<(u8, Data, Data, Data)> for Instruction<OpCode> {
+    fn from((opc, a, b, c): (u8, Data, Data, Data)) -> Self {
+        Self {
+            opcode: OpCode(opc),
+            a,
+            b,
+            c,
+        }
+    }
+}
+
+impl<O> Instruction<O> {
+    pub fn new(opcode: impl Into<O>, a: Data, b: Data, c: Data) -> Self {
+        Self {
+            opcode: opcode.into(),
+            a,
+            b,
+            c,
+        }
+    }
+
+    /// Returns this instruction with its opcode replaced by the given one.
+    pub fn with_opcode<P>(self, opcode: P) -> Instruction<P> {
+        Instruction {
+            opcode,
+            a: self.a,
+            b: self.b,
+            c: self.c,
+        }
+    }
+}
+
+/// Maps the opcodes of a program to the operations they perform.
+pub trait InstructionSet {
+    type OpCode: Copy;
+
+    fn decode(&self, opcode: Self::OpCode) -> Result<Mnemonic, ExecutionError>;
+}
+
+/// The instruction set of programs that name their operations by mnemonics.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Mnemonics;
+
+impl InstructionSet for Mnemonics {
+    type OpCode = Mnemonic;
+
+    #[inline]
+    fn decode(&self, opcode: Self::OpCode) -> Result<Mnemonic, ExecutionError> {
+        Ok(opcode)
+    }
+}
+
+/// The instruction set of programs that refer to their operations by opcode
+/// numbers.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OpCodeTable(HashMap<OpCode, Mnemonic>);
+
+impl OpCodeTable {
+    pub fn get(&self, opcode: OpCode) -> Option<Mnemonic> {
+        self.0.get(&opcode).copied()
+    }
+
+    /// Looks up the opcode of a mnemonic.
+    pub fn opcode(&self, mnemonic: Mnemonic) -> Option<OpCode> {
+        self.0
+            .iter()
+            .find(|(_, &candidate)| candidate == mnemonic)
+            .map(|(&opcode, _)| opcode)
+    }
+}
+
+impl From<HashMap<OpCode, Mnemonic>> for OpCodeTable {
+    fn from(opcodes: HashMap<OpCode, Mnemonic>) -> Self {
+        OpCodeTable(opcodes)
+    }
+}
+
+impl FromIterator<(OpCode, Mnemonic)> for OpCodeTable {
+    fn from_iter<T: IntoIterator<Item = (OpCode, Mnemonic)>>(iter: T) -> Self {
+        OpCodeTable(HashMap::from_iter(iter))
+    }
+}
+
+impl InstructionSet for OpCodeTable {
+    type OpCode = OpCode;
+
+    fn decode(&self, opcode: Self::OpCode) -> Result<Mnemonic, ExecutionError> {
+        self.get(opcode)
+            .ok_or(ExecutionError::UnsupportedOpCode(opcode))
+    }
+}
+
+/// Executes a single operation on the given registers without touching any
+/// instruction pointer.
+pub fn execute<const N: usize>(
+    instruction: Instruction<Mnemonic>,
+    reg: &mut Register<N>,
+) -> Result<(), ExecutionError> {
+    let Instruction { opcode, a, b, c } = instruction;
+    let overflow = || ExecutionError::Overflow(instruction);
+    let value = match opcode {
+        AddR => reg.get(a)?.checked_add(reg.get(b)?).ok_or_else(overflow)?,
+        AddI => reg.get(a)?.checked_add(b).ok_or_else(overflow)?,
+        MulR => reg.get(a)?.checked_mul(reg.get(b)?).ok_or_else(overflow)?,
+        MulI => reg.get(a)?.checked_mul(b).ok_or_else(overflow)?,
+        BanR => reg.get(a)? & reg.get(b)?,
+        BanI => reg.get(a)? & b,
+        BorR => reg.get(a)? | reg.get(b)?,
+        BorI => reg.get(a)? | b,
+        SetR => reg.get(a)?,
+        SetI => a,
+        GtIR => Data::from(a > reg.get(b)?),
+        GtRI => Data::from(reg.get(a)? > b),
+        GtRR => Data::from(reg.get(a)? > reg.get(b)?),
+        EqIR => Data::from(a == reg.get(b)?),
+        EqRI => Data::from(reg.get(a)? == b),
+        EqRR => Data::from(reg.get(a)? == reg.get(b)?),
+    };
+    reg.set(c, value)
+}
+
+/// Runs programs of the instruction set `S` on a device with `N` registers.
+///
+/// If the instruction pointer is bound to a register, its value is written to
+/// that register before each instruction is executed and read back
+/// afterwards. Either way the instruction pointer is incremented after each
+/// instruction and the program halts as soon as it points outside of the
+/// program.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Interpreter<S, const N: usize> {
+    instruction_set: S,
+    ip_reg: Option<Addr>,
+    ip: Addr,
+}
+
+impl<S, const N: usize> Interpreter<S, N>
+where
+    S: InstructionSet,
+{
+    /// Creates an interpreter that does not bind the instruction pointer to
+    /// any register.
+    pub fn new(instruction_set: S) -> Self {
+        Self {
+            instruction_set,
+            ip_reg: None,
+            ip: 0,
+        }
+    }
+
+    /// Creates an interpreter that binds the instruction pointer to the
+    /// given register.
+    pub fn with_ip_binding(instruction_set: S, ip_reg: Addr) -> Self {
+        Self {
+            instruction_set,
+            ip_reg: Some(ip_reg),
+            ip: 0,
+        }
+    }
+
+    pub fn instruction_set(&self) -> &S {
+        &self.instruction_set
+    }
+
+    pub fn ip_reg(&self) -> Option<Addr> {
+        self.ip_reg
+    }
+
+    pub fn ip(&self) -> Addr {
+        self.ip
+    }
+
+    /// Continues execution at the given address.
+    pub fn jump(&mut self, ip: Addr) {
+        self.ip = ip;
+    }
+
+    /// Executes the given instruction and advances the instruction pointer.
+    #[inline]
+    pub fn execute(
+        &mut self,
+        instruction: Instruction<S::OpCode>,
+        register: &mut Register<N>,
+    ) -> Result<(), ExecutionError> {
+        let mnemonic = self.instruction_set.decode(instruction.opcode)?;
+        if let Some(ip_reg) = self.ip_reg {
+            register.set(ip_reg, self.ip)?;
+        }
+        execute(instruction.with_opcode(mnemonic), register)?;
+        if let Some(ip_reg) = self.ip_reg {
+            self.ip = register.get(ip_reg)?;
+        }
+        // The largest address lies outside of every program, so saturating
+        // the instruction pointer there halts the program.
+        self.ip = self.ip.saturating_add(1);
+        Ok(())
+    }
+
+    /// Executes the instruction the instruction pointer points to. Returns
+    /// `false` if the program has halted.
+    pub fn step(
+        &mut self,
+        program: &[Instruction<S::OpCode>],
+        register: &mut Register<N>,
+    ) -> Result<bool, ExecutionError> {
+        match program.get(self.ip as usize) {
+            Some(&instruction) => self.execute(instruction, register).map(|()| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Runs the program until it halts.
+    pub fn run(
+        &mut self,
+        program: &[Instruction<S::OpCode>],
+        register: &mut Register<N>,
+    ) -> Result<(), ExecutionError> {
+        while self.step(program, register)? {}
+        Ok(())
+    }
+}
+
+/// A program that binds the instruction pointer to a register using the
+/// `#ip` declaration.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Program {
+    ip_reg: Addr,
+    instructions: Vec<Instruction<Mnemonic>>,
+}
+
+impl Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "#ip {}", self.ip_reg)?;
+        for instruction in &self.instructions {
+            writeln!(f, "{}", instruction)?;
+        }
+        Ok(())
+    }
+}
+
+impl Program {
+    pub fn new(
+        ip_reg: Addr,
+        instructions: impl IntoIterator<Item = Instruction<Mnemonic>>,
+    ) -> Self {
+        Self {
+            ip_reg,
+            instructions: Vec::from_iter(instructions),
+        }
+    }
+
+    pub fn ip_reg(&self) -> Addr {
+        self.ip_reg
+    }
+
+    pub fn instructions(&self) -> &[Instruction<Mnemonic>] {
+        &self.instructions
+    }
+
+    /// Analyzes the program for loops that can be executed natively.
+    pub fn optimizations(&self) -> Optimizations {
+        Optimizations::analyze(self.ip_reg, &self.instructions)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,359 @@
+//! Recognizes common loop idioms in `#ip` programs and replaces them with
+//! equivalent native operations.
+//!
+//! The analysis only looks at the instructions of a program. An idiom is
+//! identified by the shape of its instructions and the registers it uses,
+//! so it is found wherever it sits in the program and whichever registers it
+//! operates on. Executing an idiom natively leaves the registers and the
+//! instruction pointer in exactly the state the plain interpreter would
+//! leave them in after running the loop.
+
+use super::{Addr, Data, ExecutionError, Instruction, Interpreter, Mnemonic, Mnemonics, Register};
+
+use self::Mnemonic::*;
+
+/// A loop that can be executed natively.
+///
+/// The registers are named after their role in the loop. The instruction
+/// pointer is the register bound by the `#ip` declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Idiom {
+    /// Adds `factor` to `sum` if `factor` is a divisor of `target`, by trying
+    /// all values of `counter` up to `target`:
+    ///
+    /// ```text
+    /// 'L1:  temp = factor * counter
+    ///       if temp == target then
+    ///         sum = factor + sum
+    ///       end if
+    ///       counter = counter + 1
+    ///       if counter > target then
+    ///         goto 'L2
+    ///       else
+    ///         goto 'L1
+    ///       end if
+    /// 'L2:
+    /// ```
+    ///
+    /// The loop consists of nine instructions.
+    DivisorSum {
+        factor: Data,
+        counter: Data,
+        target: Data,
+        sum: Data,
+        temp: Data,
+    },
+    /// Divides `dividend` by the constant `divisor` by counting up
+    /// `quotient` until `(quotient + 1) * divisor` exceeds `dividend`:
+    ///
+    /// ```text
+    /// 'L1:  temp = quotient + 1
+    ///       temp = temp * divisor
+    ///       if temp > dividend then
+    ///         goto exit
+    ///       end if
+    ///       quotient = quotient + 1
+    ///       goto 'L1
+    /// ```
+    ///
+    /// The loop consists of eight instructions.
+    Division {
+        quotient: Data,
+        dividend: Data,
+        divisor: Data,
+        temp: Data,
+        exit: Addr,
+    },
+}
+
+impl Idiom {
+    /// Number of instructions the loop consists of.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        match self {
+            Idiom::DivisorSum { .. } => 9,
+            Idiom::Division { .. } => 8,
+        }
+    }
+
+    /// Tries to recognize an idiom starting at the given address.
+    pub fn recognize(program: &[Instruction<Mnemonic>], ip_reg: Addr, start: Addr) -> Option<Self> {
+        let code = program.get(start as usize..)?;
+        recognize_divisor_sum(code, ip_reg, start)
+            .or_else(|| recognize_division(code, ip_reg, start))
+    }
+
+    /// Executes the loop starting at `start` natively.
+    ///
+    /// Returns the address of the next instruction and the number of
+    /// instructions the plain interpreter would have executed. Returns `None`
+    /// if the loop can not be executed natively, e.g. because it refers to a
+    /// register that does not exist or a calculation overflows. The
+    /// registers are left untouched in that case.
+    pub fn execute<const N: usize>(
+        &self,
+        start: Addr,
+        ip_reg: Addr,
+        register: &mut Register<N>,
+    ) -> Option<(Addr, u64)> {
+        let mut reg = *register;
+        let result = match *self {
+            Idiom::DivisorSum {
+                factor,
+                counter,
+                target,
+                sum,
+                temp,
+            } => {
+                let factor_value = reg.get(factor).ok()?;
+                let first = reg.get(counter).ok()?;
+                let target_value = reg.get(target).ok()?;
+                let last = first.max(target_value);
+                factor_value.checked_mul(last)?;
+                if factor_value != 0
+                    && target_value % factor_value == 0
+                    && (first..=last).contains(&(target_value / factor_value))
+                {
+                    let sum_value = reg.get(sum).ok()?.checked_add(factor_value)?;
+                    reg.set(sum, sum_value).ok()?;
+                }
+                reg.set(counter, last.checked_add(1)?).ok()?;
+                reg.set(temp, 1).ok()?;
+                reg.set(ip_reg, start + 8).ok()?;
+                let iterations = last - first + 1;
+                (start + 9, 8 * iterations - 1)
+            },
+            Idiom::Division {
+                quotient,
+                dividend,
+                divisor,
+                temp,
+                exit,
+            } => {
+                let first = reg.get(quotient).ok()?;
+                let dividend_value = reg.get(dividend).ok()?;
+                let last = first.max(dividend_value / divisor);
+                last.checked_add(1)?.checked_mul(divisor)?;
+                reg.set(quotient, last).ok()?;
+                reg.set(temp, 1).ok()?;
+                reg.set(ip_reg, exit).ok()?;
+                let iterations = last - first + 1;
+                (exit.checked_add(1)?, 7 * (iterations - 1) + 5)
+            },
+        };
+        *register = reg;
+        Some(result)
+    }
+}
+
+fn distinct(registers: &[Data]) -> bool {
+    registers
+        .iter()
+        .enumerate()
+        .all(|(idx, reg)| !registers[idx + 1..].contains(reg))
+}
+
+/// Matches `opcode x y out` and `opcode y x out` for commutative operations.
+fn commutative(instruction: Instruction<Mnemonic>, x: Data, y: Data) -> bool {
+    (instruction.a == x && instruction.b == y) || (instruction.a == y && instruction.b == x)
+}
+
+fn recognize_divisor_sum(code: &[Instruction<Mnemonic>], ip: Addr, start: Addr) -> Option<Idiom> {
+    let code = code.get(..9)?;
+    let counter = code[5].a;
+    let (factor, temp) = match code[0] {
+        Instruction {
+            opcode: MulR,
+            a,
+            b,
+            c,
+        } if a == counter || b == counter => (if a == counter { b } else { a }, c),
+        _ => return None,
+    };
+    let target = match code[1] {
+        Instruction {
+            opcode: EqRR,
+            a,
+            b,
+            c,
+        } if c == temp && (a == temp || b == temp) => {
+            if a == temp {
+                b
+            } else {
+                a
+            }
+        },
+        _ => return None,
+    };
+    let sum = code[4].c;
+    let matches = code[2].opcode == AddR
+        && commutative(code[2], temp, ip)
+        && code[2].c == ip
+        && code[3] == Instruction::new(AddI, ip, 1, ip)
+        && code[4].opcode == AddR
+        && commutative(code[4], factor, sum)
+        && code[5] == Instruction::new(AddI, counter, 1, counter)
+        && code[6] == Instruction::new(GtRR, counter, target, temp)
+        && code[7].opcode == AddR
+        && commutative(code[7], temp, ip)
+        && code[7].c == ip
+        && code[8].opcode == SetI
+        && code[8].a.checked_add(1) == Some(start)
+        && code[8].c == ip
+        && distinct(&[factor, counter, target, sum, temp, ip]);
+    if matches {
+        Some(Idiom::DivisorSum {
+            factor,
+            counter,
+            target,
+            sum,
+            temp,
+        })
+    } else {
+        None
+    }
+}
+
+fn recognize_division(code: &[Instruction<Mnemonic>], ip: Addr, start: Addr) -> Option<Idiom> {
+    let code = code.get(..8)?;
+    let (quotient, temp) = match code[0] {
+        Instruction {
+            opcode: AddI,
+            a,
+            b: 1,
+            c,
+        } => (a, c),
+        _ => return None,
+    };
+    let divisor = code[1].b;
+    let dividend = code[2].b;
+    let exit = code[5].a;
+    let matches = code[1] == Instruction::new(MulI, temp, divisor, temp)
+        && divisor != 0
+        && code[2] == Instruction::new(GtRR, temp, dividend, temp)
+        && code[3].opcode == AddR
+        && commutative(code[3], temp, ip)
+        && code[3].c == ip
+        && code[4] == Instruction::new(AddI, ip, 1, ip)
+        && code[5].opcode == SetI
+        && code[5].c == ip
+        && code[6] == Instruction::new(AddI, quotient, 1, quotient)
+        && code[7].opcode == SetI
+        && code[7].a.checked_add(1) == Some(start)
+        && code[7].c == ip
+        && distinct(&[quotient, dividend, temp, ip]);
+    if matches {
+        Some(Idiom::Division {
+            quotient,
+            dividend,
+            divisor,
+            temp,
+            exit,
+        })
+    } else {
+        None
+    }
+}
+
+/// The idioms recognized in a program by their start address.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Optimizations {
+    ip_reg: Addr,
+    idioms: Vec<Option<Idiom>>,
+}
+
+impl Optimizations {
+    /// Analyzes a program whose instruction pointer is bound to `ip_reg`.
+    pub fn analyze(ip_reg: Addr, program: &[Instruction<Mnemonic>]) -> Self {
+        let mut idioms = vec![None; program.len()];
+        let mut addr = 0;
+        while addr < program.len() {
+            match Idiom::recognize(program, ip_reg, addr as Addr) {
+                Some(idiom) => {
+                    debug!("recognized {:?} at {}", idiom, addr);
+                    idioms[addr] = Some(idiom);
+                    addr += idiom.len();
+                },
+                None => addr += 1,
+            }
+        }
+        Self { ip_reg, idioms }
+    }
+
+    pub fn ip_reg(&self) -> Addr {
+        self.ip_reg
+    }
+
+    pub fn len(&self) -> usize {
+        self.idioms.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.idioms.iter().all(Option::is_none)
+    }
+
+    pub fn get(&self, start: Addr) -> Option<&Idiom> {
+        self.idioms.get(start as usize).and_then(Option::as_ref)
+    }
+
+    /// Returns the recognized idioms ordered by their start address.
+    pub fn iter(&self) -> impl Iterator<Item = (Addr, &Idiom)> {
+        self.idioms
+            .iter()
+            .enumerate()
+            .filter_map(|(start, idiom)| idiom.as_ref().map(|idiom| (start as Addr, idiom)))
+    }
+
+    /// Keeps only the idioms for which the predicate returns `true`.
+    pub fn retain(&mut self, mut predicate: impl FnMut(Addr, &Idiom) -> bool) {
+        for (start, slot) in self.idioms.iter_mut().enumerate() {
+            if slot.is_some_and(|idiom| !predicate(start as Addr, &idiom)) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Executes the next instruction, or the whole loop if an idiom starts at
+    /// the current address.
+    ///
+    /// Returns the number of instructions the plain interpreter would have
+    /// executed, or `None` if the program has halted.
+    pub fn step<const N: usize>(
+        &self,
+        interpreter: &mut Interpreter<Mnemonics, N>,
+        program: &[Instruction<Mnemonic>],
+        register: &mut Register<N>,
+    ) -> Result<Option<u64>, ExecutionError> {
+        let ip = interpreter.ip();
+        if interpreter.ip_reg() == Some(self.ip_reg) {
+            if let Some(idiom) = self.get(ip) {
+                if let Some((next_ip, executed)) = idiom.execute(ip, self.ip_reg, register) {
+                    interpreter.jump(next_ip);
+                    return Ok(Some(executed));
+                }
+            }
+        }
+        match interpreter.step(program, register)? {
+            true => Ok(Some(1)),
+            false => Ok(None),
+        }
+    }
+
+    /// Runs the program until it halts. Returns the number of instructions
+    /// the plain interpreter would have executed.
+    pub fn run<const N: usize>(
+        &self,
+        interpreter: &mut Interpreter<Mnemonics, N>,
+        program: &[Instruction<Mnemonic>],
+        register: &mut Register<N>,
+    ) -> Result<u64, ExecutionError> {
+        let mut executed = 0;
+        while let Some(count) = self.step(interpreter, program, register)? {
+            executed += count;
+        }
+        Ok(executed)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,219 @@
+use super::*;
+
+const DAY19_INPUT: &str = include_str!("../../../input/2018/day19.txt");
+const DAY21_INPUT: &str = include_str!("../../../input/2018/day21.txt");
+
+fn divisor_sum_program() -> Vec<Instruction<Mnemonic>> {
+    vec![
+        Instruction::new(AddI, 0, 0, 0),
+        Instruction::new(MulR, 5, 2, 1),
+        Instruction::new(EqRR, 1, 4, 1),
+        Instruction::new(AddR, 1, 3, 3),
+        Instruction::new(AddI, 3, 1, 3),
+        Instruction::new(AddR, 5, 0, 0),
+        Instruction::new(AddI, 2, 1, 2),
+        Instruction::new(GtRR, 2, 4, 1),
+        Instruction::new(AddR, 3, 1, 3),
+        Instruction::new(SetI, 0, 0, 3),
+    ]
+}
+
+fn division_program(divisor: Data) -> Vec<Instruction<Mnemonic>> {
+    vec![
+        Instruction::new(AddI, 0, 0, 0),
+        Instruction::new(AddI, 5, 1, 2),
+        Instruction::new(MulI, 2, divisor, 2),
+        Instruction::new(GtRR, 2, 3, 2),
+        Instruction::new(AddR, 2, 1, 1),
+        Instruction::new(AddI, 1, 1, 1),
+        Instruction::new(SetI, 8, 0, 1),
+        Instruction::new(AddI, 5, 1, 5),
+        Instruction::new(SetI, 0, 0, 1),
+        Instruction::new(SetR, 5, 0, 3),
+    ]
+}
+
+fn run_plain(
+    program: &[Instruction<Mnemonic>],
+    ip_reg: Addr,
+    mut register: Register<6>,
+) -> (Register<6>, Addr, u64) {
+    let mut interpreter = Interpreter::with_ip_binding(Mnemonics, ip_reg);
+    let mut executed = 0;
+    while interpreter.step(program, &mut register).unwrap() {
+        executed += 1;
+    }
+    (register, interpreter.ip(), executed)
+}
+
+fn run_optimized(
+    program: &[Instruction<Mnemonic>],
+    ip_reg: Addr,
+    mut register: Register<6>,
+) -> (Register<6>, Addr, u64) {
+    let optimizations = Optimizations::analyze(ip_reg, program);
+    let mut interpreter = Interpreter::with_ip_binding(Mnemonics, ip_reg);
+    let executed = optimizations
+        .run(&mut interpreter, program, &mut register)
+        .unwrap();
+    (register, interpreter.ip(), executed)
+}
+
+mod analyze {
+    use super::*;
+    use crate::day19;
+
+    #[test]
+    fn divisor_sum_in_day19_input() {
+        let program = day19::parse(DAY19_INPUT).unwrap();
+
+        let optimizations = Optimizations::analyze(program.ip_reg(), program.instructions());
+
+        assert_eq!(
+            optimizations.iter().collect::<Vec<_>>(),
+            vec![(
+                3,
+                &Idiom::DivisorSum {
+                    factor: 5,
+                    counter: 2,
+                    target: 4,
+                    sum: 0,
+                    temp: 1,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn division_in_day21_input() {
+        let program = day19::parse(DAY21_INPUT).unwrap();
+
+        let optimizations = Optimizations::analyze(program.ip_reg(), program.instructions());
+
+        assert_eq!(
+            optimizations.iter().collect::<Vec<_>>(),
+            vec![(
+                18,
+                &Idiom::Division {
+                    quotient: 5,
+                    dividend: 3,
+                    divisor: 256,
+                    temp: 2,
+                    exit: 25,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn divisor_sum_with_swapped_operands() {
+        let mut program = divisor_sum_program();
+        program[1] = Instruction::new(MulR, 2, 5, 1);
+        program[2] = Instruction::new(EqRR, 4, 1, 1);
+        program[5] = Instruction::new(AddR, 0, 5, 0);
+
+        let optimizations = Optimizations::analyze(3, &program);
+
+        assert_eq!(
+            optimizations.get(1),
+            Some(&Idiom::DivisorSum {
+                factor: 5,
+                counter: 2,
+                target: 4,
+                sum: 0,
+                temp: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn loop_using_ip_register_is_not_recognized() {
+        let program = divisor_sum_program();
+
+        let optimizations = Optimizations::analyze(4, &program);
+
+        assert!(optimizations.is_empty());
+    }
+
+    #[test]
+    fn loop_jumping_elsewhere_is_not_recognized() {
+        let mut program = divisor_sum_program();
+        program[9] = Instruction::new(SetI, 1, 0, 3);
+
+        let optimizations = Optimizations::analyze(3, &program);
+
+        assert!(optimizations.is_empty());
+    }
+}
+
+mod execute {
+    use super::*;
+
+    #[test]
+    fn divisor_sum_of_day19_example() {
+        let register = Register::from([0, 0, 1, 0, 12, 3]);
+
+        let optimized = run_optimized(&divisor_sum_program(), 3, register);
+
+        assert_eq!(optimized, run_plain(&divisor_sum_program(), 3, register));
+        assert_eq!(optimized.0, Register::from([3, 1, 13, 9, 12, 3]));
+    }
+
+    #[test]
+    fn division_by_256() {
+        let register = Register::from([0, 0, 0, 65536, 0, 0]);
+
+        let optimized = run_optimized(&division_program(256), 1, register);
+
+        assert_eq!(optimized, run_plain(&division_program(256), 1, register));
+        assert_eq!(optimized.0, Register::from([0, 9, 1, 256, 0, 256]));
+    }
+
+    #[test]
+    fn overflowing_loop_is_left_to_the_interpreter() {
+        let idiom = Idiom::DivisorSum {
+            factor: 5,
+            counter: 2,
+            target: 4,
+            sum: 0,
+            temp: 1,
+        };
+        let mut register = Register::<6>::from([0, 0, 1, 0, Data::MAX, 2]);
+
+        let result = idiom.execute(1, 3, &mut register);
+
+        assert_eq!(result, None);
+        assert_eq!(register, Register::from([0, 0, 1, 0, Data::MAX, 2]));
+    }
+
+    proptest! {
+        #[test]
+        fn divisor_sum_produces_same_registers_as_plain_interpreter(
+            sum in 0..100 as Data,
+            counter in 0..60 as Data,
+            target in 0..60 as Data,
+            factor in 0..50 as Data,
+        ) {
+            let register = Register::from([sum, 0, counter, 0, target, factor]);
+
+            let optimized = run_optimized(&divisor_sum_program(), 3, register);
+
+            prop_assert_eq!(optimized, run_plain(&divisor_sum_program(), 3, register));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn division_produces_same_registers_as_plain_interpreter(
+            divisor in 1..10 as Data,
+            dividend in 0..3000 as Data,
+            quotient in 0..400 as Data,
+        ) {
+            let register = Register::from([0, 0, 0, dividend, 0, quotient]);
+
+            let optimized = run_optimized(&division_program(divisor), 1, register);
+
+            prop_assert_eq!(optimized, run_plain(&division_program(divisor), 1, register));
+        }
+    }
+}
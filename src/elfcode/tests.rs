@@ -0,0 +1,214 @@
+use super::*;
+
+mod register {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let register = Register::from([3, 2, 1, 1]);
+
+        assert_eq!(register.to_string(), "[3, 2, 1, 1]");
+    }
+
+    #[test]
+    fn get_and_set_valid_register() {
+        let mut register = Register::<6>::default();
+
+        register.set(5, 42).unwrap();
+
+        assert_eq!(register.get(5), Ok(42));
+        assert_eq!(register.values(), &[0, 0, 0, 0, 0, 42]);
+    }
+
+    #[test]
+    fn get_invalid_register() {
+        let register = Register::<4>::default();
+
+        let value = register.get(4);
+
+        assert_eq!(
+            value,
+            Err(ExecutionError::InvalidRegister {
+                register: 4,
+                num_registers: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn set_invalid_register() {
+        let mut register = Register::<4>::default();
+
+        let result = register.set(6, 1);
+
+        assert_eq!(
+            result,
+            Err(ExecutionError::InvalidRegister {
+                register: 6,
+                num_registers: 4,
+            })
+        );
+        assert_eq!(register, Register::default());
+    }
+}
+
+mod execute {
+    use super::*;
+
+    #[test]
+    fn example_sample_of_day16() {
+        let before = Register::from([3, 2, 1, 1]);
+
+        let behaving_like = INSTRUCTION_SET
+            .iter()
+            .filter(|&&mnemonic| {
+                let mut register = before;
+                execute(Instruction::new(mnemonic, 2, 1, 2), &mut register).is_ok()
+                    && register == Register::from([3, 2, 2, 1])
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert_eq!(behaving_like, vec![AddI, MulR, SetI]);
+    }
+
+    #[test]
+    fn reading_invalid_register_is_an_error() {
+        let mut register = Register::from([1, 2, 3, 4]);
+
+        let result = execute(Instruction::new(AddR, 0, 7, 1), &mut register);
+
+        assert_eq!(
+            result,
+            Err(ExecutionError::InvalidRegister {
+                register: 7,
+                num_registers: 4,
+            })
+        );
+        assert_eq!(register, Register::from([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn writing_invalid_register_is_an_error() {
+        let mut register = Register::from([1, 2, 3, 4]);
+
+        let result = execute(Instruction::new(SetI, 9, 0, 4), &mut register);
+
+        assert_eq!(
+            result,
+            Err(ExecutionError::InvalidRegister {
+                register: 4,
+                num_registers: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let mut register = Register::from([Data::MAX, 2, 0, 0]);
+
+        let result = execute(Instruction::new(MulR, 0, 1, 2), &mut register);
+
+        assert_eq!(
+            result,
+            Err(ExecutionError::Overflow(Instruction::new(MulR, 0, 1, 2)))
+        );
+    }
+}
+
+mod interpreter {
+    use super::*;
+
+    #[test]
+    fn run_opcode_program_without_ip_binding() {
+        let program = vec![
+            Instruction::from((0, 7, 0, 0)),
+            Instruction::from((1, 0, 0, 1)),
+            Instruction::from((0, 3, 0, 2)),
+        ];
+        let opcodes = OpCodeTable::from_iter(vec![(OpCode(0), SetI), (OpCode(1), AddR)]);
+        let mut interpreter = Interpreter::new(opcodes);
+        let mut register = Register::<4>::default();
+
+        interpreter.run(&program, &mut register).unwrap();
+
+        assert_eq!(register, Register::from([7, 14, 3, 0]));
+        assert_eq!(interpreter.ip(), 3);
+        assert_eq!(interpreter.ip_reg(), None);
+    }
+
+    #[test]
+    fn run_unsupported_opcode() {
+        let program = vec![Instruction::from((5, 7, 0, 0))];
+        let mut interpreter = Interpreter::new(OpCodeTable::default());
+        let mut register = Register::<4>::default();
+
+        let result = interpreter.run(&program, &mut register);
+
+        assert_eq!(result, Err(ExecutionError::UnsupportedOpCode(OpCode(5))));
+    }
+
+    #[test]
+    fn run_mnemonic_program_with_ip_binding() {
+        let program = vec![
+            Instruction::new(SetI, 5, 0, 1),
+            Instruction::new(SetI, 6, 0, 2),
+            Instruction::new(AddI, 0, 1, 0),
+            Instruction::new(AddR, 1, 2, 3),
+            Instruction::new(SetR, 1, 0, 0),
+            Instruction::new(SetI, 8, 0, 4),
+            Instruction::new(SetI, 9, 0, 5),
+        ];
+        let mut interpreter = Interpreter::with_ip_binding(Mnemonics, 0);
+        let mut register = Register::<6>::default();
+
+        interpreter.run(&program, &mut register).unwrap();
+
+        assert_eq!(register, Register::from([6, 5, 6, 0, 0, 9]));
+        assert_eq!(interpreter.ip(), 7);
+    }
+
+    #[test]
+    fn ip_bound_to_invalid_register() {
+        let program = vec![Instruction::new(SetI, 5, 0, 1)];
+        let mut interpreter = Interpreter::with_ip_binding(Mnemonics, 4);
+        let mut register = Register::<4>::default();
+
+        let result = interpreter.run(&program, &mut register);
+
+        assert_eq!(
+            result,
+            Err(ExecutionError::InvalidRegister {
+                register: 4,
+                num_registers: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn ip_set_to_largest_address_halts() {
+        let program = vec![Instruction::new(SetI, Data::MAX, 0, 0)];
+        let mut interpreter = Interpreter::with_ip_binding(Mnemonics, 0);
+        let mut register = Register::<4>::default();
+
+        let result = interpreter.run(&program, &mut register);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(interpreter.ip(), Addr::MAX);
+        assert_eq!(register, Register::from([Data::MAX, 0, 0, 0]));
+    }
+
+    #[test]
+    fn step_returns_false_when_halted() {
+        let program = vec![Instruction::new(SetI, 5, 0, 1)];
+        let mut interpreter = Interpreter::new(Mnemonics);
+        let mut register = Register::<2>::default();
+
+        let first = interpreter.step(&program, &mut register);
+        let second = interpreter.step(&program, &mut register);
+
+        assert_eq!(first, Ok(true));
+        assert_eq!(second, Ok(false));
+        assert_eq!(register, Register::from([0, 5]));
+    }
+}
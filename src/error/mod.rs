@@ -0,0 +1,132 @@
+//! Errors shared by the puzzle input parsers of all days.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+/// Describes where and why the input of a day could not be parsed.
+///
+/// Lines and columns start at 1. The column counts characters, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+impl ParseError {
+    pub fn new(
+        day: u8,
+        line: usize,
+        column: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Self {
+        Self {
+            day,
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// An error about `part`, which must be a slice of `text`, the content of
+    /// the line with the given number.
+    pub fn at(day: u8, line: usize, text: &str, part: &str, expected: impl Into<String>) -> Self {
+        Self::new(day, line, column(text, part), expected, describe(part))
+    }
+
+    /// An error about a line that ends before the expected part.
+    pub fn end_of_line(day: u8, line: usize, text: &str, expected: impl Into<String>) -> Self {
+        Self::new(day, line, text.chars().count() + 1, expected, "end of line")
+    }
+
+    /// An error about an input that ends before the expected part.
+    pub fn end_of_input(day: u8, input: &str, expected: impl Into<String>) -> Self {
+        Self::new(day, input.lines().count() + 1, 1, expected, "end of input")
+    }
+}
+
+/// Returns the number and the content of the only non-blank line of an input.
+pub fn single_line<'a>(
+    day: u8,
+    input: &'a str,
+    expected: &str,
+) -> Result<(usize, &'a str), ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (idx, line) = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(day, input, expected))?;
+    if let Some((extra_idx, extra)) = lines.next() {
+        return Err(ParseError::at(
+            day,
+            extra_idx + 1,
+            extra,
+            extra.trim(),
+            "end of input",
+        ));
+    }
+    Ok((idx + 1, line))
+}
+
+/// Returns the column at which `part` starts within `text`.
+///
+/// `part` must be a slice of `text`, e.g. as returned by `split` or `trim`.
+/// Otherwise the first column is returned.
+pub fn column(text: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(text.as_ptr() as usize);
+    if offset
+        .checked_add(part.len())
+        .is_none_or(|end| end > text.len())
+    {
+        return 1;
+    }
+    text.get(..offset)
+        .map_or(1, |before| before.chars().count() + 1)
+}
+
+/// Quotes the text found instead of the expected part.
+pub fn describe(found: &str) -> String {
+    if found.is_empty() {
+        "nothing".to_string()
+    } else {
+        format!("{:?}", found)
+    }
+}
+
+/// Parses a number that is a slice of `text`.
+pub fn number<T>(
+    day: u8,
+    line: usize,
+    text: &str,
+    part: &str,
+    expected: impl Into<String>,
+) -> Result<T, ParseError>
+where
+    T: std::str::FromStr,
+{
+    part.parse()
+        .map_err(|_| ParseError::at(day, line, text, part, expected))
+}
+
+#[cfg(test)]
+mod tests;
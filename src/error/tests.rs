@@ -0,0 +1,27 @@
+use super::*;
+
+#[test]
+fn column_of_slice() {
+    let text = "pos=<1,-2>";
+
+    let part = text.split(['<', ',']).nth(1).unwrap();
+
+    assert_eq!(column(text, part), 6);
+}
+
+#[test]
+fn column_of_unrelated_text() {
+    assert_eq!(column("abc", "xyz"), 1);
+}
+
+#[test]
+fn display() {
+    let text = "10 players";
+
+    let error = ParseError::at(9, 1, text, &text[3..], "a number");
+
+    assert_eq!(
+        error.to_string(),
+        "day 9, line 1, column 4: expected a number, found \"players\""
+    );
+}
@@ -0,0 +1,450 @@
+//! Positions on and maps of a two dimensional grid.
+//!
+//! Many puzzles take place on a map given as text. The positions on such a map
+//! are ordered in reading order: top to bottom and within a line left to right.
+//! The x-axis points to the right and the y-axis points down.
+
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt::{self, Debug, Display},
+    hash::Hash,
+    ops::{Add, Index, IndexMut, Sub},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::ParseError;
+
+/// An integer type used for the coordinates of a position.
+pub trait Coord:
+    Copy + Ord + Hash + Debug + Display + Add<Output = Self> + Sub<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    fn to_usize(self) -> Option<usize>;
+
+    fn from_usize(value: usize) -> Option<Self>;
+}
+
+macro_rules! impl_coord {
+    ($($int:ty),*) => {
+        $(
+            impl Coord for $int {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MIN: Self = <$int>::MIN;
+                const MAX: Self = <$int>::MAX;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_sub(self, rhs)
+                }
+
+                fn to_usize(self) -> Option<usize> {
+                    usize::try_from(self).ok()
+                }
+
+                fn from_usize(value: usize) -> Option<Self> {
+                    <$int>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i16, i32, i64, isize, u16, u32, u64, usize);
+
+/// A position on the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pos<C> {
+    pub x: C,
+    pub y: C,
+}
+
+impl<C: Coord> Display for Pos<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+/// Positions are ordered in reading order.
+impl<C: Coord> Ord for Pos<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.y.cmp(&other.y).then(self.x.cmp(&other.x))
+    }
+}
+
+impl<C: Coord> PartialOrd for Pos<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Coord> Add for Pos<C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Pos::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<C: Coord> Sub for Pos<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Pos::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<C: Coord> Pos<C> {
+    pub const MIN: Self = Pos {
+        x: C::MIN,
+        y: C::MIN,
+    };
+
+    pub const MAX: Self = Pos {
+        x: C::MAX,
+        y: C::MAX,
+    };
+
+    pub const ORIGIN: Self = Pos {
+        x: C::ZERO,
+        y: C::ZERO,
+    };
+
+    pub fn new(x: C, y: C) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> C {
+        let distance = |a: C, b: C| if a < b { b - a } else { a - b };
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// Returns the position moved by the given offset or `None` if the moved
+    /// position is not representable by the coordinate type.
+    pub fn offset(self, (dx, dy): (i8, i8)) -> Option<Self> {
+        let shift = |coord: C, delta: i8| match delta.cmp(&0) {
+            Ordering::Less => coord.checked_sub(C::ONE),
+            Ordering::Equal => Some(coord),
+            Ordering::Greater => coord.checked_add(C::ONE),
+        };
+        Some(Pos::new(shift(self.x, dx)?, shift(self.y, dy)?))
+    }
+
+    /// Returns the up to 4 positions above, left, right and below of this
+    /// position in reading order.
+    pub fn neighbours4(self) -> Neighbours<C> {
+        Neighbours::new(self, &NEIGHBOUR_OFFSETS_4)
+    }
+
+    /// Returns the up to 8 positions surrounding this position including the
+    /// diagonal ones in reading order.
+    pub fn neighbours8(self) -> Neighbours<C> {
+        Neighbours::new(self, &NEIGHBOUR_OFFSETS_8)
+    }
+}
+
+const NEIGHBOUR_OFFSETS_4: [(i8, i8); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+const NEIGHBOUR_OFFSETS_8: [(i8, i8); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Iterator over the neighbours of a position.
+///
+/// Neighbours that are not representable by the coordinate type are skipped.
+#[derive(Debug, Clone)]
+pub struct Neighbours<C> {
+    center: Pos<C>,
+    offsets: &'static [(i8, i8)],
+}
+
+impl<C> Neighbours<C> {
+    fn new(center: Pos<C>, offsets: &'static [(i8, i8)]) -> Self {
+        Self { center, offsets }
+    }
+}
+
+impl<C: Coord> Iterator for Neighbours<C> {
+    type Item = Pos<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((&offset, rest)) = self.offsets.split_first() {
+            self.offsets = rest;
+            if let Some(neighbour) = self.center.offset(offset) {
+                return Some(neighbour);
+            }
+        }
+        None
+    }
+}
+
+/// Returns the top left and the bottom right corner of the smallest rectangle
+/// containing all the given positions or `None` if there are no positions.
+pub fn bounding_box<C: Coord>(
+    positions: impl IntoIterator<Item = Pos<C>>,
+) -> Option<(Pos<C>, Pos<C>)> {
+    positions.into_iter().fold(None, |corners, pos| {
+        let (top_left, bottom_right) = corners.unwrap_or((pos, pos));
+        Some((
+            Pos::new(top_left.x.min(pos.x), top_left.y.min(pos.y)),
+            Pos::new(bottom_right.x.max(pos.x), bottom_right.y.max(pos.y)),
+        ))
+    })
+}
+
+/// A rectangular map that stores a value for every position within its
+/// bounds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Grid<T, C = usize> {
+    origin: Pos<C>,
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T, C: Coord> Grid<T, C> {
+    /// Creates a grid with its top left corner at (0,0) and all cells set to
+    /// the given value.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::with_origin(Pos::ORIGIN, width, height, value)
+    }
+
+    /// Creates a grid with its top left corner at the given origin and all
+    /// cells set to the given value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bottom right corner is not representable by the
+    /// coordinate type.
+    pub fn with_origin(origin: Pos<C>, width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let grid = Self {
+            origin,
+            width,
+            height,
+            cells: vec![value; width * height],
+        };
+        if width > 0 && height > 0 {
+            assert!(
+                grid.position_at(width * height - 1).is_some(),
+                "grid of size {}x{} at {} exceeds the coordinate range",
+                width,
+                height,
+                origin
+            );
+        }
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn origin(&self) -> Pos<C> {
+        self.origin
+    }
+
+    /// Returns the top left and the bottom right corner of the grid or `None`
+    /// if the grid is empty.
+    pub fn bounds(&self) -> Option<(Pos<C>, Pos<C>)> {
+        if self.cells.is_empty() {
+            None
+        } else {
+            Some((self.origin, self.position_at(self.cells.len() - 1)?))
+        }
+    }
+
+    pub fn contains(&self, pos: Pos<C>) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    pub fn get(&self, pos: Pos<C>) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos<C>) -> Option<&mut T> {
+        self.index_of(pos).map(move |idx| &mut self.cells[idx])
+    }
+
+    /// Sets the value at the given position and returns the previous one or
+    /// `None` if the position is out of bounds.
+    pub fn set(&mut self, pos: Pos<C>, value: T) -> Option<T> {
+        self.get_mut(pos).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Returns all positions of the grid in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos<C>> + '_ {
+        (0..self.cells.len()).map(move |idx| self.position_at(idx).expect("within bounds"))
+    }
+
+    /// Returns all positions of the grid together with their values in
+    /// reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos<C>, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the 4 neighbours of a position that are within the bounds of
+    /// the grid.
+    pub fn neighbours4(&self, pos: Pos<C>) -> impl Iterator<Item = Pos<C>> + '_ {
+        pos.neighbours4().filter(move |&pos| self.contains(pos))
+    }
+
+    /// Returns the 8 neighbours of a position that are within the bounds of
+    /// the grid.
+    pub fn neighbours8(&self, pos: Pos<C>) -> impl Iterator<Item = Pos<C>> + '_ {
+        pos.neighbours8().filter(move |&pos| self.contains(pos))
+    }
+
+    /// Renders the grid as text map with one line per row using the given
+    /// function to get the symbol of each cell.
+    pub fn render(&self, mut symbol: impl FnMut(&T) -> char) -> String {
+        let mut map = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            map.extend(row.iter().map(&mut symbol));
+            map.push('\n');
+        }
+        map
+    }
+
+    fn index_of(&self, pos: Pos<C>) -> Option<usize> {
+        let x = pos.x.checked_sub(self.origin.x)?.to_usize()?;
+        let y = pos.y.checked_sub(self.origin.y)?.to_usize()?;
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    fn position_at(&self, idx: usize) -> Option<Pos<C>> {
+        let x = self
+            .origin
+            .x
+            .checked_add(C::from_usize(idx % self.width)?)?;
+        let y = self
+            .origin
+            .y
+            .checked_add(C::from_usize(idx / self.width)?)?;
+        Some(Pos::new(x, y))
+    }
+}
+
+impl<T: Clone + Default, C: Coord> Grid<T, C> {
+    /// Parses a text map into a grid with its top left corner at (0,0).
+    ///
+    /// The function `cell` returns the value for a symbol or `None` if the
+    /// symbol is not valid. Lines shorter than the longest line are filled up
+    /// with the default value.
+    pub fn parse_map(
+        day: u8,
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        if width == 0 {
+            return Err(ParseError::end_of_input(day, input, expected));
+        }
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (line_no, line) in lines.iter().enumerate().map(|(idx, line)| (idx + 1, line)) {
+            for (offset, chr) in line.char_indices() {
+                let value = cell(chr).ok_or_else(|| {
+                    ParseError::at(
+                        day,
+                        line_no,
+                        line,
+                        &line[offset..offset + chr.len_utf8()],
+                        expected,
+                    )
+                })?;
+                cells.push(value);
+            }
+            let len = line.chars().count();
+            cells.extend((len..width).map(|_| T::default()));
+        }
+        let grid = Self {
+            origin: Pos::ORIGIN,
+            width,
+            height: lines.len(),
+            cells,
+        };
+        if grid.position_at(grid.cells.len() - 1).is_none() {
+            return Err(ParseError::new(
+                day,
+                lines.len(),
+                width,
+                expected,
+                format!("a map of {}x{} cells", width, lines.len()),
+            ));
+        }
+        Ok(grid)
+    }
+}
+
+impl<T: Display, C: Coord> Display for Grid<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, C: Coord> Index<Pos<C>> for Grid<T, C> {
+    type Output = T;
+
+    fn index(&self, pos: Pos<C>) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {} is out of bounds", pos))
+    }
+}
+
+impl<T, C: Coord> IndexMut<Pos<C>> for Grid<T, C> {
+    fn index_mut(&mut self, pos: Pos<C>) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {} is out of bounds", pos))
+    }
+}
+
+#[cfg(test)]
+mod tests;
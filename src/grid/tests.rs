@@ -0,0 +1,199 @@
+use super::*;
+
+const MAP: &str = "\
+#..
+.#
+..#
+";
+
+fn parse_map(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse_map(0, input, "'.' or '#'", |chr| match chr {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    })
+}
+
+mod pos {
+    use super::*;
+
+    #[test]
+    fn reading_order() {
+        let mut positions = vec![
+            Pos::new(2, 1),
+            Pos::new(0, 2),
+            Pos::new(1, 0),
+            Pos::new(0, 1),
+        ];
+
+        positions.sort();
+
+        assert_eq!(
+            positions,
+            vec![
+                Pos::new(1, 0),
+                Pos::new(0, 1),
+                Pos::new(2, 1),
+                Pos::new(0, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn manhattan_distance() {
+        let distance = Pos::new(1u32, 6).manhattan_distance(Pos::new(4, 2));
+
+        assert_eq!(distance, 7);
+    }
+
+    #[test]
+    fn neighbours4_in_reading_order() {
+        let neighbours = Pos::new(-1i16, 5).neighbours4().collect::<Vec<_>>();
+
+        assert_eq!(
+            neighbours,
+            vec![
+                Pos::new(-1, 4),
+                Pos::new(-2, 5),
+                Pos::new(0, 5),
+                Pos::new(-1, 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbours8_in_reading_order() {
+        let neighbours = Pos::new(1u16, 1).neighbours8().collect::<Vec<_>>();
+
+        assert_eq!(
+            neighbours,
+            vec![
+                Pos::new(0, 0),
+                Pos::new(1, 0),
+                Pos::new(2, 0),
+                Pos::new(0, 1),
+                Pos::new(2, 1),
+                Pos::new(0, 2),
+                Pos::new(1, 2),
+                Pos::new(2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbours_out_of_coordinate_range_are_skipped() {
+        let neighbours = Pos::new(0usize, 0).neighbours8().collect::<Vec<_>>();
+
+        assert_eq!(
+            neighbours,
+            vec![Pos::new(1, 0), Pos::new(0, 1), Pos::new(1, 1)]
+        );
+    }
+}
+
+mod bounding_box {
+    use super::*;
+
+    #[test]
+    fn of_positions() {
+        let positions = vec![Pos::new(3, -2), Pos::new(-1, 4), Pos::new(0, 0)];
+
+        let corners = bounding_box(positions);
+
+        assert_eq!(corners, Some((Pos::new(-1, -2), Pos::new(3, 4))));
+    }
+
+    #[test]
+    fn of_no_positions() {
+        let corners = bounding_box(Vec::<Pos<u32>>::new());
+
+        assert_eq!(corners, None);
+    }
+}
+
+mod grid {
+    use super::*;
+
+    #[test]
+    fn parse_and_render_map() {
+        let grid = parse_map(MAP).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert!(grid[Pos::new(1, 1)]);
+        assert!(!grid[Pos::new(2, 1)]);
+        assert_eq!(
+            grid.render(|&wall| if wall { '#' } else { '.' }),
+            "#..\n.#.\n..#\n"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_symbol() {
+        let result = parse_map("#..\n.x.\n");
+
+        assert_eq!(result, Err(ParseError::new(0, 2, 2, "'.' or '#'", "\"x\"")));
+    }
+
+    #[test]
+    fn parse_empty_map() {
+        let result = parse_map("");
+
+        assert_eq!(
+            result,
+            Err(ParseError::new(0, 1, 1, "'.' or '#'", "end of input"))
+        );
+    }
+
+    #[test]
+    fn bounds_with_origin() {
+        let grid = Grid::with_origin(Pos::new(-2i32, 10), 4, 3, 0u8);
+
+        assert_eq!(grid.bounds(), Some((Pos::new(-2, 10), Pos::new(1, 12))));
+        assert!(grid.contains(Pos::new(1, 12)));
+        assert!(!grid.contains(Pos::new(2, 12)));
+        assert!(!grid.contains(Pos::new(-3, 10)));
+    }
+
+    #[test]
+    fn set_and_get() {
+        let mut grid = Grid::<char, i16>::new(2, 2, '.');
+
+        let previous = grid.set(Pos::new(1, 0), '#');
+        let outside = grid.set(Pos::new(2, 0), '#');
+
+        assert_eq!(previous, Some('.'));
+        assert_eq!(outside, None);
+        assert_eq!(grid.get(Pos::new(1, 0)), Some(&'#'));
+        assert_eq!(grid.to_string(), ".#\n..\n");
+    }
+
+    #[test]
+    fn iterate_in_reading_order() {
+        let grid = parse_map(".#\n#.").unwrap();
+
+        let walls = grid
+            .iter()
+            .filter(|(_, &wall)| wall)
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+
+        assert_eq!(walls, vec![Pos::new(1, 0), Pos::new(0, 1)]);
+    }
+
+    #[test]
+    fn neighbours_within_bounds() {
+        let grid = Grid::<u8>::new(2, 3, 0);
+
+        let neighbours = grid.neighbours4(Pos::new(1, 2)).collect::<Vec<_>>();
+
+        assert_eq!(neighbours, vec![Pos::new(1, 1), Pos::new(0, 2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "position (0,3) is out of bounds")]
+    fn index_out_of_bounds() {
+        let grid = Grid::<u8>::new(2, 3, 0);
+
+        let _ = grid[Pos::new(0, 3)];
+    }
+}
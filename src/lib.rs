@@ -55,5 +55,21 @@ pub mod day16;
 pub mod day17;
 pub mod day18;
 pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod elfcode;
+pub mod error;
+pub mod grid;
+pub mod registry;
+pub mod render;
+#[cfg(feature = "serde")]
+mod serde_pairs;
+pub mod union_find;
+
+pub use crate::registry::{registry, solve};
 
 aoc_lib! { year = 2018 }
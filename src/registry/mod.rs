@@ -0,0 +1,274 @@
+//! Lists all implemented puzzle solvers and runs them by day and part.
+//!
+//! The solvers are the same functions `cargo aoc` runs. Each of them parses
+//! the puzzle input with the `try_parse` function of its day, so a malformed
+//! input is reported as an error instead of aborting the process.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::{error::ParseError, *};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    NoSuchPuzzle {
+        day: u8,
+        part: u8,
+    },
+    Parse(ParseError),
+    /// The solver could not find an answer for the given input.
+    Solver(String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::NoSuchPuzzle { day, part } => {
+                write!(f, "there is no puzzle for day {} part {}", day, part)
+            },
+            SolveError::Parse(err) => write!(f, "{}", err),
+            SolveError::Solver(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+    fn from(err: ParseError) -> Self {
+        SolveError::Parse(err)
+    }
+}
+
+type SolveFn = fn(&str) -> Result<String, SolveError>;
+
+/// The solver of one part of the puzzle of a day.
+#[derive(Debug, Clone, Copy)]
+pub struct Solver {
+    day: u8,
+    part: u8,
+    title: &'static str,
+    solve: SolveFn,
+}
+
+impl Solver {
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn part(&self) -> u8 {
+        self.part
+    }
+
+    /// The title of the puzzle as given in the documentation of its day.
+    pub fn title(&self) -> &'static str {
+        self.title
+    }
+
+    /// Parses the input and solves the part of the puzzle.
+    pub fn solve(&self, input: &str) -> Result<String, SolveError> {
+        (self.solve)(input)
+    }
+}
+
+macro_rules! solver {
+    ($day:literal, $part:literal, $title:literal, $module:ident :: $function:ident) => {
+        Solver {
+            day: $day,
+            part: $part,
+            title: $title,
+            solve: |input| Ok($module::$function(&$module::try_parse(input)?).to_string()),
+        }
+    };
+    ($day:literal, $part:literal, $title:literal, $solve:expr) => {
+        Solver {
+            day: $day,
+            part: $part,
+            title: $title,
+            solve: $solve,
+        }
+    };
+}
+
+static SOLVERS: [Solver; 49] = [
+    solver!(1, 1, "Chronal Calibration", day01::accumulate),
+    solver!(1, 2, "Chronal Calibration", |input| {
+        day01::calibrate(&day01::try_parse(input)?)
+            .map(|frequency| frequency.to_string())
+            .ok_or_else(|| SolveError::Solver("no frequency is reached twice".into()))
+    }),
+    solver!(2, 1, "Inventory Management System", day02::checksum),
+    solver!(2, 2, "Inventory Management System", |input| {
+        day02::search_prototype_boxes(&day02::try_parse(input)?)
+            .ok_or_else(|| SolveError::Solver("no correct box IDs found".into()))
+    }),
+    solver!(3, 1, "No Matter How You Slice It", day03::overlapping_area),
+    solver!(3, 2, "No Matter How You Slice It", |input| {
+        day03::non_overlapping_claims(&day03::try_parse(input)?)
+            .map(|id| id.to_string())
+            .ok_or_else(|| SolveError::Solver("there is not exactly one intact claim".into()))
+    }),
+    solver!(4, 1, "Repose Record", day04::strategy1),
+    solver!(4, 2, "Repose Record", day04::strategy2),
+    solver!(5, 1, "Alchemical Reduction", |input| {
+        Ok(day05::reduced_polymer_len(day05::try_parse(input)?).to_string())
+    }),
+    solver!(5, 2, "Alchemical Reduction", |input| {
+        Ok(day05::improved_polymer_len(day05::try_parse(input)?).to_string())
+    }),
+    solver!(6, 1, "Chronal Coordinates", day06::solve_part1),
+    solver!(6, 2, "Chronal Coordinates", day06::solve_part2),
+    solver!(7, 1, "The Sum of Its Parts", day07::execution_order),
+    solver!(7, 2, "The Sum of Its Parts", day07::execution_time),
+    solver!(8, 1, "Memory Maneuver", day08::metadata_checksum),
+    solver!(8, 2, "Memory Maneuver", day08::value_of_license_root),
+    solver!(9, 1, "Marble Mania", day09::marble_highscore),
+    solver!(9, 2, "Marble Mania", day09::marble_highscore_100),
+    solver!(10, 1, "The Stars Align", day10::align_stars),
+    solver!(10, 2, "The Stars Align", day10::time_to_aligned_stars),
+    solver!(11, 1, "Chronal Charge", day11::best_cell_group),
+    solver!(11, 2, "Chronal Charge", day11::best_cell_group_size),
+    solver!(
+        12,
+        1,
+        "Subterranean Sustainability",
+        day12::sum_of_pot_numbers_after_20_generations
+    ),
+    solver!(
+        12,
+        2,
+        "Subterranean Sustainability",
+        day12::sum_of_pot_numbers_after_50_000_000_000_generations
+    ),
+    solver!(13, 1, "Mine Cart Madness", day13::location_of_first_crash),
+    solver!(13, 2, "Mine Cart Madness", day13::location_of_last_cart),
+    solver!(14, 1, "Chocolate Charts", |input| {
+        day14::try_parse(input)?;
+        Ok(day14::score_seq_after_num_recipes(input).to_string())
+    }),
+    solver!(14, 2, "Chocolate Charts", |input| {
+        day14::try_parse(input)?;
+        Ok(day14::num_needed_recipes(input).to_string())
+    }),
+    solver!(15, 1, "Beverage Bandits", day15::fight),
+    solver!(15, 2, "Beverage Bandits", day15::fake_fight),
+    solver!(
+        16,
+        1,
+        "Chronal Classification",
+        day16::num_samples_behaving_like_three_or_more_opcodes
+    ),
+    solver!(16, 2, "Chronal Classification", |input| {
+        day16::run_program(&day16::try_parse(input)?)
+            .map(|register| register.to_string())
+            .map_err(SolveError::Solver)
+    }),
+    solver!(
+        17,
+        1,
+        "Reservoir Research",
+        day17::num_tiles_flooded_by_water
+    ),
+    solver!(
+        17,
+        2,
+        "Reservoir Research",
+        day17::num_tiles_flooded_by_water_after_spring_has_run_dry
+    ),
+    solver!(
+        18,
+        1,
+        "Settlers of The North Pole",
+        day18::total_resource_value_after_10_minutes
+    ),
+    solver!(
+        18,
+        2,
+        "Settlers of The North Pole",
+        day18::total_resource_value_after_1_000_000_000_minutes
+    ),
+    solver!(19, 1, "Go With The Flow", |input| {
+        day19::run_background_process(&day19::try_parse(input)?)
+            .map(|value| value.to_string())
+            .map_err(SolveError::Solver)
+    }),
+    solver!(19, 2, "Go With The Flow", |input| {
+        day19::run_background_process_2(&day19::try_parse(input)?)
+            .map(|value| value.to_string())
+            .map_err(SolveError::Solver)
+    }),
+    solver!(20, 1, "A Regular Map", day20::largest_number_of_doors),
+    solver!(
+        20,
+        2,
+        "A Regular Map",
+        day20::num_rooms_at_least_1000_doors_away
+    ),
+    solver!(21, 1, "Chronal Conversion", |input| {
+        day21::value_halting_after_fewest_instructions(&day21::try_parse(input)?)
+            .map(|value| value.to_string())
+            .map_err(SolveError::Solver)
+    }),
+    solver!(21, 2, "Chronal Conversion", |input| {
+        day21::value_halting_after_most_instructions(&day21::try_parse(input)?)
+            .map(|value| value.to_string())
+            .map_err(SolveError::Solver)
+    }),
+    solver!(22, 1, "Mode Maze", day22::total_risk_level),
+    solver!(22, 2, "Mode Maze", |input| {
+        day22::fewest_minutes_to_reach_target(&day22::try_parse(input)?)
+            .map(|minutes| minutes.to_string())
+            .ok_or_else(|| SolveError::Solver("no route to the target found".into()))
+    }),
+    solver!(23, 1, "Experimental Emergency Teleportation", |input| {
+        day23::num_nanobots_in_range_of_strongest(&day23::try_parse(input)?)
+            .map(|count| count.to_string())
+            .ok_or_else(|| SolveError::Solver("no nanobots in formation".into()))
+    }),
+    solver!(
+        23,
+        2,
+        "Experimental Emergency Teleportation",
+        day23::shortest_distance_to_best_coverage
+    ),
+    solver!(24, 1, "Immune System Simulator 20XX", |input| {
+        day24::num_units_of_winning_army(&day24::try_parse(input)?)
+            .map(|units| units.to_string())
+            .ok_or_else(|| SolveError::Solver("the battle ends in a stalemate".into()))
+    }),
+    solver!(24, 2, "Immune System Simulator 20XX", |input| {
+        day24::num_units_left_with_smallest_boost(&day24::try_parse(input)?)
+            .map(|units| units.to_string())
+            .ok_or_else(|| SolveError::Solver("no boost lets the immune system win".into()))
+    }),
+    solver!(
+        25,
+        1,
+        "Four-Dimensional Adventure",
+        day25::num_constellations
+    ),
+];
+
+/// Returns the solvers of all implemented puzzles ordered by day and part.
+pub fn registry() -> &'static [Solver] {
+    &SOLVERS
+}
+
+/// Returns the solver of a part of the puzzle of a day.
+pub fn find(day: u8, part: u8) -> Option<&'static Solver> {
+    SOLVERS
+        .iter()
+        .find(|solver| solver.day == day && solver.part == part)
+}
+
+/// Solves a part of the puzzle of a day for the given input.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, SolveError> {
+    find(day, part)
+        .ok_or(SolveError::NoSuchPuzzle { day, part })?
+        .solve(input)
+}
+
+#[cfg(test)]
+mod tests;
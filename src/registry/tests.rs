@@ -0,0 +1,114 @@
+use super::*;
+
+const MODULE_SOURCES: [&str; 25] = [
+    include_str!("../day01/mod.rs"),
+    include_str!("../day02/mod.rs"),
+    include_str!("../day03/mod.rs"),
+    include_str!("../day04/mod.rs"),
+    include_str!("../day05/mod.rs"),
+    include_str!("../day06/mod.rs"),
+    include_str!("../day07/mod.rs"),
+    include_str!("../day08/mod.rs"),
+    include_str!("../day09/mod.rs"),
+    include_str!("../day10/mod.rs"),
+    include_str!("../day11/mod.rs"),
+    include_str!("../day12/mod.rs"),
+    include_str!("../day13/mod.rs"),
+    include_str!("../day14/mod.rs"),
+    include_str!("../day15/mod.rs"),
+    include_str!("../day16/mod.rs"),
+    include_str!("../day17/mod.rs"),
+    include_str!("../day18/mod.rs"),
+    include_str!("../day19/mod.rs"),
+    include_str!("../day20/mod.rs"),
+    include_str!("../day21/mod.rs"),
+    include_str!("../day22/mod.rs"),
+    include_str!("../day23/mod.rs"),
+    include_str!("../day24/mod.rs"),
+    include_str!("../day25/mod.rs"),
+];
+
+#[test]
+fn lists_both_parts_of_each_day_but_the_last() {
+    let puzzles = registry()
+        .iter()
+        .map(|solver| (solver.day(), solver.part()))
+        .collect::<Vec<_>>();
+
+    let mut expected = (1..=24)
+        .flat_map(|day| vec![(day, 1), (day, 2)])
+        .collect::<Vec<_>>();
+    expected.push((25, 1));
+    assert_eq!(puzzles, expected);
+}
+
+#[test]
+fn titles_match_module_docs() {
+    for solver in registry() {
+        let heading = MODULE_SOURCES[solver.day() as usize - 1].lines().next();
+
+        assert_eq!(
+            heading,
+            Some(format!("//! # Day {}: {}", solver.day(), solver.title()).as_str())
+        );
+    }
+}
+
+#[test]
+fn solve_example_of_day1() {
+    let answer = solve(1, 2, "+3\n+3\n+4\n-2\n-4\n");
+
+    assert_eq!(answer, Ok("10".to_string()));
+}
+
+#[test]
+fn solve_example_of_day5() {
+    let answer = solve(5, 1, "dabAcCaCBAcCcaDA\n");
+
+    assert_eq!(answer, Ok("10".to_string()));
+}
+
+#[test]
+fn unknown_puzzle() {
+    let answer = solve(25, 2, "");
+
+    assert_eq!(answer, Err(SolveError::NoSuchPuzzle { day: 25, part: 2 }));
+}
+
+#[test]
+fn malformed_input() {
+    let answer = solve(11, 1, "serial\n");
+
+    assert_eq!(
+        answer,
+        Err(SolveError::Parse(ParseError::new(
+            11,
+            1,
+            1,
+            "a grid serial number",
+            "\"serial\""
+        )))
+    );
+}
+
+#[test]
+fn solver_error_for_empty_formation() {
+    let answer = solve(23, 1, "");
+
+    assert_eq!(
+        answer,
+        Err(SolveError::Solver("no nanobots in formation".to_string()))
+    );
+}
+
+#[test]
+fn solver_error_for_program_without_halting_check() {
+    let answer = solve(21, 1, "#ip 0\nseti 5 0 1\n");
+
+    assert_eq!(
+        answer,
+        Err(SolveError::Solver(
+            "no halting check comparing register 0 found in program".to_string()
+        ))
+    );
+}
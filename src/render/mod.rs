@@ -0,0 +1,310 @@
+//! Exports the states of a simulation as images.
+//!
+//! A simulation implements [`Render`] to map the cells of its current state to
+//! colors. A [`Recorder`] takes one frame after each step of the simulation
+//! and writes it either to a PPM file per frame ([`PpmFrames`]) or to an
+//! animated GIF ([`GifAnimation`]).
+//!
+//! ```no_run
+//! use advent_of_code_2018::{day18, render::{GifAnimation, Recorder}};
+//! use std::time::Duration;
+//!
+//! let mut area = day18::try_parse(include_str!("../../input/2018/day18.txt")).unwrap();
+//! let mut gif = GifAnimation::create("day18.gif", 4, Duration::from_millis(100)).unwrap();
+//! for _ in 0..100 {
+//!     gif.record(&area).unwrap();
+//!     area = area.nth_generation(1);
+//! }
+//! gif.finish().unwrap();
+//! ```
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    error::Error,
+    fmt::{self, Display},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use gif::{Encoder, EncodingError, Frame, Repeat};
+
+use crate::grid::{Grid, Pos};
+
+/// A color given by its red, green and blue component.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+}
+
+/// The coordinates of a rendered cell.
+///
+/// Frames are aligned by the coordinates of their cells, so a frame may be
+/// taken of only a part of the simulated area.
+pub type Coord = i64;
+
+/// A type whose state can be rendered as an image.
+pub trait Render {
+    /// Returns the color of each cell of the current state.
+    fn render(&self) -> Grid<Rgb, Coord>;
+}
+
+/// Records the states of a simulation as frames.
+pub trait Recorder {
+    /// Renders the current state of the simulation and records it as the
+    /// next frame.
+    fn record(&mut self, state: &dyn Render) -> Result<(), RenderError>;
+}
+
+#[derive(Debug)]
+pub enum RenderError {
+    Io(io::Error),
+    Gif(EncodingError),
+    /// The frame does not fit into an image of the format.
+    FrameTooLarge {
+        width: usize,
+        height: usize,
+    },
+    NoFrames,
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenderError::Io(err) => write!(f, "can not write image: {}", err),
+            RenderError::Gif(err) => write!(f, "can not encode GIF: {}", err),
+            RenderError::FrameTooLarge { width, height } => write!(
+                f,
+                "a frame of {}x{} pixels is too large for the image format",
+                width, height
+            ),
+            RenderError::NoFrames => f.write_str("no frames have been recorded"),
+        }
+    }
+}
+
+impl Error for RenderError {}
+
+impl From<io::Error> for RenderError {
+    fn from(err: io::Error) -> Self {
+        RenderError::Io(err)
+    }
+}
+
+impl From<EncodingError> for RenderError {
+    fn from(err: EncodingError) -> Self {
+        RenderError::Gif(err)
+    }
+}
+
+/// Creates a frame covering the area between the given corners with all cells
+/// set to the background color. Without corners the frame is empty.
+pub fn canvas(corners: Option<(Pos<Coord>, Pos<Coord>)>, background: Rgb) -> Grid<Rgb, Coord> {
+    match corners {
+        Some((top_left, bottom_right)) => {
+            let size = bottom_right - top_left;
+            Grid::with_origin(
+                top_left,
+                size.x as usize + 1,
+                size.y as usize + 1,
+                background,
+            )
+        },
+        None => Grid::new(0, 0, background),
+    }
+}
+
+/// Returns the pixels of a frame in reading order with each cell scaled to a
+/// square of `scale` times `scale` pixels.
+fn pixels(frame: &Grid<Rgb, Coord>, scale: usize) -> Vec<Rgb> {
+    let width = frame.width() * scale;
+    let mut pixels = Vec::with_capacity(width * frame.height() * scale);
+    let mut row = Vec::with_capacity(width);
+    for (_, &color) in frame.iter() {
+        row.extend((0..scale).map(|_| color));
+        if row.len() == width {
+            for _ in 0..scale {
+                pixels.extend_from_slice(&row);
+            }
+            row.clear();
+        }
+    }
+    pixels
+}
+
+/// Writes a frame as binary PPM image with each cell scaled to a square of
+/// `scale` times `scale` pixels.
+pub fn write_ppm(mut writer: impl Write, frame: &Grid<Rgb, Coord>, scale: usize) -> io::Result<()> {
+    write!(
+        writer,
+        "P6\n{} {}\n255\n",
+        frame.width() * scale,
+        frame.height() * scale
+    )?;
+    let bytes = pixels(frame, scale)
+        .into_iter()
+        .flat_map(|Rgb(red, green, blue)| vec![red, green, blue])
+        .collect::<Vec<_>>();
+    writer.write_all(&bytes)?;
+    writer.flush()
+}
+
+/// Writes each frame to its own PPM file.
+///
+/// The files are named by a prefix followed by the number of the frame, like
+/// `day17-00000.ppm`, `day17-00001.ppm` and so on.
+#[derive(Debug, Clone)]
+pub struct PpmFrames {
+    dir: PathBuf,
+    prefix: String,
+    scale: usize,
+    num_frames: usize,
+}
+
+impl PpmFrames {
+    /// Creates a recorder writing the frames to the given directory, which is
+    /// created if it does not exist.
+    pub fn new(dir: impl Into<PathBuf>, prefix: impl Into<String>, scale: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: prefix.into(),
+            scale,
+            num_frames: 0,
+        }
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.num_frames
+    }
+
+    /// The path of the file the frame with the given number is written to.
+    pub fn path(&self, frame_no: usize) -> PathBuf {
+        self.dir.join(format!("{}{:05}.ppm", self.prefix, frame_no))
+    }
+}
+
+impl Recorder for PpmFrames {
+    fn record(&mut self, state: &dyn Render) -> Result<(), RenderError> {
+        if self.num_frames == 0 {
+            fs::create_dir_all(&self.dir)?;
+        }
+        let file = File::create(self.path(self.num_frames))?;
+        write_ppm(BufWriter::new(file), &state.render(), self.scale)?;
+        self.num_frames += 1;
+        Ok(())
+    }
+}
+
+/// Writes all frames to an animated GIF that loops forever.
+///
+/// The first frame determines the area shown by the animation. Cells of later
+/// frames outside of this area are cut off and cells missing in later frames
+/// are drawn black.
+pub struct GifAnimation<W: Write> {
+    writer: Option<W>,
+    encoder: Option<(Encoder<W>, Grid<Rgb, Coord>)>,
+    scale: usize,
+    delay: u16,
+}
+
+impl GifAnimation<BufWriter<File>> {
+    /// Creates a recorder writing the animation to a file.
+    pub fn create(path: impl AsRef<Path>, scale: usize, delay: Duration) -> io::Result<Self> {
+        File::create(path).map(|file| Self::new(BufWriter::new(file), scale, delay))
+    }
+}
+
+impl<W: Write> GifAnimation<W> {
+    /// Creates a recorder showing each frame for the given delay with each
+    /// cell scaled to a square of `scale` times `scale` pixels.
+    pub fn new(writer: W, scale: usize, delay: Duration) -> Self {
+        let centiseconds = delay.as_millis() / 10;
+        Self {
+            writer: Some(writer),
+            encoder: None,
+            scale,
+            delay: centiseconds.min(u128::from(u16::MAX)) as u16,
+        }
+    }
+
+    /// Finishes the animation and returns the writer.
+    pub fn finish(mut self) -> Result<W, RenderError> {
+        match self.encoder.take() {
+            Some((encoder, _)) => Ok(encoder.into_inner()?),
+            None => Err(RenderError::NoFrames),
+        }
+    }
+
+    fn start(&mut self, frame: &Grid<Rgb, Coord>) -> Result<(), RenderError> {
+        let (width, height) = (frame.width() * self.scale, frame.height() * self.scale);
+        let too_large = || RenderError::FrameTooLarge { width, height };
+        let gif_width = u16::try_from(width).map_err(|_| too_large())?;
+        let gif_height = u16::try_from(height).map_err(|_| too_large())?;
+        let writer = self.writer.take().expect("animation is not started yet");
+        let mut encoder = Encoder::new(writer, gif_width, gif_height, &[])?;
+        encoder.set_repeat(Repeat::Infinite)?;
+        let canvas = Grid::with_origin(frame.origin(), frame.width(), frame.height(), Rgb::BLACK);
+        self.encoder = Some((encoder, canvas));
+        Ok(())
+    }
+}
+
+impl<W: Write> Recorder for GifAnimation<W> {
+    fn record(&mut self, state: &dyn Render) -> Result<(), RenderError> {
+        let frame = state.render();
+        if self.encoder.is_none() {
+            self.start(&frame)?;
+        }
+        let (encoder, canvas) = self.encoder.as_mut().expect("animation is started");
+        let positions = canvas.positions().collect::<Vec<Pos<Coord>>>();
+        for pos in positions {
+            canvas[pos] = frame.get(pos).cloned().unwrap_or(Rgb::BLACK);
+        }
+        let pixels = pixels(canvas, self.scale);
+        let width = (canvas.width() * self.scale) as u16;
+        let height = (canvas.height() * self.scale) as u16;
+        let mut gif_frame = indexed_frame(width, height, &pixels);
+        gif_frame.delay = self.delay;
+        encoder.write_frame(&gif_frame)?;
+        Ok(())
+    }
+}
+
+/// Creates a frame with its own palette of all colors of the pixels or a
+/// quantized palette if there are more than 256 colors.
+fn indexed_frame(width: u16, height: u16, pixels: &[Rgb]) -> Frame<'static> {
+    let mut palette = HashMap::<Rgb, u8>::new();
+    let mut indices = Vec::with_capacity(pixels.len());
+    for &color in pixels {
+        let next_index = palette.len();
+        let index = match palette.get(&color) {
+            Some(&index) => index,
+            None if next_index < 256 => {
+                palette.insert(color, next_index as u8);
+                next_index as u8
+            },
+            None => {
+                let rgb = pixels
+                    .iter()
+                    .flat_map(|&Rgb(red, green, blue)| vec![red, green, blue])
+                    .collect::<Vec<_>>();
+                return Frame::from_rgb_speed(width, height, &rgb, 10);
+            },
+        };
+        indices.push(index);
+    }
+    let mut colors = palette.into_iter().collect::<Vec<_>>();
+    colors.sort_by_key(|&(_, index)| index);
+    let palette = colors
+        .into_iter()
+        .flat_map(|(Rgb(red, green, blue), _)| vec![red, green, blue])
+        .collect::<Vec<_>>();
+    Frame::from_palette_pixels(width, height, indices, palette, None)
+}
+
+#[cfg(test)]
+mod tests;
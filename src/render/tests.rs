@@ -0,0 +1,171 @@
+use super::*;
+
+use std::env;
+
+const RED: Rgb = Rgb(255, 0, 0);
+const BLUE: Rgb = Rgb(0, 0, 255);
+
+struct Frame(Grid<Rgb, Coord>);
+
+impl Render for Frame {
+    fn render(&self) -> Grid<Rgb, Coord> {
+        self.0.clone()
+    }
+}
+
+fn frame(origin: Pos<Coord>, width: usize, height: usize, dots: &[(Coord, Coord)]) -> Frame {
+    let mut grid = Grid::with_origin(origin, width, height, BLUE);
+    for &(x, y) in dots {
+        grid[Pos::new(x, y)] = RED;
+    }
+    Frame(grid)
+}
+
+fn decode_gif(bytes: &[u8]) -> Vec<(u16, u16, Vec<u8>)> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(bytes).unwrap();
+    let mut frames = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        frames.push((frame.width, frame.height, frame.buffer.to_vec()));
+    }
+    frames
+}
+
+mod canvas {
+    use super::*;
+
+    #[test]
+    fn covers_corners() {
+        let canvas = canvas(Some((Pos::new(-1, 2), Pos::new(3, 2))), RED);
+
+        assert_eq!(canvas.bounds(), Some((Pos::new(-1, 2), Pos::new(3, 2))));
+        assert_eq!(canvas[Pos::new(0, 2)], RED);
+    }
+
+    #[test]
+    fn without_corners_is_empty() {
+        let canvas = canvas(None, RED);
+
+        assert_eq!(canvas.bounds(), None);
+    }
+}
+
+mod ppm {
+    use super::*;
+
+    #[test]
+    fn write_scaled_frame() {
+        let Frame(grid) = frame(Pos::new(0, 0), 2, 1, &[(1, 0)]);
+        let mut bytes = Vec::new();
+
+        write_ppm(&mut bytes, &grid, 2).unwrap();
+
+        let mut expected = b"P6\n4 2\n255\n".to_vec();
+        for _ in 0..2 {
+            expected.extend_from_slice(&[0, 0, 255, 0, 0, 255, 255, 0, 0, 255, 0, 0]);
+        }
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn record_numbered_frames() {
+        let dir = env::temp_dir().join(format!("aoc2018-render-{}", std::process::id()));
+        let mut recorder = PpmFrames::new(&dir, "frame-", 1);
+
+        recorder.record(&frame(Pos::new(0, 0), 1, 1, &[])).unwrap();
+        recorder
+            .record(&frame(Pos::new(0, 0), 1, 1, &[(0, 0)]))
+            .unwrap();
+
+        assert_eq!(recorder.num_frames(), 2);
+        assert_eq!(recorder.path(1), dir.join("frame-00001.ppm"));
+        assert_eq!(
+            fs::read(recorder.path(1)).unwrap(),
+            b"P6\n1 1\n255\n\xff\x00\x00".to_vec()
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
+
+mod gif_animation {
+    use super::*;
+
+    #[test]
+    fn record_frames() {
+        let mut animation = GifAnimation::new(Vec::new(), 1, Duration::from_millis(200));
+
+        animation
+            .record(&frame(Pos::new(0, 0), 2, 1, &[(0, 0)]))
+            .unwrap();
+        animation
+            .record(&frame(Pos::new(0, 0), 2, 1, &[(1, 0)]))
+            .unwrap();
+        let bytes = animation.finish().unwrap();
+
+        let frames = decode_gif(&bytes);
+        assert_eq!(
+            frames,
+            vec![
+                (2, 1, vec![255, 0, 0, 255, 0, 0, 255, 255]),
+                (2, 1, vec![0, 0, 255, 255, 255, 0, 0, 255]),
+            ]
+        );
+    }
+
+    #[test]
+    fn later_frames_are_aligned_to_first_frame() {
+        let mut animation = GifAnimation::new(Vec::new(), 1, Duration::from_millis(100));
+
+        animation.record(&frame(Pos::new(0, 0), 2, 1, &[])).unwrap();
+        animation
+            .record(&frame(Pos::new(1, 0), 2, 1, &[(1, 0)]))
+            .unwrap();
+        let bytes = animation.finish().unwrap();
+
+        let frames = decode_gif(&bytes);
+        assert_eq!(frames[1], (2, 1, vec![0, 0, 0, 255, 255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn finish_without_frames() {
+        let animation = GifAnimation::new(Vec::new(), 1, Duration::from_millis(100));
+
+        let result = animation.finish();
+
+        assert!(matches!(result, Err(RenderError::NoFrames)));
+    }
+
+    #[test]
+    fn frame_too_large() {
+        let mut animation = GifAnimation::new(Vec::new(), 1_000, Duration::from_millis(100));
+
+        let result = animation.record(&frame(Pos::new(0, 0), 70, 1, &[]));
+
+        assert!(matches!(
+            result,
+            Err(RenderError::FrameTooLarge {
+                width: 70_000,
+                height: 1_000
+            })
+        ));
+    }
+}
+
+mod simulations {
+    use super::*;
+
+    use crate::day18;
+
+    #[test]
+    fn render_lumber_area() {
+        let area = day18::try_parse(".#\n|.\n").unwrap();
+
+        let frame = area.render();
+
+        assert_eq!(frame.bounds(), Some((Pos::new(0, 0), Pos::new(1, 1))));
+        assert_eq!(frame[Pos::new(1, 0)], Rgb(110, 70, 30));
+        assert_eq!(frame[Pos::new(0, 1)], Rgb(34, 139, 34));
+        assert_eq!(frame[Pos::new(1, 1)], Rgb(200, 180, 120));
+    }
+}
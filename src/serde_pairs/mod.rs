@@ -0,0 +1,29 @@
+//! Serializes a map as a sequence of key-value pairs.
+//!
+//! Formats like JSON only allow strings as keys of a map. Maps keyed by a
+//! position or another compound type are therefore written as a list of
+//! pairs. Use it on a field with `#[serde(with = "crate::serde_pairs")]`.
+
+use std::iter::FromIterator;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub fn serialize<'a, K, V, M, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Serialize + 'a,
+    V: Serialize + 'a,
+    &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+    S: Serializer,
+{
+    serializer.collect_seq(map)
+}
+
+pub fn deserialize<'de, K, V, M, D>(deserializer: D) -> Result<M, D::Error>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    M: FromIterator<(K, V)>,
+    D: Deserializer<'de>,
+{
+    Vec::<(K, V)>::deserialize(deserializer).map(|pairs| pairs.into_iter().collect())
+}
@@ -0,0 +1,95 @@
+//! Disjoint sets of indices, also known as union-find.
+//!
+//! Puzzles that group items by a pairwise relation, like overlapping claims or
+//! nearby points, number the items and merge the sets of related items.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Disjoint sets of the indices `0..len` with union by rank and path
+/// compression.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    num_sets: usize,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            num_sets: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+
+    /// Returns the representative of the set containing the given element.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merges the sets containing the two elements. Returns `false` if both
+    /// elements have already been in the same set.
+    pub fn union(&mut self, element1: usize, element2: usize) -> bool {
+        let root1 = self.find(element1);
+        let root2 = self.find(element2);
+        if root1 == root2 {
+            return false;
+        }
+        if self.ranks[root1] < self.ranks[root2] {
+            self.parents[root1] = root2;
+        } else {
+            self.parents[root2] = root1;
+            if self.ranks[root1] == self.ranks[root2] {
+                self.ranks[root1] += 1;
+            }
+        }
+        self.num_sets -= 1;
+        true
+    }
+
+    /// Returns the elements of each set. Sets are ordered by their smallest
+    /// element and the elements of a set are in ascending order.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut set_of_root: Vec<Option<usize>> = vec![None; self.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::with_capacity(self.num_sets);
+        for element in 0..self.len() {
+            let root = self.find(element);
+            match set_of_root[root] {
+                Some(idx) => sets[idx].push(element),
+                None => {
+                    set_of_root[root] = Some(sets.len());
+                    sets.push(vec![element]);
+                },
+            }
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests;
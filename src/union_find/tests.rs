@@ -0,0 +1,15 @@
+use super::*;
+
+#[test]
+fn union_and_find() {
+    let mut union_find = UnionFind::new(5);
+
+    assert!(union_find.union(0, 3));
+    assert!(union_find.union(4, 3));
+    assert!(!union_find.union(0, 4));
+
+    assert_eq!(union_find.num_sets(), 3);
+    assert_eq!(union_find.find(0), union_find.find(4));
+    assert_ne!(union_find.find(1), union_find.find(2));
+    assert_eq!(union_find.sets(), vec![vec![0, 3, 4], vec![1], vec![2]]);
+}
@@ -0,0 +1,140 @@
+//! Solves all puzzles for the inputs in `input/2018` and compares the answers
+//! to the known answers listed in `input/2018/answers.txt`.
+//!
+//! The slow puzzles are only solved when the ignored tests are run:
+//!
+//! ```text
+//! cargo test --release --test answers -- --ignored
+//! ```
+
+use std::{fs, path::PathBuf};
+
+use advent_of_code_2018::registry;
+
+const ANSWERS: &str = include_str!("../input/2018/answers.txt");
+
+#[derive(Debug, Clone, PartialEq)]
+struct KnownAnswer {
+    day: u8,
+    part: u8,
+    slow: bool,
+    answer: String,
+}
+
+fn parse_heading(line_no: usize, heading: &str) -> (u8, u8, bool) {
+    let words = heading.split_whitespace().collect::<Vec<_>>();
+    let number = |word: &str| {
+        word.parse()
+            .unwrap_or_else(|_| panic!("line {}: not a number: {:?}", line_no, word))
+    };
+    match words[..] {
+        ["day", day, "part", part] => (number(day), number(part), false),
+        ["day", day, "part", part, "slow"] => (number(day), number(part), true),
+        _ => panic!("line {}: invalid heading: {:?}", line_no, heading),
+    }
+}
+
+fn known_answers() -> Vec<KnownAnswer> {
+    let mut known_answers = Vec::<KnownAnswer>::new();
+    for (line_no, line) in ANSWERS
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line))
+    {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(answer_line) = line.strip_prefix("    ") {
+            let known = known_answers
+                .last_mut()
+                .unwrap_or_else(|| panic!("line {}: answer without heading", line_no));
+            if !known.answer.is_empty() {
+                known.answer.push('\n');
+            }
+            known.answer.push_str(answer_line);
+            continue;
+        }
+        let colon = line
+            .find(':')
+            .unwrap_or_else(|| panic!("line {}: missing colon", line_no));
+        let (day, part, slow) = parse_heading(line_no, &line[..colon]);
+        known_answers.push(KnownAnswer {
+            day,
+            part,
+            slow,
+            answer: line[colon + 1..].trim().to_string(),
+        });
+    }
+    known_answers
+}
+
+fn read_input(day: u8) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("input/2018")
+        .join(format!("day{}.txt", day));
+    fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("can not read {}: {}", path.display(), err))
+}
+
+/// Solves the puzzles that are either slow or not and reports all answers that
+/// differ from the known ones.
+fn check_answers(slow: bool) {
+    let mismatches = known_answers()
+        .into_iter()
+        .filter(|known| known.slow == slow)
+        .filter_map(|known| {
+            let input = read_input(known.day);
+            match registry::solve(known.day, known.part, &input) {
+                Ok(answer) if answer.trim() == known.answer => None,
+                Ok(answer) => Some(format!(
+                    "day {} part {}: expected {}, got {}",
+                    known.day,
+                    known.part,
+                    known.answer,
+                    answer.trim()
+                )),
+                Err(err) => Some(format!("day {} part {}: {}", known.day, known.part, err)),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    assert!(
+        mismatches.is_empty(),
+        "{} wrong answer(s):\n{}",
+        mismatches.len(),
+        mismatches.join("\n")
+    );
+}
+
+#[test]
+fn known_answers_are_listed_once_for_implemented_puzzles() {
+    let known_answers = known_answers();
+
+    for (idx, known) in known_answers.iter().enumerate() {
+        assert!(
+            registry::find(known.day, known.part).is_some(),
+            "there is no puzzle for day {} part {}",
+            known.day,
+            known.part
+        );
+        assert!(
+            known_answers[..idx]
+                .iter()
+                .all(|other| (other.day, other.part) != (known.day, known.part)),
+            "day {} part {} is listed twice",
+            known.day,
+            known.part
+        );
+    }
+}
+
+#[test]
+fn answers() {
+    check_answers(false);
+}
+
+#[test]
+#[ignore]
+fn answers_of_slow_puzzles() {
+    check_answers(true);
+}
@@ -0,0 +1,117 @@
+//! Checks that the parsed puzzle inputs and the states of the simulations
+//! survive a round trip through JSON.
+//!
+//! ```text
+//! cargo test --features serde --test serde
+//! ```
+
+#![cfg(feature = "serde")]
+
+use advent_of_code_2018::*;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::json;
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+macro_rules! parsed_input_round_trip {
+    ($($test:ident: $day:ident => $input:literal),+ $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                let parsed = $day::try_parse(include_str!($input)).unwrap();
+
+                assert_eq!(round_trip(&parsed), parsed);
+            }
+        )+
+    };
+}
+
+mod parsed_input {
+    use super::*;
+
+    parsed_input_round_trip! {
+        day03: day03 => "../input/2018/day3.txt",
+        day04: day04 => "../input/2018/day4.txt",
+        day06: day06 => "../input/2018/day6.txt",
+        day07: day07 => "../input/2018/day7.txt",
+        day08: day08 => "../input/2018/day8.txt",
+        day09: day09 => "../input/2018/day9.txt",
+        day10: day10 => "../input/2018/day10.txt",
+        day11: day11 => "../input/2018/day11.txt",
+        day12: day12 => "../input/2018/day12.txt",
+        day13: day13 => "../input/2018/day13.txt",
+        day14: day14 => "../input/2018/day14.txt",
+        day15: day15 => "../input/2018/day15.txt",
+        day16: day16 => "../input/2018/day16.txt",
+        day17: day17 => "../input/2018/day17.txt",
+        day18: day18 => "../input/2018/day18.txt",
+        day19: day19 => "../input/2018/day19.txt",
+        day20: day20 => "../input/2018/day20.txt",
+        day21: day21 => "../input/2018/day21.txt",
+        day23: day23 => "../input/2018/day23.txt",
+        day24: day24 => "../input/2018/day24.txt",
+        day25: day25 => "../input/2018/day25.txt",
+    }
+
+    #[test]
+    fn day22() {
+        let cave = day22::try_parse(include_str!("../input/2018/day22.txt")).unwrap();
+
+        let restored = round_trip(&cave);
+
+        assert_eq!(
+            day22::total_risk_level(&restored),
+            day22::total_risk_level(&cave)
+        );
+    }
+}
+
+mod simulation_state {
+    use super::*;
+
+    const COMBAT: &str = "\
+#######
+#.G...#
+#...EG#
+#.#.#G#
+#..G#E#
+#.....#
+#######
+";
+
+    #[test]
+    fn resume_lumber_area_from_checkpoint() {
+        let area = day18::try_parse(include_str!("../input/2018/day18.txt")).unwrap();
+        let checkpoint = area.nth_generation(5);
+
+        let resumed = round_trip(&checkpoint).nth_generation(5);
+
+        assert_eq!(resumed, checkpoint.nth_generation(5));
+    }
+
+    #[test]
+    fn resume_combat_from_checkpoint() {
+        let mut combat = day15::try_parse(COMBAT).unwrap();
+        combat.n_fights(10);
+
+        let mut resumed = round_trip(&combat);
+
+        assert_eq!(resumed.n_fights(5), combat.n_fights(5));
+        assert_eq!(resumed, combat);
+    }
+
+    #[test]
+    fn map_with_positions_is_a_list_of_pairs() {
+        let area = day18::try_parse("..\n.#\n").unwrap();
+
+        let json = serde_json::to_value(&area).unwrap();
+
+        assert_eq!(
+            json,
+            json!({ "resources": [[{ "x": 1, "y": 1 }, "Lumberyard"]] })
+        );
+    }
+}